@@ -0,0 +1,244 @@
+// on stable toolchains we provide our own copy of the `Allocator` API whose signatures match the unstable one in
+// `core::alloc` exactly, which means the rest of the crate is written the same way regardless of which is in use
+//
+
+#[cfg(feature = "minivec_nightly")]
+pub use alloc::alloc::{AllocError, Allocator, Global};
+
+#[cfg(not(feature = "minivec_nightly"))]
+pub use self::stable::{AllocError, Allocator, Global};
+
+#[cfg(not(feature = "minivec_nightly"))]
+mod stable {
+  extern crate alloc;
+
+  use alloc::alloc::Layout;
+  use core::ptr::NonNull;
+
+  /// `AllocError` is returned by an [`Allocator`] when it's unable to satisfy a request for memory.
+  ///
+  /// When compiled with the `minivec_nightly` feature this is instead a re-export of `core::alloc::AllocError`.
+  ///
+  #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+  pub struct AllocError;
+
+  impl core::fmt::Display for AllocError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      f.write_str("memory allocation failed")
+    }
+  }
+
+  /// `Allocator` is a stable stand-in for the unstable `core::alloc::Allocator` trait and is used to parameterize
+  /// [`MiniVec`](crate::MiniVec) over where its memory comes from.
+  ///
+  /// The required and provided methods mirror the ones in `core::alloc` so that implementations can be moved over
+  /// verbatim once the trait is stabilized. When compiled with the `minivec_nightly` feature this is instead a
+  /// re-export of `core::alloc::Allocator`.
+  ///
+  /// # Safety
+  ///
+  /// Memory blocks returned from an allocator must point to valid memory and retain their validity until the block is
+  /// deallocated, either directly or through `grow` and `shrink`. Moving or cloning the allocator must not invalidate
+  /// the memory blocks it has returned.
+  ///
+  pub unsafe trait Allocator {
+    /// `allocate` attempts to allocate a block of memory that fits `layout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AllocError`] if the memory could not be allocated.
+    ///
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// `deallocate` releases the memory referenced by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator and `layout` must be the layout it
+    /// was allocated with.
+    ///
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// `grow` attempts to extend the memory block referenced by `ptr` so that it fits `new_layout`, preserving its
+    /// contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AllocError`] if the memory could not be allocated, in which case the old block is untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator with `old_layout` and
+    /// `new_layout.size()` must be greater than or equal to `old_layout.size()`.
+    ///
+    unsafe fn grow(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      debug_assert!(new_layout.size() >= old_layout.size());
+
+      let new_ptr = self.allocate(new_layout)?;
+      core::ptr::copy_nonoverlapping(
+        ptr.as_ptr(),
+        new_ptr.cast::<u8>().as_ptr(),
+        old_layout.size(),
+      );
+      self.deallocate(ptr, old_layout);
+
+      Ok(new_ptr)
+    }
+
+    /// `shrink` attempts to reduce the memory block referenced by `ptr` so that it fits `new_layout`, preserving as
+    /// much of its contents as fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AllocError`] if the memory could not be allocated, in which case the old block is untouched.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated by this allocator with `old_layout` and
+    /// `new_layout.size()` must be less than or equal to `old_layout.size()`.
+    ///
+    unsafe fn shrink(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      debug_assert!(new_layout.size() <= old_layout.size());
+
+      let new_ptr = self.allocate(new_layout)?;
+      core::ptr::copy_nonoverlapping(
+        ptr.as_ptr(),
+        new_ptr.cast::<u8>().as_ptr(),
+        new_layout.size(),
+      );
+      self.deallocate(ptr, old_layout);
+
+      Ok(new_ptr)
+    }
+  }
+
+  unsafe impl<A> Allocator for &A
+  where
+    A: Allocator + ?Sized,
+  {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+      (**self).allocate(layout)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+      (**self).deallocate(ptr, layout);
+    }
+
+    #[inline]
+    unsafe fn grow(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      (**self).grow(ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn shrink(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      (**self).shrink(ptr, old_layout, new_layout)
+    }
+  }
+
+  /// `Global` is the default [`Allocator`] and forwards every request to the global allocator, i.e. whatever is
+  /// registered with `#[global_allocator]`.
+  ///
+  /// When compiled with the `minivec_nightly` feature this is instead a re-export of `alloc::alloc::Global`.
+  ///
+  #[derive(Copy, Clone, Default, Debug)]
+  pub struct Global;
+
+  unsafe impl Allocator for Global {
+    #[inline]
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+      // every layout `MiniVec` requests contains at least its header so zero-sized requests are exceedingly rare but
+      // they're still valid and must not be forwarded to the global allocator
+      //
+      if layout.size() == 0 {
+        let dangling = unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+        return Ok(NonNull::slice_from_raw_parts(dangling, 0));
+      }
+
+      let p = unsafe { alloc::alloc::alloc(layout) };
+      NonNull::new(p)
+        .map(|p| NonNull::slice_from_raw_parts(p, layout.size()))
+        .ok_or(AllocError)
+    }
+
+    #[inline]
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+      if layout.size() > 0 {
+        alloc::alloc::dealloc(ptr.as_ptr(), layout);
+      }
+    }
+
+    #[inline]
+    unsafe fn grow(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      debug_assert!(new_layout.size() >= old_layout.size());
+
+      if old_layout.size() == 0 || old_layout.align() != new_layout.align() {
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(
+          ptr.as_ptr(),
+          new_ptr.cast::<u8>().as_ptr(),
+          old_layout.size(),
+        );
+        self.deallocate(ptr, old_layout);
+        return Ok(new_ptr);
+      }
+
+      let p = alloc::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size());
+      NonNull::new(p)
+        .map(|p| NonNull::slice_from_raw_parts(p, new_layout.size()))
+        .ok_or(AllocError)
+    }
+
+    #[inline]
+    unsafe fn shrink(
+      &self,
+      ptr: NonNull<u8>,
+      old_layout: Layout,
+      new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+      debug_assert!(new_layout.size() <= old_layout.size());
+
+      if new_layout.size() == 0 || old_layout.align() != new_layout.align() {
+        let new_ptr = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(
+          ptr.as_ptr(),
+          new_ptr.cast::<u8>().as_ptr(),
+          new_layout.size(),
+        );
+        self.deallocate(ptr, old_layout);
+        return Ok(new_ptr);
+      }
+
+      let p = alloc::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size());
+      NonNull::new(p)
+        .map(|p| NonNull::slice_from_raw_parts(p, new_layout.size()))
+        .ok_or(AllocError)
+    }
+  }
+}
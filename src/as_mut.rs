@@ -1,13 +1,20 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> core::convert::AsMut<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::convert::AsMut<[T]>
+  for MiniVec<T, A, H, G>
+{
   fn as_mut(&mut self) -> &mut [T] {
     &mut *self
   }
 }
 
-impl<T> core::convert::AsMut<MiniVec<T>> for MiniVec<T> {
-  fn as_mut(&mut self) -> &mut MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::convert::AsMut<MiniVec<T, A, H, G>>
+  for MiniVec<T, A, H, G>
+{
+  fn as_mut(&mut self) -> &mut MiniVec<T, A, H, G> {
     self
   }
 }
@@ -1,13 +1,18 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> AsRef<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> AsRef<[T]> for MiniVec<T, A, H, G> {
   fn as_ref(&self) -> &[T] {
     self
   }
 }
 
-impl<T> AsRef<MiniVec<T>> for MiniVec<T> {
-  fn as_ref(&self) -> &MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> AsRef<MiniVec<T, A, H, G>>
+  for MiniVec<T, A, H, G>
+{
+  fn as_ref(&self) -> &MiniVec<T, A, H, G> {
     self
   }
 }
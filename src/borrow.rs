@@ -1,12 +1,19 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> core::borrow::Borrow<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::borrow::Borrow<[T]>
+  for MiniVec<T, A, H, G>
+{
   fn borrow(&self) -> &[T] {
     &(self[..])
   }
 }
 
-impl<T> core::borrow::BorrowMut<[T]> for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::borrow::BorrowMut<[T]>
+  for MiniVec<T, A, H, G>
+{
   fn borrow_mut(&mut self) -> &mut [T] {
     &mut (self[..])
   }
@@ -1,4 +1,10 @@
+#[cfg(not(feature = "no_global_oom_handling"))]
+use crate::handle_reserve;
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
+use crate::TryReserveError;
 
 // We take tons of inspiration from the stdlib here but also choose to optimize
 // based on the underlying type needing to be dropped or not
@@ -7,24 +13,24 @@ use crate::MiniVec;
 // by avoiding the store to the `DropGuard { len }` data member.
 //
 
-fn to_vec<T: Clone>(xs: &[T]) -> MiniVec<T> {
-  struct DropGuard<'a, T> {
-    pub vec: &'a mut MiniVec<T>,
+pub(crate) fn try_to_vec<T: Clone, A: Allocator + Clone, H: IndexType, G: GrowthPolicy>(
+  v: &MiniVec<T, A, H, G>,
+) -> Result<MiniVec<T, A, H, G>, TryReserveError> {
+  struct DropGuard<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> {
+    pub vec: &'a mut MiniVec<T, A, H, G>,
     pub len: usize,
   }
 
-  impl<'a, T> Drop for DropGuard<'a, T> {
+  impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for DropGuard<'_, T, A, H, G> {
     fn drop(&mut self) {
       unsafe { self.vec.set_len(self.len) };
     }
   }
 
-  if xs.is_empty() {
-    return MiniVec::<T>::new();
-  }
+  let xs = v.as_slice();
 
   let len = xs.len();
-  let mut cpy = MiniVec::<T>::with_capacity(len);
+  let mut cpy = v.try_with_capacity_like(len)?;
 
   if core::mem::needs_drop::<T>() {
     let mut guard = DropGuard {
@@ -52,21 +58,21 @@ fn to_vec<T: Clone>(xs: &[T]) -> MiniVec<T> {
     unsafe { cpy.set_len(len) };
   }
 
-  cpy
+  Ok(cpy)
 }
 
-#[cfg(feature = "minivec_nightly")]
-impl<T: Clone> Clone for MiniVec<T> {
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
+impl<T: Clone, A: Allocator + Clone, H: IndexType, G: GrowthPolicy> Clone for MiniVec<T, A, H, G> {
   default fn clone(&self) -> Self {
-    to_vec(self.as_slice())
+    handle_reserve(try_to_vec(self))
   }
 }
 
-#[cfg(feature = "minivec_nightly")]
-impl<T: Copy> Clone for MiniVec<T> {
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
+impl<T: Copy, A: Allocator + Clone, H: IndexType, G: GrowthPolicy> Clone for MiniVec<T, A, H, G> {
   fn clone(&self) -> Self {
     let len = self.len();
-    let mut cpy = MiniVec::<T>::with_capacity(len);
+    let mut cpy = handle_reserve(self.try_with_capacity_like(len));
 
     let src = self.as_ptr();
     let dst = cpy.as_mut_ptr();
@@ -78,9 +84,12 @@ impl<T: Copy> Clone for MiniVec<T> {
   }
 }
 
-#[cfg(not(feature = "minivec_nightly"))]
-impl<T: Clone> Clone for MiniVec<T> {
+#[cfg(all(
+  not(feature = "minivec_nightly"),
+  not(feature = "no_global_oom_handling")
+))]
+impl<T: Clone, A: Allocator + Clone, H: IndexType, G: GrowthPolicy> Clone for MiniVec<T, A, H, G> {
   fn clone(&self) -> Self {
-    to_vec(self.as_slice())
+    handle_reserve(try_to_vec(self))
   }
 }
@@ -1,8 +1,13 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T: core::fmt::Debug> core::fmt::Debug for MiniVec<T> {
+impl<T: core::fmt::Debug, A: Allocator, H: IndexType, G: GrowthPolicy> core::fmt::Debug
+  for MiniVec<T, A, H, G>
+{
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let this: &[T] = &*self;
+    let this: &[T] = self;
 
     this.fmt(f)
   }
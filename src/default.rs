@@ -1,7 +1,10 @@
+use crate::Global;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> Default for MiniVec<T> {
+impl<T, H: IndexType, G: GrowthPolicy> Default for MiniVec<T, Global, H, G> {
   fn default() -> Self {
-    Self::new()
+    Self::from_buf(Self::empty_buf())
   }
 }
@@ -1,21 +1,24 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> core::ops::Deref for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::ops::Deref for MiniVec<T, A, H, G> {
   type Target = [T];
 
   fn deref(&self) -> &Self::Target {
     let header = self.header();
     let data = self.data();
-    let len = header.len;
+    let len = header.len.to_usize();
     unsafe { core::slice::from_raw_parts(data, len) }
   }
 }
 
-impl<T> core::ops::DerefMut for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::ops::DerefMut for MiniVec<T, A, H, G> {
   fn deref_mut(&mut self) -> &mut Self::Target {
     let header = self.header();
     let data = self.data();
-    let len = header.len;
+    let len = header.len.to_usize();
     unsafe { core::slice::from_raw_parts_mut(data, len) }
   }
 }
@@ -1,23 +1,30 @@
-use crate::make_layout;
-use crate::Header;
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-extern crate alloc;
-
 // TODO: someday update this impl to be:
 // unsafe impl<#[may_dangle] T> for MiniVec<T>
 //
 // so that tests will pass for `test_vec_cycle`
 //
 
-impl<T> Drop for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for MiniVec<T, A, H, G> {
   fn drop(&mut self) {
+    // zero-sized allocators aren't stored anywhere but the vector still owns one
+    //
+    if self.is_default() {
+      unsafe { core::ptr::drop_in_place(core::ptr::NonNull::<A>::dangling().as_ptr()) };
+      return;
+    }
+
+    let (p, layout) = self.allocation();
+
     unsafe {
-      #[allow(clippy::cast_ptr_alignment)]
-      let Header { len, cap } = core::ptr::read(self.buf.as_ptr().cast::<Header>());
+      core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.data(), self.len()));
 
-      core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.data(), len));
-      alloc::alloc::dealloc(self.buf.as_ptr(), make_layout::<T>(cap));
+      let alloc = core::ptr::read(self.allocator());
+      alloc.deallocate(p, layout);
     };
   }
 }
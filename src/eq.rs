@@ -1,3 +1,9 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> core::cmp::Eq for MiniVec<T> where T: core::cmp::Eq {}
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::cmp::Eq for MiniVec<T, A, H, G> where
+  T: core::cmp::Eq
+{
+}
@@ -1,6 +1,10 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<'a, T> core::iter::Extend<&'a T> for MiniVec<T>
+impl<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::Extend<&'a T>
+  for MiniVec<T, A, H, G>
 where
   T: 'a + core::marker::Copy,
 {
@@ -8,17 +12,23 @@ where
   where
     I: core::iter::IntoIterator<Item = &'a T>,
   {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+
     for &x in iter {
       self.push(x);
     }
   }
 }
 
-impl<T> core::iter::Extend<T> for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::Extend<T> for MiniVec<T, A, H, G> {
   fn extend<I>(&mut self, iter: I)
   where
     I: core::iter::IntoIterator<Item = T>,
   {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+
     for x in iter {
       self.push(x);
     }
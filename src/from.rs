@@ -1,7 +1,11 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
 extern crate alloc;
 
+#[cfg(not(feature = "no_global_oom_handling"))]
 impl<'a, T> core::convert::From<&'a [T]> for MiniVec<T>
 where
   T: Clone,
@@ -16,6 +20,7 @@ where
   }
 }
 
+#[cfg(not(feature = "no_global_oom_handling"))]
 impl<'a, T> core::convert::From<&'a mut [T]> for MiniVec<T>
 where
   T: Clone,
@@ -30,6 +35,7 @@ where
   }
 }
 
+#[cfg(not(feature = "no_global_oom_handling"))]
 impl<'a> core::convert::From<&'a str> for MiniVec<u8> {
   fn from(s: &'a str) -> Self {
     let mut v = MiniVec::with_capacity(s.len());
@@ -42,11 +48,12 @@ impl<'a> core::convert::From<&'a str> for MiniVec<u8> {
   }
 }
 
-impl<'a, T> core::convert::From<&'a MiniVec<T>> for alloc::borrow::Cow<'a, [T]>
+impl<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy>
+  core::convert::From<&'a MiniVec<T, A, H, G>> for alloc::borrow::Cow<'a, [T]>
 where
   T: Clone,
 {
-  fn from(v: &'a MiniVec<T>) -> alloc::borrow::Cow<'a, [T]> {
+  fn from(v: &'a MiniVec<T, A, H, G>) -> alloc::borrow::Cow<'a, [T]> {
     alloc::borrow::Cow::Borrowed(v.as_slice())
   }
 }
@@ -1,45 +1,67 @@
-use crate::{IntoIter, MiniVec};
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
+use crate::IntoIter;
+use crate::MiniVec;
+#[cfg(not(feature = "no_global_oom_handling"))]
+use crate::{handle_reserve, Global};
+use crate::{Allocator, GrowthPolicy, IndexType, TryReserveError};
+
+// the fallible core of `FromIterator`, shared with `MiniVec::try_from_iter()`
+//
+pub(crate) fn try_from_iter_in<T, A, H, G, I>(
+  iter: I,
+  alloc: A,
+) -> Result<MiniVec<T, A, H, G>, TryReserveError>
+where
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
+  I: Iterator<Item = T>,
+{
+  struct DropGuard<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> {
+    v: &'a mut MiniVec<T, A, H, G>,
+    len: usize,
+  }
 
-#[cfg(not(feature = "minivec_nightly"))]
-impl<T> core::iter::FromIterator<T> for MiniVec<T> {
-  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-    struct DropGuard<'a, T> {
-      v: &'a mut MiniVec<T>,
-      len: usize,
+  impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for DropGuard<'_, T, A, H, G> {
+    fn drop(&mut self) {
+      unsafe { self.v.set_len(self.len) };
     }
+  }
 
-    impl<'a, T> Drop for DropGuard<'a, T> {
-      fn drop(&mut self) {
-        unsafe { self.v.set_len(self.len) };
-      }
-    }
+  let (lower_bound, _) = iter.size_hint();
+  let mut v = MiniVec::<T, A, H, G>::try_with_capacity_in(lower_bound.saturating_add(1), alloc)?;
 
-    let iter = iter.into_iter();
-    let (lower_bound, _) = iter.size_hint();
-    let mut v = MiniVec::<T>::with_capacity(lower_bound.saturating_add(1));
+  let mut guard = DropGuard { v: &mut v, len: 0 };
 
-    let mut guard = DropGuard { v: &mut v, len: 0 };
+  let mut capacity = guard.v.capacity();
 
-    let mut capacity = guard.v.capacity();
+  for item in iter {
+    if guard.len >= capacity {
+      guard.v.try_grow_amortized(guard.len + 1)?;
+      capacity = guard.v.capacity();
+    }
 
-    iter.for_each(|item| {
-      if guard.len >= capacity {
-        guard.v.grow(crate::next_capacity::<T>(capacity)).unwrap();
-        capacity = guard.v.capacity();
-      }
+    unsafe { core::ptr::write(guard.v.as_mut_ptr().add(guard.len), item) };
+    guard.len += 1;
+  }
 
-      unsafe { core::ptr::write(guard.v.as_mut_ptr().add(guard.len), item) };
-      guard.len += 1;
-    });
+  unsafe { guard.v.set_len(guard.len) };
+  core::mem::forget(guard);
 
-    unsafe { guard.v.set_len(guard.len) };
-    core::mem::forget(guard);
+  Ok(v)
+}
 
-    v
+#[cfg(all(
+  not(feature = "minivec_nightly"),
+  not(feature = "no_global_oom_handling")
+))]
+impl<T, H: IndexType, G: GrowthPolicy> core::iter::FromIterator<T> for MiniVec<T, Global, H, G> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    handle_reserve(try_from_iter_in(iter.into_iter(), Global))
   }
 }
 
-#[cfg(feature = "minivec_nightly")]
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
 trait MiniVecFromIter<T, I>
 where
   I: Iterator<Item = T>,
@@ -47,45 +69,14 @@ where
   fn from_iter(iter: I) -> MiniVec<T>;
 }
 
-#[cfg(feature = "minivec_nightly")]
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
 impl<T, I: Iterator<Item = T>> MiniVecFromIter<T, I> for MiniVec<T> {
   default fn from_iter(iter: I) -> MiniVec<T> {
-    struct DropGuard<'a, T> {
-      v: &'a mut MiniVec<T>,
-      len: usize,
-    }
-
-    impl<'a, T> Drop for DropGuard<'a, T> {
-      fn drop(&mut self) {
-        unsafe { self.v.set_len(self.len) };
-      }
-    }
-
-    let (lower_bound, _) = iter.size_hint();
-    let mut v = MiniVec::<T>::with_capacity(lower_bound.saturating_add(1));
-
-    let mut guard = DropGuard { v: &mut v, len: 0 };
-
-    let mut capacity = guard.v.capacity();
-
-    iter.for_each(|item| {
-      if guard.len >= capacity {
-        guard.v.grow(crate::next_capacity::<T>(capacity)).unwrap();
-        capacity = guard.v.capacity();
-      }
-
-      unsafe { core::ptr::write(guard.v.as_mut_ptr().add(guard.len), item) };
-      guard.len += 1;
-    });
-
-    unsafe { guard.v.set_len(guard.len) };
-    core::mem::forget(guard);
-
-    v
+    handle_reserve(try_from_iter_in(iter, Global))
   }
 }
 
-#[cfg(feature = "minivec_nightly")]
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
 impl<T, I: core::iter::TrustedLen<Item = T>> MiniVecFromIter<T, I> for MiniVec<T> {
   fn from_iter(iter: I) -> MiniVec<T> {
     let (lower_bound, _) = iter.size_hint();
@@ -101,7 +92,7 @@ impl<T, I: core::iter::TrustedLen<Item = T>> MiniVecFromIter<T, I> for MiniVec<T
   }
 }
 
-#[cfg(feature = "minivec_nightly")]
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
 impl<T> MiniVecFromIter<T, IntoIter<T>> for MiniVec<T> {
   fn from_iter(mut iter: IntoIter<T>) -> MiniVec<T> {
     let pos = iter.pos;
@@ -121,7 +112,7 @@ impl<T> MiniVecFromIter<T, IntoIter<T>> for MiniVec<T> {
   }
 }
 
-#[cfg(feature = "minivec_nightly")]
+#[cfg(all(feature = "minivec_nightly", not(feature = "no_global_oom_handling")))]
 impl<T> core::iter::FromIterator<T> for MiniVec<T> {
   fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
     let iter = iter.into_iter();
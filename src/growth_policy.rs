@@ -0,0 +1,221 @@
+/// `GrowthPolicy` decides how much capacity a [`MiniVec`](crate::MiniVec) asks for whenever it runs out of room.
+///
+/// It's selected per vector type through the fourth type parameter of `MiniVec`, which defaults to [`Doubling`]. The
+/// policy is consulted by [`push`](crate::MiniVec::push), [`insert`](crate::MiniVec::insert),
+/// [`reserve`](crate::MiniVec::reserve) and [`try_reserve`](crate::MiniVec::try_reserve) as well as the `Extend` and
+/// `FromIterator` implementations. [`reserve_exact`](crate::MiniVec::reserve_exact) and friends bypass it.
+///
+/// A policy only ever suggests a capacity, the vector always allocates at least as many elements as it requires and
+/// never more than its [`IndexType`](crate::IndexType) can count.
+///
+/// Only `MiniVec`, and the types built on top of one, can be given a policy. The crate's other containers, e.g.
+/// `MiniVecDeque`, `MiniBitVec`, `MiniJagged` or `MiniVec2`, always grow like [`Doubling`].
+///
+/// # Example
+///
+/// ```
+/// struct Linear;
+///
+/// impl minivec::GrowthPolicy for Linear {
+///   fn initial_capacity(_elem_size: usize) -> usize {
+///     1
+///   }
+///
+///   fn next_capacity(_elem_size: usize, current: usize, required: usize) -> usize {
+///     core::cmp::max(current + 1, required)
+///   }
+/// }
+///
+/// let mut vec = minivec::MiniVec::<i32, minivec::Global, usize, Linear>::new();
+/// vec.push(1);
+/// assert_eq!(vec.capacity(), 1);
+///
+/// vec.push(2);
+/// assert_eq!(vec.capacity(), 2);
+/// ```
+///
+pub trait GrowthPolicy {
+  /// `initial_capacity` returns the capacity of the first allocation made for a vector whose elements are `elem_size`
+  /// bytes large. A vector which already requires more elements than that allocates exactly as many as it requires.
+  ///
+  fn initial_capacity(elem_size: usize) -> usize;
+
+  /// `next_capacity` returns the capacity a vector with a capacity of `current` should grow to when it needs room for
+  /// at least `required` elements.
+  ///
+  /// A vector only calls this once it has a capacity, i.e. `current` is never `0` as the first allocation is sized by
+  /// [`initial_capacity`](GrowthPolicy::initial_capacity) instead. Returning less than `required` is not an error,
+  /// the vector rounds the result up to `required`.
+  ///
+  fn next_capacity(elem_size: usize, current: usize, required: usize) -> usize;
+}
+
+/// `Doubling` is the default [`GrowthPolicy`] and mirrors the one used by `alloc::vec::Vec`.
+///
+/// The first allocation holds 8 elements for single-byte types, 4 for types up to 1 KiB and 1 otherwise. Every
+/// subsequent allocation doubles the capacity.
+///
+pub struct Doubling;
+
+impl GrowthPolicy for Doubling {
+  fn initial_capacity(elem_size: usize) -> usize {
+    match elem_size {
+      1 => 8,
+      2..=1024 => 4,
+      _ => 1,
+    }
+  }
+
+  fn next_capacity(elem_size: usize, current: usize, required: usize) -> usize {
+    grow_until(
+      current,
+      required,
+      || Self::initial_capacity(elem_size),
+      |c| c.saturating_mul(2),
+    )
+  }
+}
+
+/// `OneAndAHalf` is a [`GrowthPolicy`] that grows the capacity by a factor of 1.5, trading more frequent
+/// reallocations for less unused space than [`Doubling`].
+///
+/// The first allocation is the same as the one made by [`Doubling`].
+///
+pub struct OneAndAHalf;
+
+impl GrowthPolicy for OneAndAHalf {
+  fn initial_capacity(elem_size: usize) -> usize {
+    Doubling::initial_capacity(elem_size)
+  }
+
+  fn next_capacity(elem_size: usize, current: usize, required: usize) -> usize {
+    grow_until(
+      current,
+      required,
+      || Self::initial_capacity(elem_size),
+      |c| c.saturating_add(core::cmp::max(c / 2, 1)),
+    )
+  }
+}
+
+/// `FixedIncrement` is a [`GrowthPolicy`] that grows the capacity `N` elements at a time.
+///
+/// This suits many small vectors that only ever grow by an element or two, where any multiplicative policy wastes
+/// most of what it allocates. An `N` of `0` is treated as `1`.
+///
+pub struct FixedIncrement<const N: usize>;
+
+impl<const N: usize> GrowthPolicy for FixedIncrement<N> {
+  fn initial_capacity(_elem_size: usize) -> usize {
+    core::cmp::max(N, 1)
+  }
+
+  fn next_capacity(_elem_size: usize, current: usize, required: usize) -> usize {
+    let increment = core::cmp::max(N, 1);
+    if required <= current {
+      return current;
+    }
+
+    let steps = (required - current).div_ceil(increment);
+    current.saturating_add(steps.saturating_mul(increment))
+  }
+}
+
+/// `PageRounded` is a [`GrowthPolicy`] that doubles the capacity like [`Doubling`] but then rounds the storage for the
+/// elements up to a whole number of `PAGE`-byte pages.
+///
+/// This suits large buffers, where the allocator hands out whole pages anyway and the slack would otherwise go unused.
+/// `PAGE` defaults to 4 KiB. An element larger than a page is rounded up to a whole number of pages as well.
+///
+pub struct PageRounded<const PAGE: usize = 4096>;
+
+impl<const PAGE: usize> PageRounded<PAGE> {
+  fn round(elem_size: usize, capacity: usize) -> usize {
+    if elem_size == 0 || PAGE == 0 {
+      return capacity;
+    }
+
+    let bytes = capacity.saturating_mul(elem_size);
+    let pages = bytes.div_ceil(PAGE);
+
+    core::cmp::max(pages.saturating_mul(PAGE) / elem_size, capacity)
+  }
+}
+
+impl<const PAGE: usize> GrowthPolicy for PageRounded<PAGE> {
+  fn initial_capacity(elem_size: usize) -> usize {
+    Self::round(elem_size, 1)
+  }
+
+  fn next_capacity(elem_size: usize, current: usize, required: usize) -> usize {
+    let capacity = Doubling::next_capacity(elem_size, current, required);
+    Self::round(elem_size, capacity)
+  }
+}
+
+// repeatedly applies `grow` to the current capacity, or the initial one for a vector that hasn't allocated yet, until
+// it fits `required` elements
+//
+fn grow_until(
+  current: usize,
+  required: usize,
+  initial: impl FnOnce() -> usize,
+  grow: impl Fn(usize) -> usize,
+) -> usize {
+  let mut capacity = if current == 0 { initial() } else { current };
+
+  while capacity < required {
+    let next = grow(capacity);
+    if next <= capacity {
+      return required;
+    }
+
+    capacity = next;
+  }
+
+  capacity
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn doubling_test() {
+    assert_eq!(Doubling::next_capacity(1, 0, 1), 8);
+    assert_eq!(Doubling::next_capacity(4, 0, 1), 4);
+    assert_eq!(Doubling::next_capacity(2048, 0, 1), 1);
+    assert_eq!(Doubling::next_capacity(4, 4, 5), 8);
+    assert_eq!(Doubling::next_capacity(4, 4, 100), 128);
+    assert_eq!(
+      Doubling::next_capacity(4, usize::MAX / 2 + 1, usize::MAX),
+      usize::MAX
+    );
+  }
+
+  #[test]
+  fn one_and_a_half_test() {
+    assert_eq!(OneAndAHalf::next_capacity(4, 0, 1), 4);
+    assert_eq!(OneAndAHalf::next_capacity(4, 4, 5), 6);
+    assert_eq!(OneAndAHalf::next_capacity(4, 1, 2), 2);
+    assert_eq!(OneAndAHalf::next_capacity(4, 6, 10), 13);
+  }
+
+  #[test]
+  fn fixed_increment_test() {
+    assert_eq!(FixedIncrement::<2>::next_capacity(4, 0, 1), 2);
+    assert_eq!(FixedIncrement::<2>::next_capacity(4, 2, 3), 4);
+    assert_eq!(FixedIncrement::<2>::next_capacity(4, 4, 9), 10);
+    assert_eq!(FixedIncrement::<0>::next_capacity(4, 4, 5), 5);
+  }
+
+  #[test]
+  fn page_rounded_test() {
+    assert_eq!(PageRounded::<4096>::initial_capacity(4), 1024);
+    assert_eq!(PageRounded::<4096>::next_capacity(4, 1024, 1025), 2048);
+    assert_eq!(PageRounded::<4096>::next_capacity(3, 1365, 1366), 2730);
+    assert_eq!(PageRounded::<4096>::next_capacity(8192, 0, 1), 1);
+    assert_eq!(PageRounded::<4096>::next_capacity(5000, 0, 1), 1);
+    assert_eq!(PageRounded::<4096>::next_capacity(0, 0, 1), 1);
+  }
+}
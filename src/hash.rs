@@ -1,14 +1,17 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T> core::hash::Hash for MiniVec<T>
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::hash::Hash for MiniVec<T, A, H, G>
 where
   T: core::hash::Hash,
 {
-  fn hash<H>(&self, state: &mut H)
+  fn hash<S>(&self, state: &mut S)
   where
-    H: core::hash::Hasher,
+    S: core::hash::Hasher,
   {
-    let this: &[T] = &**self;
+    let this: &[T] = self;
     core::hash::Hash::hash(this, state);
   }
 }
@@ -0,0 +1,247 @@
+use crate::r#impl::helpers::allocator_offset;
+use crate::{handle_reserve, AllocError, Allocator, Global, MiniVec, TryReserveError};
+
+extern crate alloc;
+
+use alloc::alloc::Layout;
+use core::ptr::NonNull;
+
+// a `HeaderVec` is a `MiniVec` whose allocator carries the user's header, stateful allocators already live in the
+// allocation directly after the `Header` so every editing method of `MiniVec` keeps the user header in place for free:
+//
+// | Header { len, cap } | H | elements ... |
+//
+
+/// `HeaderAlloc` is the [`Allocator`] of the [`MiniVec`] behind a [`HeaderVec`]. It forwards every request to
+/// [`Global`] and only exists to store the user header inside of the vector's allocation.
+///
+#[derive(Clone, Debug)]
+#[repr(transparent)]
+pub struct HeaderAlloc<H> {
+  header: H,
+}
+
+impl<H> HeaderAlloc<H> {
+  /// `header` returns a reference to the user header, which is how the header of a vector split off of a `HeaderVec`
+  /// is reached.
+  ///
+  #[must_use]
+  pub fn header(&self) -> &H {
+    &self.header
+  }
+}
+
+unsafe impl<H> Allocator for HeaderAlloc<H> {
+  #[inline]
+  fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    Global.allocate(layout)
+  }
+
+  #[inline]
+  unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+    Global.deallocate(ptr, layout);
+  }
+
+  #[inline]
+  unsafe fn grow(
+    &self,
+    ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_layout: Layout,
+  ) -> Result<NonNull<[u8]>, AllocError> {
+    Global.grow(ptr, old_layout, new_layout)
+  }
+
+  #[inline]
+  unsafe fn shrink(
+    &self,
+    ptr: NonNull<u8>,
+    old_layout: Layout,
+    new_layout: Layout,
+  ) -> Result<NonNull<[u8]>, AllocError> {
+    Global.shrink(ptr, old_layout, new_layout)
+  }
+}
+
+/// `HeaderVec` is a vector that stores a user-defined header `H` in the same allocation as its elements, right next to
+/// the length and capacity. It's only the size of a single pointer, where a struct holding an `H` and a `MiniVec<T>`
+/// would be the size of both.
+///
+/// A `HeaderVec` dereferences to the [`MiniVec`] it wraps so every method of `MiniVec` is available on it, and the
+/// header moves along with the elements whenever the vector reallocates. Vectors split off of a `HeaderVec`, e.g. by
+/// [`split_off`](MiniVec::split_off), receive a clone of its header.
+///
+/// Unlike a `MiniVec`, a `HeaderVec` allocates as soon as it's created because its header has to live somewhere. Zero-
+/// sized headers aren't stored at all.
+///
+/// # Example
+///
+/// ```
+/// let mut vec = minivec::HeaderVec::new("numbers");
+/// vec.extend_from_slice(&[3, 1, 2]);
+/// vec.sort_unstable();
+///
+/// *vec.header_mut() = "sorted numbers";
+///
+/// assert_eq!(*vec.header(), "sorted numbers");
+/// assert_eq!(vec, [1, 2, 3]);
+/// assert_eq!(core::mem::size_of_val(&vec), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct HeaderVec<H, T> {
+  vec: MiniVec<T, HeaderAlloc<H>>,
+}
+
+impl<H, T> HeaderVec<H, T> {
+  /// `from_raw_part` reconstructs a `HeaderVec` from a previous call to [`as_mut_ptr`](MiniVec::as_mut_ptr) or from
+  /// the pointer returned by [`into_raw_part`](HeaderVec::into_raw_part).
+  ///
+  /// # Safety
+  ///
+  /// `ptr` must have been obtained from a `HeaderVec<H, T>` which has since been forgotten, see
+  /// [`MiniVec::from_raw_part`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::HeaderVec::new(42_u64);
+  /// vec.push(1);
+  ///
+  /// let ptr = vec.into_raw_part();
+  /// let vec = unsafe { minivec::HeaderVec::<u64, i32>::from_raw_part(ptr) };
+  ///
+  /// assert_eq!(*vec.header(), 42);
+  /// assert_eq!(vec, [1]);
+  /// ```
+  ///
+  pub unsafe fn from_raw_part(ptr: *mut T) -> HeaderVec<H, T> {
+    HeaderVec {
+      vec: MiniVec::from_data(ptr),
+    }
+  }
+
+  /// `header` returns a reference to the user header.
+  ///
+  #[must_use]
+  pub fn header(&self) -> &H {
+    self.vec.allocator().header()
+  }
+
+  /// `header_mut` returns a mutable reference to the user header.
+  ///
+  pub fn header_mut(&mut self) -> &mut H {
+    if core::mem::size_of::<H>() == 0 {
+      return unsafe { &mut *NonNull::<H>::dangling().as_ptr() };
+    }
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &mut *self
+        .vec
+        .buf
+        .as_ptr()
+        .add(allocator_offset::<HeaderAlloc<H>, usize>())
+        .cast::<H>()
+    }
+  }
+
+  /// `into_raw_part` consumes the vector and returns a pointer to its elements which can be handed back to
+  /// [`from_raw_part`](HeaderVec::from_raw_part).
+  ///
+  #[must_use]
+  pub fn into_raw_part(self) -> *mut T {
+    let mut vec = core::mem::ManuallyDrop::new(self);
+    vec.as_mut_ptr()
+  }
+
+  /// `new` creates an empty vector holding `header`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the allocation fails.
+  ///
+  #[must_use]
+  pub fn new(header: H) -> HeaderVec<H, T> {
+    Self::with_capacity(header, 0)
+  }
+
+  /// `try_with_capacity` is the fallible version of [`with_capacity`](HeaderVec::with_capacity).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the capacity overflows or the allocator fails.
+  ///
+  pub fn try_with_capacity(header: H, capacity: usize) -> Result<HeaderVec<H, T>, TryReserveError> {
+    let vec = MiniVec::try_with_capacity_in(capacity, HeaderAlloc { header })?;
+    Ok(HeaderVec { vec })
+  }
+
+  /// `with_capacity` creates an empty vector holding `header` with room for at least `capacity` elements.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows or the allocation fails.
+  ///
+  #[must_use]
+  pub fn with_capacity(header: H, capacity: usize) -> HeaderVec<H, T> {
+    handle_reserve(Self::try_with_capacity(header, capacity))
+  }
+}
+
+impl<H: Clone, T: Clone> Clone for HeaderVec<H, T> {
+  fn clone(&self) -> Self {
+    HeaderVec {
+      vec: self.vec.clone(),
+    }
+  }
+}
+
+impl<H: core::fmt::Debug, T: core::fmt::Debug> core::fmt::Debug for HeaderVec<H, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("HeaderVec")
+      .field("header", self.header())
+      .field("data", &self.as_slice())
+      .finish()
+  }
+}
+
+impl<H, T> core::ops::Deref for HeaderVec<H, T> {
+  type Target = MiniVec<T, HeaderAlloc<H>>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.vec
+  }
+}
+
+impl<H, T> core::ops::DerefMut for HeaderVec<H, T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.vec
+  }
+}
+
+impl<H: Eq, T: Eq> Eq for HeaderVec<H, T> {}
+
+impl<H: core::hash::Hash, T: core::hash::Hash> core::hash::Hash for HeaderVec<H, T> {
+  fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+    self.header().hash(state);
+    self.as_slice().hash(state);
+  }
+}
+
+impl<H: PartialEq, T: PartialEq> PartialEq for HeaderVec<H, T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.header() == other.header() && self.as_slice() == other.as_slice()
+  }
+}
+
+impl<H, T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for HeaderVec<H, T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<H, T: PartialEq<U>, U> PartialEq<[U]> for HeaderVec<H, T> {
+  fn eq(&self, other: &[U]) -> bool {
+    self.as_slice() == other
+  }
+}
@@ -2,9 +2,11 @@ pub mod drain;
 pub mod drain_filter;
 pub mod helpers;
 pub mod into_iter;
+#[cfg(not(feature = "no_global_oom_handling"))]
 pub mod splice;
 
 pub use drain::Drain;
 pub use drain_filter::DrainFilter;
 pub use into_iter::IntoIter;
+#[cfg(not(feature = "no_global_oom_handling"))]
 pub use splice::Splice;
@@ -1,48 +1,47 @@
-use crate::MiniVec;
+use crate::{Allocator, Doubling, Global, GrowthPolicy, IndexType, MiniVec};
 
 extern crate alloc;
 
 /// `Drain` is an iterator that removes the selected sub-range from the `MiniVec` and returns the removed elements to
 /// the caller lazily.
 ///
-pub struct Drain<'a, T: 'a> {
-  vec_: core::ptr::NonNull<MiniVec<T>>,
-  drain_pos_: core::ptr::NonNull<T>,
-  drain_end_: core::ptr::NonNull<T>,
-  remaining_pos_: core::ptr::NonNull<T>,
+// positions are tracked as indices into the vector's buffer rather than as pointers so that zero-sized types, for
+// which every element shares the same address, are handled uniformly
+//
+#[allow(clippy::struct_field_names)]
+pub struct Drain<'a, T: 'a, A: Allocator = Global, H: IndexType = usize, G: GrowthPolicy = Doubling>
+{
+  vec_: core::ptr::NonNull<MiniVec<T, A, H, G>>,
+  drain_pos_: usize,
+  drain_end_: usize,
+  remaining_pos_: usize,
   remaining_: usize,
   marker_: core::marker::PhantomData<&'a T>,
 }
 
-pub fn make_drain_iterator<'a, T>(
-  vec: &mut MiniVec<T>,
-  data: *mut T,
+pub fn make_drain_iterator<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy>(
+  vec: &mut MiniVec<T, A, H, G>,
   remaining: usize,
   start_idx: usize,
   end_idx: usize,
-) -> Drain<'a, T> {
-  if data.is_null() {
-    Drain {
-      vec_: core::ptr::NonNull::from(vec),
-      drain_pos_: core::ptr::NonNull::dangling(),
-      drain_end_: core::ptr::NonNull::dangling(),
-      remaining_pos_: core::ptr::NonNull::dangling(),
-      remaining_: remaining,
-      marker_: core::marker::PhantomData,
-    }
-  } else {
-    Drain {
-      vec_: core::ptr::NonNull::from(vec),
-      drain_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(start_idx)) },
-      drain_end_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-      remaining_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-      remaining_: remaining,
-      marker_: core::marker::PhantomData,
-    }
+) -> Drain<'a, T, A, H, G> {
+  Drain {
+    vec_: core::ptr::NonNull::from(vec),
+    drain_pos_: start_idx,
+    drain_end_: end_idx,
+    remaining_pos_: end_idx,
+    remaining_: remaining,
+    marker_: core::marker::PhantomData,
+  }
+}
+
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drain<'_, T, A, H, G> {
+  fn data(&mut self) -> *mut T {
+    unsafe { self.vec_.as_mut().as_mut_ptr() }
   }
 }
 
-impl<T> Iterator for Drain<'_, T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Iterator for Drain<'_, T, A, H, G> {
   type Item = T;
 
   fn next(&mut self) -> Option<Self::Item> {
@@ -50,43 +49,39 @@ impl<T> Iterator for Drain<'_, T> {
       return None;
     }
 
-    let p = self.drain_pos_.as_ptr();
-    let tmp = unsafe { core::ptr::read(p) };
-    self.drain_pos_ = unsafe { core::ptr::NonNull::new_unchecked(p.add(1)) };
+    let tmp = unsafe { core::ptr::read(self.data().add(self.drain_pos_)) };
+    self.drain_pos_ += 1;
     Some(tmp)
   }
 
   fn size_hint(&self) -> (usize, Option<usize>) {
-    let len = (self.drain_end_.as_ptr() as *const _ as usize
-      - self.drain_pos_.as_ptr() as *const _ as usize)
-      / core::mem::size_of::<T>();
+    let len = self.drain_end_ - self.drain_pos_;
 
     (len, Some(len))
   }
 }
 
-impl<T> ExactSizeIterator for Drain<'_, T> {}
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> ExactSizeIterator for Drain<'_, T, A, H, G> {}
 
-impl<T> DoubleEndedIterator for Drain<'_, T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> DoubleEndedIterator for Drain<'_, T, A, H, G> {
   fn next_back(&mut self) -> Option<Self::Item> {
-    let pos = unsafe { self.drain_end_.as_ptr().sub(1) };
-    if pos < self.drain_pos_.as_ptr() {
+    if self.drain_end_ <= self.drain_pos_ {
       return None;
     }
 
-    let tmp = unsafe { core::ptr::read(pos) };
-    self.drain_end_ = unsafe { core::ptr::NonNull::new_unchecked(pos) };
+    self.drain_end_ -= 1;
+    let tmp = unsafe { core::ptr::read(self.data().add(self.drain_end_)) };
     Some(tmp)
   }
 }
 
-impl<T> Drop for Drain<'_, T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for Drain<'_, T, A, H, G> {
   fn drop(&mut self) {
-    struct DropGuard<'b, 'a, T> {
-      drain: &'b mut Drain<'a, T>,
+    struct DropGuard<'b, 'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> {
+      drain: &'b mut Drain<'a, T, A, H, G>,
     }
 
-    impl<'b, 'a, T> Drop for DropGuard<'b, 'a, T> {
+    impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for DropGuard<'_, '_, T, A, H, G> {
       fn drop(&mut self) {
         for x in &mut self.drain {
           core::mem::drop(x);
@@ -96,7 +91,7 @@ impl<T> Drop for Drain<'_, T> {
           let v = unsafe { self.drain.vec_.as_mut() };
           let v_len = v.len();
 
-          let src = self.drain.remaining_pos_.as_ptr();
+          let src = unsafe { v.as_ptr().add(self.drain.remaining_pos_) };
           let dst = unsafe { v.as_mut_ptr().add(v_len) };
 
           unsafe {
@@ -3,11 +3,14 @@
 ///
 /// Elements are only removed and returned to the caller when the predicate evaluates to true.
 ///
-pub struct DrainFilter<'a, T, F>
+pub struct DrainFilter<'a, T, F, A = crate::Global, H = usize, G = crate::Doubling>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
-  vec: &'a mut crate::MiniVec<T>,
+  vec: &'a mut crate::MiniVec<T, A, H, G>,
   pred: F,
   old_len: usize,
   new_len: usize,
@@ -15,12 +18,15 @@ where
   panicked: bool,
 }
 
-pub fn make_drain_filter_iterator<T, F>(
-  vec: &mut crate::MiniVec<T>,
+pub fn make_drain_filter_iterator<T, F, A, H, G>(
+  vec: &mut crate::MiniVec<T, A, H, G>,
   pred: F,
-) -> DrainFilter<'_, T, F>
+) -> DrainFilter<'_, T, F, A, H, G>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
   let old_len = vec.len();
   DrainFilter {
@@ -33,9 +39,12 @@ where
   }
 }
 
-impl<T, F> core::iter::Iterator for DrainFilter<'_, T, F>
+impl<T, F, A, H, G> core::iter::Iterator for DrainFilter<'_, T, F, A, H, G>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
   type Item = T;
 
@@ -52,11 +61,11 @@ where
 
       if pred_result {
         self.pos += 1;
-        return Some(unsafe { core::ptr::read(val as *mut T) });
+        return Some(unsafe { core::ptr::read(core::ptr::from_mut(val)) });
       }
 
       if self.pos > self.new_len {
-        let src = val as *mut T;
+        let src = core::ptr::from_mut(val);
         let dst = unsafe { data.add(self.new_len) };
         unsafe {
           core::ptr::copy_nonoverlapping(src, dst, 1);
@@ -75,16 +84,22 @@ where
   }
 }
 
-struct DropGuard<'a, 'b, T, F>
+struct DropGuard<'a, 'b, T, F, A, H, G>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
-  drain: &'b mut DrainFilter<'a, T, F>,
+  drain: &'b mut DrainFilter<'a, T, F, A, H, G>,
 }
 
-impl<'a, 'b, T, F> Drop for DropGuard<'a, 'b, T, F>
+impl<T, F, A, H, G> Drop for DropGuard<'_, '_, T, F, A, H, G>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
   fn drop(&mut self) {
     let num_remaining = self.drain.old_len - self.drain.pos;
@@ -111,9 +126,12 @@ where
   }
 }
 
-impl<T, F> Drop for DrainFilter<'_, T, F>
+impl<T, F, A, H, G> Drop for DrainFilter<'_, T, F, A, H, G>
 where
   F: core::ops::FnMut(&mut T) -> bool,
+  A: crate::Allocator,
+  H: crate::IndexType,
+  G: crate::GrowthPolicy,
 {
   fn drop(&mut self) {
     let drop_guard = DropGuard { drain: self };
@@ -1,4 +1,4 @@
-use crate::Header;
+use crate::{Header, IndexType};
 
 extern crate alloc;
 
@@ -8,23 +8,15 @@ pub const fn next_aligned(n: usize, alignment: usize) -> usize {
   (n + (alignment - 1)) & !(alignment - 1)
 }
 
-pub const fn next_capacity<T>(capacity: usize) -> usize {
-  let elem_size = core::mem::size_of::<T>();
-
-  if capacity == 0 {
-    return match elem_size {
-      1 => 8,
-      2..=1024 => 4,
-      _ => 1,
-    };
-  }
-
-  capacity.saturating_mul(2)
-}
-
-pub const fn max_align<T>() -> usize {
+pub const fn max_align<T, A, H>() -> usize {
   let align_t = core::mem::align_of::<T>();
-  let header_align = core::mem::align_of::<Header>();
+  let header_align = if core::mem::size_of::<A>() == 0 {
+    core::mem::align_of::<Header<H>>()
+  } else if core::mem::align_of::<A>() > core::mem::align_of::<Header<H>>() {
+    core::mem::align_of::<A>()
+  } else {
+    core::mem::align_of::<Header<H>>()
+  };
 
   if align_t > header_align {
     align_t
@@ -33,28 +25,141 @@ pub const fn max_align<T>() -> usize {
   }
 }
 
-pub const fn make_layout<T>(capacity: usize) -> alloc::alloc::Layout {
-  let alignment = max_align::<T>();
-  let header_size = core::mem::size_of::<Header>();
+// stateful allocators are stored directly after the header while zero-sized ones aren't stored at all
+//
+pub const fn allocator_offset<A, H>() -> usize {
+  next_aligned(
+    core::mem::size_of::<Header<H>>(),
+    core::mem::align_of::<A>(),
+  )
+}
+
+// the header is padded up to the alignment of `T`, which is why only element types aligned to at least 32 bytes leave
+// room in front of their data for the larger headers of `MiniShared` and `MiniVecDeque`
+//
+pub const fn header_bytes<T, A, H>() -> usize {
+  let header_size = if core::mem::size_of::<A>() == 0 {
+    core::mem::size_of::<Header<H>>()
+  } else {
+    allocator_offset::<A, H>() + core::mem::size_of::<A>()
+  };
+
+  next_aligned(header_size, max_align::<T, A, H>())
+}
+
+// over-aligned allocations are padded in front of the header so that the data still begins `N` bytes after it
+//
+// the padding is always large enough to hold the alignment itself in the `usize` directly preceding the header
+//
+pub const fn header_offset<T, A, H>(alignment: usize) -> usize {
+  if alignment <= max_align::<T, A, H>() {
+    return 0;
+  }
+
+  let header_bytes = header_bytes::<T, A, H>();
+  next_aligned(header_bytes + core::mem::size_of::<usize>(), alignment) - header_bytes
+}
+
+// returns `None` when the allocation would be too large to be described by a `Layout`
+//
+pub const fn make_aligned_layout<T, A, H>(
+  capacity: usize,
+  alignment: usize,
+) -> Option<alloc::alloc::Layout> {
+  let Some(data_bytes) = capacity.checked_mul(core::mem::size_of::<T>()) else {
+    return None;
+  };
+
+  let Some(data_bytes) = data_bytes.checked_add(alignment - 1) else {
+    return None;
+  };
 
-  let num_bytes = next_aligned(header_size, alignment)
-    + next_aligned(capacity * core::mem::size_of::<T>(), alignment);
+  let header_bytes = header_offset::<T, A, H>(alignment) + header_bytes::<T, A, H>();
+  let Some(num_bytes) = header_bytes.checked_add(data_bytes & !(alignment - 1)) else {
+    return None;
+  };
 
-  unsafe { alloc::alloc::Layout::from_size_align_unchecked(num_bytes, alignment) }
+  match alloc::alloc::Layout::from_size_align(num_bytes, alignment) {
+    Ok(layout) => Some(layout),
+    Err(_) => None,
+  }
 }
 
-pub const fn max_elems<T>() -> usize {
-  let alignment = max_align::<T>();
-  let header_bytes = next_aligned(core::mem::size_of::<Header>(), alignment);
-  let max = usize::MAX;
-  let m = max - (max % alignment) - header_bytes;
+// the offset of the elements in an allocation holding a single `P` directly followed by the elements themselves
+//
+// this is the layout of the containers which don't need a full `Header`, e.g. `MiniBoxSlice` only stores its length
+//
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub const fn prefixed_data_offset<P, T>() -> usize {
+  let align = if core::mem::align_of::<T>() > core::mem::align_of::<P>() {
+    core::mem::align_of::<T>()
+  } else {
+    core::mem::align_of::<P>()
+  };
 
-  m / core::mem::size_of::<T>()
+  next_aligned(core::mem::size_of::<P>(), align)
+}
+
+// returns `None` when the allocation would be too large to be described by a `Layout`
+//
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub const fn make_prefixed_layout<P, T>(len: usize) -> Option<alloc::alloc::Layout> {
+  let align = if core::mem::align_of::<T>() > core::mem::align_of::<P>() {
+    core::mem::align_of::<T>()
+  } else {
+    core::mem::align_of::<P>()
+  };
+
+  let Some(data_bytes) = len.checked_mul(core::mem::size_of::<T>()) else {
+    return None;
+  };
+
+  let Some(num_bytes) = prefixed_data_offset::<P, T>().checked_add(data_bytes) else {
+    return None;
+  };
+
+  match alloc::alloc::Layout::from_size_align(num_bytes, align) {
+    Ok(layout) => Some(layout),
+    Err(_) => None,
+  }
+}
+
+pub const fn max_aligned_elems<T, A, H: IndexType>(alignment: usize) -> usize {
+  // ZSTs never need more than the header so they can hold as many elements as `len` can count
+  //
+  if core::mem::size_of::<T>() == 0 {
+    return H::MAX;
+  }
+
+  // the top bit of the stored capacity flags over-aligned allocations so it's never available to the capacity itself
+  //
+  let max_capacity = H::MAX >> 1;
+
+  #[allow(clippy::cast_sign_loss)]
+  let max = isize::MAX as usize;
+
+  let header_bytes = header_offset::<T, A, H>(alignment) + header_bytes::<T, A, H>();
+  let m = (max - (max % alignment) - header_bytes) / core::mem::size_of::<T>();
+
+  if m < max_capacity {
+    m
+  } else {
+    max_capacity
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::Global;
+
+  fn make_layout<T>(capacity: usize) -> alloc::alloc::Layout {
+    make_aligned_layout::<T, Global, usize>(capacity, max_align::<T, Global, usize>()).unwrap()
+  }
+
+  fn max_elems<T>() -> usize {
+    max_aligned_elems::<T, Global, usize>(max_align::<T, Global, usize>())
+  }
   #[test]
   fn next_aligned_test() {
     assert_eq!(next_aligned(9, 4), 12);
@@ -73,17 +178,17 @@ mod tests {
 
   #[test]
   fn max_align_test() {
-    let header_alignment = core::mem::align_of::<Header>();
+    let header_alignment = core::mem::align_of::<Header<usize>>();
 
-    assert!(core::mem::align_of::<i32>() <= core::mem::align_of::<Header>());
-    assert_eq!(max_align::<i32>(), header_alignment);
+    assert!(core::mem::align_of::<i32>() <= core::mem::align_of::<Header<usize>>());
+    assert_eq!(max_align::<i32, Global, usize>(), header_alignment);
 
-    assert!(core::mem::align_of::<u8>() <= core::mem::align_of::<Header>());
-    assert_eq!(max_align::<u8>(), header_alignment);
+    assert!(core::mem::align_of::<u8>() <= core::mem::align_of::<Header<usize>>());
+    assert_eq!(max_align::<u8, Global, usize>(), header_alignment);
 
-    assert!(core::mem::align_of::<OverAligned>() > core::mem::align_of::<Header>());
+    assert!(core::mem::align_of::<OverAligned>() > core::mem::align_of::<Header<usize>>());
     assert_eq!(
-      max_align::<OverAligned>(),
+      max_align::<OverAligned, Global, usize>(),
       core::mem::align_of::<OverAligned>()
     );
   }
@@ -94,17 +199,17 @@ mod tests {
     //
     let layout = make_layout::<i32>(0);
 
-    assert_eq!(layout.align(), core::mem::align_of::<Header>());
-    assert_eq!(layout.size(), core::mem::size_of::<Header>());
+    assert_eq!(layout.align(), core::mem::align_of::<Header<usize>>());
+    assert_eq!(layout.size(), core::mem::size_of::<Header<usize>>());
 
     // non-empty, less than
     //
     let layout = make_layout::<i32>(512);
-    assert!(core::mem::align_of::<i32>() < core::mem::align_of::<Header>());
-    assert_eq!(layout.align(), core::mem::align_of::<Header>());
+    assert!(core::mem::align_of::<i32>() < core::mem::align_of::<Header<usize>>());
+    assert_eq!(layout.align(), core::mem::align_of::<Header<usize>>());
     assert_eq!(
       layout.size(),
-      core::mem::size_of::<Header>() + 512 * core::mem::size_of::<i32>()
+      core::mem::size_of::<Header<usize>>() + 512 * core::mem::size_of::<i32>()
     );
 
     // non-empty, equal
@@ -112,24 +217,157 @@ mod tests {
     let layout = make_layout::<i64>(512);
     assert_eq!(
       core::mem::align_of::<i64>(),
-      core::mem::align_of::<Header>()
+      core::mem::align_of::<Header<usize>>()
     );
-    assert_eq!(layout.align(), core::mem::align_of::<Header>());
+    assert_eq!(layout.align(), core::mem::align_of::<Header<usize>>());
     assert_eq!(
       layout.size(),
-      core::mem::size_of::<Header>() + 512 * core::mem::size_of::<i64>()
+      core::mem::size_of::<Header<usize>>() + 512 * core::mem::size_of::<i64>()
     );
 
     // non-empty, greater
     let layout = make_layout::<OverAligned>(512);
-    assert!(core::mem::align_of::<OverAligned>() > core::mem::align_of::<Header>());
+    assert!(core::mem::align_of::<OverAligned>() > core::mem::align_of::<Header<usize>>());
     assert_eq!(layout.align(), core::mem::align_of::<OverAligned>());
     assert_eq!(
       layout.size(),
       next_aligned(
-        core::mem::size_of::<Header>(),
+        core::mem::size_of::<Header<usize>>(),
         core::mem::align_of::<OverAligned>()
       ) + 512 * core::mem::size_of::<OverAligned>()
     );
+
+    // zero-sized, only the header is ever allocated
+    //
+    let layout = make_layout::<()>(usize::MAX);
+    assert_eq!(layout.align(), core::mem::align_of::<Header<usize>>());
+    assert_eq!(layout.size(), core::mem::size_of::<Header<usize>>());
+  }
+
+  #[test]
+  fn make_aligned_layout_test() {
+    // an alignment the type already has needs no padding
+    //
+    assert_eq!(
+      header_offset::<i32, Global, usize>(max_align::<i32, Global, usize>()),
+      0
+    );
+    assert_eq!(
+      make_aligned_layout::<i32, Global, usize>(512, max_align::<i32, Global, usize>()).unwrap(),
+      make_layout::<i32>(512)
+    );
+
+    // over-aligned, the data must begin on a 64-byte boundary and there must be room for the stored alignment
+    //
+    let header_bytes = next_aligned(
+      core::mem::size_of::<Header<usize>>(),
+      max_align::<i32, Global, usize>(),
+    );
+    let offset = header_offset::<i32, Global, usize>(64);
+    assert_eq!((offset + header_bytes) % 64, 0);
+    assert!(offset >= core::mem::size_of::<usize>());
+
+    let layout = make_aligned_layout::<i32, Global, usize>(512, 64).unwrap();
+    assert_eq!(layout.align(), 64);
+    assert_eq!(
+      layout.size(),
+      offset + header_bytes + 512 * core::mem::size_of::<i32>()
+    );
+
+    // the header is exactly as large as the alignment, the padding can't be empty
+    //
+    let offset = header_offset::<i32, Global, usize>(header_bytes);
+    assert!(header_bytes > max_align::<i32, Global, usize>());
+    assert_eq!(offset, header_bytes);
+  }
+
+  #[test]
+  fn stateful_allocator_layout_test() {
+    #[repr(align(32))]
+    struct Stateful {
+      _state: u8,
+    }
+
+    // a zero-sized allocator is never stored
+    //
+    assert_eq!(
+      header_bytes::<i32, Global, usize>(),
+      core::mem::size_of::<Header<usize>>()
+    );
+
+    // a stateful allocator sits right after the header and the data follows it
+    //
+
+    assert_eq!(allocator_offset::<Stateful, usize>(), 32);
+    assert_eq!(max_align::<i32, Stateful, usize>(), 32);
+    assert_eq!(header_bytes::<i32, Stateful, usize>(), 64);
+
+    let layout =
+      make_aligned_layout::<i32, Stateful, usize>(0, max_align::<i32, Stateful, usize>()).unwrap();
+    assert_eq!(layout.align(), 32);
+    assert_eq!(layout.size(), 64);
+  }
+
+  #[test]
+  fn layout_overflow_test() {
+    assert!(make_aligned_layout::<i32, Global, usize>(usize::MAX / 4, 4).is_none());
+    assert!(make_aligned_layout::<i32, Global, usize>(isize::MAX as usize / 4, 4).is_none());
+    assert!(make_aligned_layout::<u8, Global, usize>(usize::MAX, 8).is_none());
+    assert!(make_aligned_layout::<(), Global, usize>(usize::MAX, 8).is_some());
+  }
+
+  #[test]
+  fn compact_header_layout_test() {
+    assert_eq!(header_bytes::<u8, Global, u32>(), 8);
+    assert_eq!(header_bytes::<u8, Global, u16>(), 4);
+
+    // the header is still padded out to the alignment of the elements
+    //
+    assert_eq!(header_bytes::<u64, Global, u16>(), 8);
+
+    let layout =
+      make_aligned_layout::<u8, Global, u16>(10, max_align::<u8, Global, u16>()).unwrap();
+    assert_eq!(layout.align(), 2);
+    assert_eq!(layout.size(), 14);
+
+    let layout =
+      make_aligned_layout::<i32, Global, u32>(512, max_align::<i32, Global, u32>()).unwrap();
+    assert_eq!(layout.align(), 4);
+    assert_eq!(layout.size(), 8 + 512 * core::mem::size_of::<i32>());
+
+    // the capacity is bounded by the index type, less the bit reserved for over-aligned allocations
+    //
+    assert_eq!(
+      max_aligned_elems::<u8, Global, u16>(2),
+      (u16::MAX >> 1) as usize
+    );
+    assert_eq!(max_aligned_elems::<(), Global, u32>(4), u32::MAX as usize);
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn prefixed_layout_test() {
+    assert_eq!(
+      prefixed_data_offset::<usize, u8>(),
+      core::mem::size_of::<usize>()
+    );
+    assert_eq!(prefixed_data_offset::<usize, OverAligned>(), 512);
+    assert_eq!(prefixed_data_offset::<u16, u8>(), 2);
+
+    let layout = make_prefixed_layout::<usize, i32>(3).unwrap();
+    assert_eq!(layout.align(), core::mem::align_of::<usize>());
+    assert_eq!(layout.size(), core::mem::size_of::<usize>() + 12);
+
+    let layout = make_prefixed_layout::<usize, ()>(usize::MAX).unwrap();
+    assert_eq!(layout.size(), core::mem::size_of::<usize>());
+
+    assert!(make_prefixed_layout::<usize, i32>(usize::MAX / 2).is_none());
+  }
+
+  #[test]
+  fn max_elems_test() {
+    assert_eq!(max_elems::<()>(), usize::MAX);
+    assert!(max_aligned_elems::<i32, Global, usize>(4096) < max_elems::<i32>());
+    assert!(max_elems::<i32>() <= isize::MAX as usize / core::mem::size_of::<i32>());
   }
 }
@@ -1,3 +1,5 @@
+use crate::{Allocator, Doubling, Global, GrowthPolicy, IndexType};
+
 extern crate alloc;
 
 // we diverge pretty heavily from the stdlib here
@@ -10,15 +12,15 @@ extern crate alloc;
 /// `IntoIter` is an iterator type that consumes the `MiniVec` and transfers ownership of the contained elements to the
 /// caller when iterated.
 ///
-pub struct IntoIter<T> {
-  pub(crate) v: crate::MiniVec<T>,
+pub struct IntoIter<T, A: Allocator = Global, H: IndexType = usize, G: GrowthPolicy = Doubling> {
+  pub(crate) v: crate::MiniVec<T, A, H, G>,
   pub(crate) pos: *const T,
   marker: core::marker::PhantomData<T>,
 }
 
-impl<T> IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> IntoIter<T, A, H, G> {
   #[must_use]
-  pub(crate) fn new(w: crate::MiniVec<T>) -> Self {
+  pub(crate) fn new(w: crate::MiniVec<T, A, H, G>) -> Self {
     let v = w;
     let pos = v.data();
 
@@ -40,19 +42,20 @@ impl<T> IntoIter<T> {
   /// `as_mut_slice` returns a mutable slice to the remaining elements of the iterator that have not yet been moved.
   ///
   pub fn as_mut_slice(&mut self) -> &mut [T] {
-    let data: *mut T = self.pos as *mut T;
+    let data: *mut T = self.pos.cast_mut();
     unsafe { core::slice::from_raw_parts_mut(data, self.v.len()) }
   }
 }
 
-impl<T> AsRef<[T]> for IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> AsRef<[T]> for IntoIter<T, A, H, G> {
   fn as_ref(&self) -> &[T] {
     self.as_slice()
   }
 }
 
-impl<T: Clone> Clone for IntoIter<T> {
-  fn clone(&self) -> IntoIter<T> {
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<T: Clone, A: Allocator + Clone, H: IndexType, G: GrowthPolicy> Clone for IntoIter<T, A, H, G> {
+  fn clone(&self) -> IntoIter<T, A, H, G> {
     let w = self.v.clone();
     let pos_cpy = self.pos;
     IntoIter {
@@ -63,7 +66,9 @@ impl<T: Clone> Clone for IntoIter<T> {
   }
 }
 
-impl<T: alloc::fmt::Debug> alloc::fmt::Debug for IntoIter<T> {
+impl<T: alloc::fmt::Debug, A: Allocator, H: IndexType, G: GrowthPolicy> alloc::fmt::Debug
+  for IntoIter<T, A, H, G>
+{
   fn fmt(&self, f: &mut alloc::fmt::Formatter<'_>) -> alloc::fmt::Result {
     f.debug_tuple("MiniVec::IntoIter")
       .field(&self.as_slice())
@@ -71,25 +76,20 @@ impl<T: alloc::fmt::Debug> alloc::fmt::Debug for IntoIter<T> {
   }
 }
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> DoubleEndedIterator for IntoIter<T, A, H, G> {
   fn next_back(&mut self) -> Option<Self::Item> {
-    let header = self.v.header_mut();
-
-    let data = self.pos;
-    let count = header.len;
-    let end = unsafe { data.add(count) };
-
-    if data >= end {
+    let len = self.v.len();
+    if len == 0 {
       return None;
-    };
+    }
 
-    header.len -= 1;
+    self.v.header_mut().len = H::from_usize(len - 1);
 
-    Some(unsafe { core::ptr::read(data.add(header.len)) })
+    Some(unsafe { core::ptr::read(self.pos.add(len - 1)) })
   }
 }
 
-impl<T> Drop for IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for IntoIter<T, A, H, G> {
   fn drop(&mut self) {
     for v in self {
       core::mem::drop(v);
@@ -97,7 +97,7 @@ impl<T> Drop for IntoIter<T> {
   }
 }
 
-impl<T> ExactSizeIterator for IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> ExactSizeIterator for IntoIter<T, A, H, G> {
   fn len(&self) -> usize {
     self.v.len()
   }
@@ -107,24 +107,23 @@ impl<T> ExactSizeIterator for IntoIter<T> {
   // }
 }
 
-impl<T> core::iter::FusedIterator for IntoIter<T> {}
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::FusedIterator
+  for IntoIter<T, A, H, G>
+{
+}
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Iterator for IntoIter<T, A, H, G> {
   type Item = T;
 
   fn next(&mut self) -> Option<Self::Item> {
-    let header = self.v.header_mut();
-
-    let data = self.pos;
-    let count = header.len;
-    let end = unsafe { data.add(count) };
-
-    if data >= end {
+    if self.v.is_empty() {
       return None;
     }
 
+    let data = self.pos;
     self.pos = unsafe { data.add(1) };
-    header.len -= 1;
+    let len = self.v.len();
+    self.v.header_mut().len = H::from_usize(len - 1);
 
     Some(unsafe { core::ptr::read(data) })
   }
@@ -135,7 +134,13 @@ impl<T> Iterator for IntoIter<T> {
   }
 }
 
-unsafe impl<T: Send> Send for IntoIter<T> {}
-unsafe impl<T: Sync> Sync for IntoIter<T> {}
+unsafe impl<T: Send, A: Allocator + Send, H: IndexType, G: GrowthPolicy> Send
+  for IntoIter<T, A, H, G>
+{
+}
+unsafe impl<T: Sync, A: Allocator + Sync, H: IndexType, G: GrowthPolicy> Sync
+  for IntoIter<T, A, H, G>
+{
+}
 
-// unsafe impl<T> core::iter::InPlaceIterable for IntoIter<T> {}
+// unsafe impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::InPlaceIterable for IntoIter<T, A, H, G> {}
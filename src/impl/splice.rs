@@ -1,59 +1,63 @@
-use crate::MiniVec;
-
-extern crate alloc;
+use crate::{Allocator, Doubling, Global, GrowthPolicy, IndexType, MiniVec};
 
 /// `Splice` is an iterator that removes a sub-section of the backing `MiniVec` and then replaces it with the contents
 /// of another iterator. The removed sub-section and the iterator used to replace it can have independent lengths.
 ///
-pub struct Splice<'a, I>
+// much like `Drain`, positions are indices so that zero-sized types are handled uniformly
+//
+#[allow(clippy::struct_field_names)]
+pub struct Splice<'a, I, A = Global, H = usize, G = Doubling>
 where
   I: 'a + Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
 {
-  vec_: core::ptr::NonNull<MiniVec<I::Item>>,
-  drain_pos_: core::ptr::NonNull<I::Item>,
-  drain_end_: core::ptr::NonNull<I::Item>,
-  remaining_pos_: core::ptr::NonNull<I::Item>,
+  vec_: core::ptr::NonNull<MiniVec<I::Item, A, H, G>>,
+  drain_pos_: usize,
+  drain_end_: usize,
+  remaining_pos_: usize,
   remaining_: usize,
   marker_: core::marker::PhantomData<&'a I::Item>,
   fill_: I,
 }
 
-pub fn make_splice_iterator<'a, I: 'a + Iterator>(
-  vec: &mut MiniVec<I::Item>,
-  data: *mut I::Item,
+pub fn make_splice_iterator<'a, I: 'a + Iterator, A: Allocator, H: IndexType, G: GrowthPolicy>(
+  vec: &mut MiniVec<I::Item, A, H, G>,
   remaining: usize,
   start_idx: usize,
   end_idx: usize,
   fill: I,
-) -> Splice<'a, I> {
-  if data.is_null() {
-    let dangling = core::ptr::NonNull::<I::Item>::dangling();
-
-    Splice {
-      vec_: core::ptr::NonNull::from(vec),
-      drain_pos_: dangling,
-      drain_end_: dangling,
-      remaining_pos_: dangling,
-      remaining_: 0,
-      marker_: core::marker::PhantomData,
-      fill_: fill,
-    }
-  } else {
-    Splice {
-      vec_: core::ptr::NonNull::from(vec),
-      drain_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(start_idx)) },
-      drain_end_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-      remaining_pos_: unsafe { core::ptr::NonNull::new_unchecked(data.add(end_idx)) },
-      remaining_: remaining,
-      marker_: core::marker::PhantomData,
-      fill_: fill,
-    }
+) -> Splice<'a, I, A, H, G> {
+  Splice {
+    vec_: core::ptr::NonNull::from(vec),
+    drain_pos_: start_idx,
+    drain_end_: end_idx,
+    remaining_pos_: end_idx,
+    remaining_: remaining,
+    marker_: core::marker::PhantomData,
+    fill_: fill,
+  }
+}
+
+impl<I, A, H, G> Splice<'_, I, A, H, G>
+where
+  I: Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
+{
+  fn data(&mut self) -> *mut I::Item {
+    unsafe { self.vec_.as_mut().as_mut_ptr() }
   }
 }
 
-impl<I> Iterator for Splice<'_, I>
+impl<I, A, H, G> Iterator for Splice<'_, I, A, H, G>
 where
   I: Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
 {
   type Item = I::Item;
 
@@ -62,49 +66,57 @@ where
       return None;
     }
 
-    let p = self.drain_pos_.as_ptr();
-    let tmp = unsafe { core::ptr::read(p) };
-    self.drain_pos_ = unsafe { core::ptr::NonNull::new_unchecked(p.add(1)) };
+    let tmp = unsafe { core::ptr::read(self.data().add(self.drain_pos_)) };
+    self.drain_pos_ += 1;
     Some(tmp)
   }
 
   fn size_hint(&self) -> (usize, Option<usize>) {
-    let len = (self.drain_end_.as_ptr() as *const _ as usize
-      - self.drain_pos_.as_ptr() as *const _ as usize)
-      / core::mem::size_of::<I::Item>();
+    let len = self.drain_end_ - self.drain_pos_;
 
     (len, Some(len))
   }
 }
 
-impl<I: Iterator> ExactSizeIterator for Splice<'_, I> {}
+impl<I: Iterator, A: Allocator, H: IndexType, G: GrowthPolicy> ExactSizeIterator
+  for Splice<'_, I, A, H, G>
+{
+}
 
-impl<I> DoubleEndedIterator for Splice<'_, I>
+impl<I, A, H, G> DoubleEndedIterator for Splice<'_, I, A, H, G>
 where
   I: Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
 {
   fn next_back(&mut self) -> Option<Self::Item> {
-    let pos = unsafe { self.drain_end_.as_ptr().sub(1) };
-    if pos < self.drain_pos_.as_ptr() {
+    if self.drain_end_ <= self.drain_pos_ {
       return None;
     }
 
-    let tmp = unsafe { core::ptr::read(pos) };
-    self.drain_end_ = unsafe { core::ptr::NonNull::new_unchecked(pos) };
+    self.drain_end_ -= 1;
+    let tmp = unsafe { core::ptr::read(self.data().add(self.drain_end_)) };
     Some(tmp)
   }
 }
 
-struct DropGuard<'b, 'a, I>
+struct DropGuard<'b, 'a, I, A, H, G>
 where
   I: Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
 {
-  splice: &'b mut Splice<'a, I>,
+  splice: &'b mut Splice<'a, I, A, H, G>,
 }
 
-impl<'b, 'a, I> Drop for DropGuard<'b, 'a, I>
+impl<I, A, H, G> Drop for DropGuard<'_, '_, I, A, H, G>
 where
   I: Iterator,
+  A: Allocator,
+  H: IndexType,
+  G: GrowthPolicy,
 {
   fn drop(&mut self) {
     // much like Drain, remove the rest of the elements from the splice range if they
@@ -123,8 +135,7 @@ where
 
     // infer the number of items we drained by where the remaining_pos_ is
     //
-    let num_drained = (self.splice.remaining_pos_.as_ptr() as usize - drain_begin as usize)
-      / core::mem::size_of::<I::Item>();
+    let num_drained = self.splice.remaining_pos_ - vec.len();
 
     // fill the drained sub-section using the iterator the user supplied
     // if the iterator, for example, has more elements than the draiend region allows,
@@ -154,7 +165,7 @@ where
       // we don't need to memcpy and can instead just adjust the length of the vector
       // and return
       //
-      if unsafe { vec.as_ptr().add(vec.len()) == self.splice.remaining_pos_.as_ptr() } {
+      if vec.len() == self.splice.remaining_pos_ {
         unsafe {
           vec.set_len(vec.len() + self.splice.remaining_);
         }
@@ -166,7 +177,7 @@ where
       // this basically downshifts the elements from right-to-left so it's safe to
       // call `core::ptr::copy`
       //
-      let src = self.splice.remaining_pos_.as_ptr();
+      let src = unsafe { vec.as_ptr().add(self.splice.remaining_pos_) };
       let dst = unsafe { vec.as_mut_ptr().add(vec.len()) };
       let count = self.splice.remaining_;
       unsafe {
@@ -183,12 +194,10 @@ where
     let mut tmp: MiniVec<_> = (&mut self.splice.fill_).collect();
 
     // reserve extra capacity if required
-    // note, this will invalidate all of our previously cached pointers in the Splice
-    // iterator so we have to store the offset of the drain tail manually
+    // the Splice iterator only stores offsets so reallocating here is safe
     //
-    let capacity = vec.capacity();
-    let remaining_offset = (self.splice.remaining_pos_.as_ptr() as usize - vec.as_ptr() as usize)
-      / core::mem::size_of::<I::Item>();
+    let capacity = vec.writable_capacity();
+    let remaining_offset = self.splice.remaining_pos_;
 
     // if our vector's length + the remaining elements + the extra tmp length exceeds
     // our capacity we need to reallocate
@@ -196,11 +205,7 @@ where
     let total_elements = vec.len() + self.splice.remaining_ + tmp.len();
 
     if total_elements > capacity {
-      if let Err(crate::TryReserveErrorKind::AllocError { layout }) =
-        vec.grow(total_elements).map_err(|e| e.kind())
-      {
-        alloc::alloc::handle_alloc_error(layout);
-      }
+      crate::handle_reserve(vec.grow(total_elements));
     }
 
     // let's first move the Drain tail over to the right
@@ -238,7 +243,7 @@ where
   }
 }
 
-impl<I: Iterator> Drop for Splice<'_, I> {
+impl<I: Iterator, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for Splice<'_, I, A, H, G> {
   fn drop(&mut self) {
     while let Some(item) = self.next() {
       let guard = DropGuard { splice: self };
@@ -1,23 +1,27 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T, I> core::ops::Index<I> for MiniVec<T>
+impl<T, I, A: Allocator, H: IndexType, G: GrowthPolicy> core::ops::Index<I> for MiniVec<T, A, H, G>
 where
   I: core::slice::SliceIndex<[T]>,
 {
   type Output = <I as core::slice::SliceIndex<[T]>>::Output;
 
-  fn index(&self, index: I) -> &<MiniVec<T> as core::ops::Index<I>>::Output {
-    let v: &[T] = &**self;
+  fn index(&self, index: I) -> &<MiniVec<T, A, H, G> as core::ops::Index<I>>::Output {
+    let v: &[T] = self;
     core::ops::Index::index(v, index)
   }
 }
 
-impl<T, I> core::ops::IndexMut<I> for MiniVec<T>
+impl<T, I, A: Allocator, H: IndexType, G: GrowthPolicy> core::ops::IndexMut<I>
+  for MiniVec<T, A, H, G>
 where
   I: core::slice::SliceIndex<[T]>,
 {
-  fn index_mut(&mut self, index: I) -> &mut <MiniVec<T> as core::ops::Index<I>>::Output {
-    let v: &mut [T] = &mut **self;
+  fn index_mut(&mut self, index: I) -> &mut <MiniVec<T, A, H, G> as core::ops::Index<I>>::Output {
+    let v: &mut [T] = self;
     core::ops::IndexMut::index_mut(v, index)
   }
 }
@@ -0,0 +1,58 @@
+mod sealed {
+  pub trait Sealed {}
+
+  impl Sealed for usize {}
+  impl Sealed for u32 {}
+  impl Sealed for u16 {}
+}
+
+/// `IndexType` is the integer type a [`MiniVec`](crate::MiniVec) uses to store its length and capacity in the header
+/// of its allocation.
+///
+/// It's implemented for `usize` (the default), `u32` and `u16`. A smaller index type shrinks the header of every
+/// allocation, e.g. from 16 to 8 bytes for `u32` on 64-bit targets, at the cost of a lower maximum capacity. Growing a
+/// vector past what its index type can represent fails with
+/// [`TryReserveErrorKind::CapacityOverflow`](crate::TryReserveErrorKind::CapacityOverflow).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+pub trait IndexType: sealed::Sealed + Copy + 'static {
+  /// `MAX` is the largest length representable by this index type.
+  ///
+  const MAX: usize;
+
+  #[doc(hidden)]
+  const ZERO: Self;
+
+  #[doc(hidden)]
+  fn from_usize(n: usize) -> Self;
+
+  #[doc(hidden)]
+  fn to_usize(self) -> usize;
+}
+
+macro_rules! index_type_impl {
+  ($t:ty) => {
+    impl IndexType for $t {
+      const MAX: usize = <$t>::MAX as usize;
+
+      const ZERO: Self = 0;
+
+      #[inline]
+      #[allow(clippy::cast_possible_truncation)]
+      fn from_usize(n: usize) -> Self {
+        debug_assert!(n <= <Self as IndexType>::MAX);
+        n as $t
+      }
+
+      #[inline]
+      fn to_usize(self) -> usize {
+        self as usize
+      }
+    }
+  };
+}
+
+index_type_impl!(usize);
+index_type_impl!(u32);
+index_type_impl!(u16);
@@ -1,17 +1,24 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
 use crate::r#impl::into_iter::IntoIter;
 
-impl<T> core::iter::IntoIterator for MiniVec<T> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::IntoIterator
+  for MiniVec<T, A, H, G>
+{
   type Item = T;
-  type IntoIter = IntoIter<T>;
+  type IntoIter = IntoIter<T, A, H, G>;
 
   fn into_iter(self) -> Self::IntoIter {
-    IntoIter::<T>::new(self)
+    IntoIter::<T, A, H, G>::new(self)
   }
 }
 
-impl<'a, T> core::iter::IntoIterator for &'a MiniVec<T> {
+impl<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::IntoIterator
+  for &'a MiniVec<T, A, H, G>
+{
   type Item = &'a T;
   type IntoIter = core::slice::Iter<'a, T>;
 
@@ -20,7 +27,9 @@ impl<'a, T> core::iter::IntoIterator for &'a MiniVec<T> {
   }
 }
 
-impl<'a, T> core::iter::IntoIterator for &'a mut MiniVec<T> {
+impl<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> core::iter::IntoIterator
+  for &'a mut MiniVec<T, A, H, G>
+{
   type Item = &'a mut T;
   type IntoIter = core::slice::IterMut<'a, T>;
 
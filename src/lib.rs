@@ -1,6 +1,9 @@
 #![no_std]
 #![warn(clippy::pedantic, missing_docs)]
-#![cfg_attr(feature = "minivec_nightly", feature(min_specialization, trusted_len))]
+#![cfg_attr(
+  feature = "minivec_nightly",
+  feature(allocator_api, min_specialization, trusted_len)
+)]
 
 //! A space-optimized version of `alloc::vec::Vec` that's only the size of a single pointer!
 //! Ideal for low-level APIs where ABI calling conventions will typically require most structs be
@@ -32,26 +35,62 @@
 //! * [`from_raw_part`](MiniVec::from_raw_part)
 //! * [`drain_vec`](MiniVec::drain_vec)
 //! * [`assume_minivec_init`](MiniVec::assume_minivec_init)
+//! * non-panicking, bounds-checked editing through [`checked_remove`](MiniVec::checked_remove),
+//!   [`try_drain`](MiniVec::try_drain) and friends, which report an [`IndexError`], [`RangeError`] or, when they
+//!   need to allocate, a [`CheckedError`]
 //!
 //! `MiniVec` has the following extensions to the existing `Vec` API:
 //! * [`push`](MiniVec::push) returns a mutable reference to the newly created element
 //!
 //! `MiniVec` is `#![no_std]`-compliant.
 //!
-//! `MiniVec` does not support zero-sized types.
+//! `MiniVec` supports zero-sized types. Much like `Vec`, a `MiniVec` of zero-sized types has a capacity of
+//! `usize::MAX` but it still requires a small allocation for its header once the first element is inserted.
+//!
+//! `MiniVec` supports custom allocators through its second type parameter. Because the `Allocator` API is not yet
+//! stable, the crate provides its own [`Allocator`] trait with the same signatures as `core::alloc::Allocator`, which
+//! it re-exports instead when compiled with the `minivec_nightly` feature. Because `MiniVec` is only a single pointer,
+//! [`MiniVec::new_in()`](MiniVec::new_in) is forced to allocate when using a stateful `Allocator` as the allocator is
+//! stored alongside the length and capacity. Zero-sized allocators are never stored and don't allocate.
+//!
+//! `MiniVec` can store its length and capacity as `u32` or `u16` instead of `usize` through its third type parameter,
+//! see [`IndexType`] and [`CompactMiniVec`]. This shrinks the header of every allocation in exchange for a smaller
+//! maximum capacity.
+//!
+//! `MiniVec` lets each vector type choose how its capacity grows through its fourth type parameter, see
+//! [`GrowthPolicy`]. The default, [`Doubling`], matches `Vec`.
+//!
+//! Every associated function that can allocate has a fallible `try_` counterpart which returns a [`TryReserveError`]
+//! instead of aborting, e.g. [`try_push`](MiniVec::try_push) and [`try_with_capacity`](MiniVec::try_with_capacity).
+//! Enabling the `no_global_oom_handling` feature removes the infallible versions altogether.
+//!
+//! [`MiniString`] builds a pointer-sized UTF-8 string on top of `MiniVec<u8>`.
+//! [`MiniBoxSlice`] is an exactly-sized, non-growable boxed slice whose header only stores its length.
+//! [`MiniRc`] and [`MiniArc`] are reference-counted slices which keep their counts in the same header as the length.
+//! [`MiniBytes`] is a cheaply cloneable, sliceable byte buffer made by [`freeze`](MiniVec::freeze)-ing a `MiniVec<u8>`.
+//! [`MiniVecDeque`] is a pointer-sized ring buffer, the counterpart of `VecDeque`.
+//! [`MiniRingBuffer`] is a fixed-capacity ring buffer which overwrites its oldest elements once full.
+//! [`MiniBinaryHeap`] is a priority queue backed by a `MiniVec`, the counterpart of `BinaryHeap`.
+//! [`MiniVecMap`] and [`MiniVecSet`] are ordered maps and sets kept sorted in a single `MiniVec`.
+//! [`MiniHashMap`] is an open-addressing hash map keeping its control bytes, keys and values in one allocation.
+//! [`MiniBitVec`] is a growable vector of bits packed into words, an eighth of the size of a `MiniVec<bool>`.
+//! [`MiniMatrix`] is a dense, row-major 2-D array keeping its shape in the header of a `MiniVec`-compatible allocation.
+//! [`MiniJagged`] is a vector of variable-length sequences whose elements and offsets share a single allocation.
+//! [`MiniVec2`], [`MiniVec3`] and [`MiniVec4`] are struct-of-arrays vectors keeping every column behind one header.
+//! [`HeaderVec`] is a `MiniVec` which stores a user-defined header in its allocation, next to the length and capacity.
+//! [`TaggedMiniVec`] is a `MiniVec` which keeps a small tag in the otherwise unused low bits of its pointer.
+//! [`MiniSmallBytes`] is a byte vector which stores short contents inline, in place of its pointer.
+//! [`MiniCow`] is a pointer-sized clone-on-write slice which either borrows its elements or owns them in a `MiniVec`.
 //!
 //! Eventual TODO's:
 //! * add myriad specializations to associated functions such as `FromIterator` once stable
-//! * add Allocator support once stable
-//!   * Because `MiniVec` is only a single pointer, `MiniVec::new_in()` will be forced to allocate when using a stateful
-//!     `Allocator`. A  theoretical implementation of `MiniVec` might be able to avoid an allocation here in the case of
-//!     a custom zero-sized `Allocator` but that has not been implemented.
 //!
 
 extern crate alloc;
 
 mod r#impl;
 
+mod allocator;
 mod as_mut;
 mod as_ref;
 mod borrow;
@@ -61,34 +100,156 @@ mod default;
 mod deref;
 mod drop;
 mod eq;
+#[cfg(not(feature = "no_global_oom_handling"))]
 mod extend;
 mod from;
 mod from_iterator;
+mod growth_policy;
 mod hash;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod header_vec;
 mod index;
+mod index_type;
 mod into_iterator;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_binary_heap;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_bit_vec;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_box_slice;
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+mod mini_bytes;
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+mod mini_cow;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_hash_map;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_jagged;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_matrix;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_ring_buffer;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_shared;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_small_bytes;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_soa;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_string;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_vec_deque;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_vec_map;
+#[cfg(not(feature = "no_global_oom_handling"))]
+mod mini_vec_set;
 mod ord;
 mod partial_eq;
 #[cfg(feature = "serde")]
 mod serde;
+mod tagged_mini_vec;
 
 use crate::r#impl::drain::make_drain_iterator;
 use crate::r#impl::drain_filter::make_drain_filter_iterator;
-use crate::r#impl::helpers::{make_layout, max_align, max_elems, next_aligned, next_capacity};
+use crate::r#impl::helpers::{
+  allocator_offset, header_bytes, header_offset, make_aligned_layout, max_align, max_aligned_elems,
+};
+#[cfg(not(feature = "no_global_oom_handling"))]
 use crate::r#impl::splice::make_splice_iterator;
 
-pub use crate::r#impl::{Drain, DrainFilter, IntoIter, Splice};
+pub use crate::allocator::{AllocError, Allocator, Global};
+pub use crate::growth_policy::{Doubling, FixedIncrement, GrowthPolicy, OneAndAHalf, PageRounded};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::header_vec::{HeaderAlloc, HeaderVec};
+pub use crate::index_type::IndexType;
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_binary_heap::{DrainSorted, MiniBinaryHeap, PeekMut};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_bit_vec::{BitIter, IterOnes, MiniBitVec};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_box_slice::{BoxSliceIntoIter, MiniBoxSlice};
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+pub use crate::mini_bytes::MiniBytes;
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+pub use crate::mini_cow::MiniCow;
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_hash_map::{
+  HashDrain, HashEntry, HashIntoIter, HashIter, HashIterMut, HashOccupiedEntry, HashVacantEntry,
+  MiniHashMap,
+};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_jagged::{JaggedIter, MiniJagged};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_matrix::{MatrixColumns, MatrixRows, MiniMatrix};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_ring_buffer::MiniRingBuffer;
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+pub use crate::mini_shared::{MiniArc, MiniArcWeak};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_shared::{MiniRc, MiniRcWeak, MiniShared, MiniWeak, RefCount};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_small_bytes::MiniSmallBytes;
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_soa::{MiniVec2, MiniVec3, MiniVec4};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_string::{FromUtf8Error, MiniString, StringDrain};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_vec_deque::{DequeDrain, DequeIntoIter, MiniVecDeque};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_vec_map::{
+  Entry, MiniVecMap, OccupiedEntry, VacantEntry, VecMapIter, VecMapIterMut,
+};
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::mini_vec_set::{Difference, Intersection, MiniVecSet, SymmetricDifference, Union};
+pub use crate::r#impl::{Drain, DrainFilter, IntoIter};
+pub use crate::tagged_mini_vec::{TaggedEdit, TaggedMiniVec};
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+pub use crate::r#impl::Splice;
 
 /// `MiniVec` is a space-optimized implementation of `alloc::vec::Vec` that is only the size of a single pointer and
 /// also extends portions of its API. `MiniVec` also aims to bring as many Nightly features from `Vec` to stable
 /// toolchains as is possible. In many cases, it is a drop-in replacement for `Vec`.
 ///
+/// `MiniVec` is parameterized over an [`Allocator`] which defaults to [`Global`]. Zero-sized allocators aren't stored
+/// anywhere while stateful ones are stored inside of the allocation, alongside the length and capacity. This keeps
+/// `MiniVec` the size of a pointer no matter the allocator but it means that a `MiniVec` using a stateful allocator
+/// must allocate as soon as it's constructed.
+///
+/// The third type parameter is the [`IndexType`] used to store the length and capacity. It defaults to `usize` and
+/// can be narrowed to `u32` or `u16` to shrink the header of the allocation, see [`CompactMiniVec`].
+///
+/// The fourth type parameter is the [`GrowthPolicy`] that decides how much the capacity grows by whenever the vector
+/// runs out of room. It defaults to [`Doubling`].
+///
 #[repr(transparent)]
-pub struct MiniVec<T> {
+pub struct MiniVec<T, A: Allocator = Global, H: IndexType = usize, G: GrowthPolicy = Doubling> {
   buf: core::ptr::NonNull<u8>,
-  phantom: core::marker::PhantomData<T>,
+  phantom: core::marker::PhantomData<(T, A, H, G)>,
 }
 
+/// `CompactMiniVec` is a [`MiniVec`] that stores its length and capacity as `u32`, halving the size of its header on
+/// 64-bit targets at the cost of a maximum capacity of `u32::MAX >> 1` elements.
+///
+/// The top bit of the stored capacity is reserved for bookkeeping, which is why the maximum is `u32::MAX >> 1` and
+/// not `u32::MAX`.
+///
+/// # Example
+///
+/// ```
+/// let mut vec = minivec::CompactMiniVec::<i32>::new();
+/// vec.push(1);
+/// vec.extend([2, 3]);
+///
+/// assert_eq!(vec, [1, 2, 3]);
+/// assert_eq!(
+///   vec.try_reserve(usize::MAX >> 16).unwrap_err().kind(),
+///   minivec::TryReserveErrorKind::CapacityOverflow
+/// );
+/// ```
+///
+pub type CompactMiniVec<T> = MiniVec<T, Global, u32>;
+
 /// `LayoutErr` is the error type returned by the alignment-based associated functions for `MiniVec`
 ///
 #[derive(core::fmt::Debug)]
@@ -138,6 +299,18 @@ impl TryReserveError {
   }
 }
 
+impl core::fmt::Display for TryReserveError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str("memory allocation failed")?;
+    f.write_str(match self.kind {
+      TryReserveErrorKind::CapacityOverflow => {
+        " because the computed capacity exceeded the collection's maximum"
+      }
+      TryReserveErrorKind::AllocError { .. } => " because the memory allocator returned an error",
+    })
+  }
+}
+
 impl core::convert::From<TryReserveErrorKind> for TryReserveError {
   #[inline]
   fn from(kind: TryReserveErrorKind) -> Self {
@@ -145,16 +318,182 @@ impl core::convert::From<TryReserveErrorKind> for TryReserveError {
   }
 }
 
+/// `IndexError` is the error type returned from the `checked_` family of functions when the supplied index is out of
+/// bounds.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IndexError {
+  /// `index` is the offending index.
+  ///
+  pub index: usize,
+  /// `len` is the length of the vector at the time of the call.
+  ///
+  pub len: usize,
+}
+
+impl core::fmt::Display for IndexError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(
+      f,
+      "index (is {}) is out of bounds for a vector of len (is {})",
+      self.index, self.len
+    )
+  }
+}
+
+/// `RangeError` is the error type returned from the range-based `try_` functions such as
+/// [`try_drain`](MiniVec::try_drain) when the supplied range is invalid for the vector.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RangeError {
+  /// `StartOverflow` is returned when an excluded start bound of `usize::MAX` can't be converted to an index.
+  ///
+  StartOverflow,
+  /// `EndOverflow` is returned when an included end bound of `usize::MAX` can't be converted to an index.
+  ///
+  EndOverflow,
+  /// `StartAfterEnd` is returned when the range begins after it ends.
+  ///
+  StartAfterEnd {
+    /// `start` is the normalized, inclusive start of the range.
+    ///
+    start: usize,
+    /// `end` is the normalized, exclusive end of the range.
+    ///
+    end: usize,
+  },
+  /// `EndOutOfBounds` is returned when the range ends past the end of the vector.
+  ///
+  EndOutOfBounds {
+    /// `end` is the normalized, exclusive end of the range.
+    ///
+    end: usize,
+    /// `len` is the length of the vector at the time of the call.
+    ///
+    len: usize,
+  },
+}
+
+impl core::fmt::Display for RangeError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match *self {
+      RangeError::StartOverflow => f.write_str("start index exceeded numeric limits"),
+      RangeError::EndOverflow => f.write_str("end index exceeded numeric limits"),
+      RangeError::StartAfterEnd { start, end } => write!(
+        f,
+        "start index (is {start}) should be <= end index (is {end})"
+      ),
+      RangeError::EndOutOfBounds { end, len } => {
+        write!(f, "end index (is {end}) should be <= len (is {len})")
+      }
+    }
+  }
+}
+
+/// `CheckedError` is the error type returned from the bounds-checked functions which may need to allocate, such as
+/// [`checked_insert`](MiniVec::checked_insert).
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CheckedError {
+  /// `Index` is returned when the supplied index is out of bounds.
+  ///
+  Index(IndexError),
+  /// `Range` is returned when the supplied range is invalid for the vector.
+  ///
+  Range(RangeError),
+  /// `Reserve` is returned when the vector could not grow.
+  ///
+  Reserve(TryReserveError),
+}
+
+impl core::fmt::Display for CheckedError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      CheckedError::Index(e) => e.fmt(f),
+      CheckedError::Range(e) => e.fmt(f),
+      CheckedError::Reserve(e) => e.fmt(f),
+    }
+  }
+}
+
+impl core::convert::From<IndexError> for CheckedError {
+  fn from(e: IndexError) -> Self {
+    CheckedError::Index(e)
+  }
+}
+
+impl core::convert::From<RangeError> for CheckedError {
+  fn from(e: RangeError) -> Self {
+    CheckedError::Range(e)
+  }
+}
+
+impl core::convert::From<TryReserveError> for CheckedError {
+  fn from(e: TryReserveError) -> Self {
+    CheckedError::Reserve(e)
+  }
+}
+
+// converts the user-supplied `range` into a pair of `[start, end)` indices that are valid for a vector of length `len`
+//
+fn normalize_range<R>(range: &R, len: usize) -> Result<(usize, usize), RangeError>
+where
+  R: core::ops::RangeBounds<usize>,
+{
+  let start_idx = match range.start_bound() {
+    core::ops::Bound::Included(&n) => n,
+    core::ops::Bound::Excluded(&n) => n.checked_add(1).ok_or(RangeError::StartOverflow)?,
+    core::ops::Bound::Unbounded => 0,
+  };
+
+  let end_idx = match range.end_bound() {
+    core::ops::Bound::Included(&n) => n.checked_add(1).ok_or(RangeError::EndOverflow)?,
+    core::ops::Bound::Excluded(&n) => n,
+    core::ops::Bound::Unbounded => len,
+  };
+
+  if start_idx > end_idx {
+    return Err(RangeError::StartAfterEnd {
+      start: start_idx,
+      end: end_idx,
+    });
+  }
+
+  if end_idx > len {
+    return Err(RangeError::EndOutOfBounds { end: end_idx, len });
+  }
+
+  Ok((start_idx, end_idx))
+}
+
+// the infallible counterpart of the `try_reserve` family, mirroring how the stdlib reacts to each failure
+//
+fn handle_reserve<T>(result: Result<T, TryReserveError>) -> T {
+  match result.map_err(|e| e.kind()) {
+    Ok(v) => v,
+    Err(TryReserveErrorKind::CapacityOverflow) => panic!("capacity overflow"),
+    Err(TryReserveErrorKind::AllocError { layout }) => alloc::alloc::handle_alloc_error(layout),
+  }
+}
+
 #[derive(Clone, Copy)]
-struct Header {
-  len: usize,
-  cap: usize,
+struct Header<H> {
+  len: H,
+  cap: H,
 }
 
+// every empty `MiniVec` points at this header instead of owning an allocation
+//
+// it must never be written through, which is why `set_len()` and friends check for it before touching the header
+//
+// it's the widest header there is so that every `IndexType` can read a zeroed header out of it
+//
+static EMPTY_HEADER: Header<usize> = Header { len: 0, cap: 0 };
+
 #[test]
 #[allow(clippy::clone_on_copy)]
 fn header_clone() {
-  let header = Header { len: 0, cap: 0 };
+  let header = Header::<usize> { len: 0, cap: 0 };
 
   let header2 = header.clone();
 
@@ -162,67 +501,351 @@ fn header_clone() {
   assert_eq!(header2.cap, header.cap);
 }
 
-impl<T> MiniVec<T> {
-  const N: usize = next_aligned(core::mem::size_of::<Header>(), max_align::<T>());
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> MiniVec<T, A, H, G> {
+  const N: usize = header_bytes::<T, A, H>();
+
+  // the top bit of `Header::cap` marks an allocation made by `with_alignment()`
+  //
+  // such allocations store their alignment in the `usize` directly preceding the header
+  //
+  const ALIGNED_FLAG: usize = (H::MAX >> 1) + 1;
 
-  fn header(&self) -> &Header {
+  fn header(&self) -> &Header<H> {
     #[allow(clippy::cast_ptr_alignment)]
     unsafe {
-      &*(self.buf.as_ptr() as *const Header)
+      &*(self.buf.as_ptr() as *const Header<H>)
     }
   }
 
-  fn header_mut(&mut self) -> &mut Header {
+  fn header_mut(&mut self) -> &mut Header<H> {
     #[allow(clippy::cast_ptr_alignment)]
     unsafe {
-      &mut *self.buf.as_ptr().cast::<Header>()
+      &mut *self.buf.as_ptr().cast::<Header<H>>()
     }
   }
 
+  unsafe fn write_header(buf: *mut u8, len: usize, cap: usize) {
+    #[allow(clippy::cast_ptr_alignment)]
+    core::ptr::write(
+      buf.cast::<Header<H>>(),
+      Header {
+        len: H::from_usize(len),
+        cap: H::from_usize(cap),
+      },
+    );
+  }
+
   fn data(&self) -> *mut T {
+    // the static empty header is only aligned to `Header<usize>` so over-aligned types can't derive their data pointer
+    // from it
+    //
+    if core::mem::align_of::<T>() > core::mem::align_of::<Header<usize>>() && self.is_default() {
+      return core::ptr::NonNull::dangling().as_ptr();
+    }
+
     unsafe { self.buf.as_ptr().add(Self::N).cast::<T>() }
   }
 
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), Self::empty_buf().as_ptr())
+  }
+
+  const fn empty_buf() -> core::ptr::NonNull<u8> {
+    let p = core::ptr::addr_of!(EMPTY_HEADER).cast::<u8>().cast_mut();
+    unsafe { core::ptr::NonNull::new_unchecked(p) }
+  }
+
+  const fn from_buf(buf: core::ptr::NonNull<u8>) -> MiniVec<T, A, H, G> {
+    MiniVec {
+      buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  // the inverse of `data()`, the backbone of `from_raw_part()`
+  //
+  unsafe fn from_data(ptr: *mut T) -> MiniVec<T, A, H, G> {
+    debug_assert!(!ptr.is_null());
+
+    if core::mem::align_of::<T>() > core::mem::align_of::<Header<usize>>()
+      && ptr == core::ptr::NonNull::dangling().as_ptr()
+    {
+      return Self::from_buf(Self::empty_buf());
+    }
+
+    let p = ptr.cast::<u8>();
+    let buf = p.sub(Self::N);
+
+    Self::from_buf(core::ptr::NonNull::<u8>::new_unchecked(buf))
+  }
+
+  // the capacity of a vector of ZSTs is never read back so it's stored without the bit reserved for the flag
+  //
+  const fn stored_capacity(capacity: usize) -> usize {
+    capacity & !Self::ALIGNED_FLAG
+  }
+
+  fn raw_capacity(&self) -> usize {
+    self.header().cap.to_usize() & !Self::ALIGNED_FLAG
+  }
+
+  // the number of elements which can be written without allocating first, which unlike `capacity()` is always `0` for
+  // the static empty header as its length must never be written to, not even for ZSTs
+  //
+  fn writable_capacity(&self) -> usize {
+    if self.is_default() {
+      return 0;
+    }
+
+    self.capacity()
+  }
+
+  fn aligned_flag(&self) -> usize {
+    self.header().cap.to_usize() & Self::ALIGNED_FLAG
+  }
+
+  fn alignment(&self) -> usize {
+    if self.aligned_flag() == 0 {
+      return max_align::<T, A, H>();
+    }
+
+    // headers narrower than a `usize` only guarantee their own alignment
+    //
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      self
+        .buf
+        .as_ptr()
+        .sub(core::mem::size_of::<usize>())
+        .cast::<usize>()
+        .read_unaligned()
+    }
+  }
+
+  // returns the start of the underlying allocation along with the layout it was allocated with
+  //
+  fn allocation(&self) -> (core::ptr::NonNull<u8>, alloc::alloc::Layout) {
+    let alignment = self.alignment();
+    let p = unsafe { self.buf.as_ptr().sub(header_offset::<T, A, H>(alignment)) };
+
+    // the layout was already computed successfully when the allocation was made
+    //
+    let layout =
+      unsafe { make_aligned_layout::<T, A, H>(self.raw_capacity(), alignment).unwrap_unchecked() };
+
+    (unsafe { core::ptr::NonNull::new_unchecked(p) }, layout)
+  }
+
+  // stores `alloc` in the header beginning at `buf`, zero-sized allocators are conjured up out of thin air whenever
+  // they're needed so they're simply forgotten
+  //
+  unsafe fn write_allocator(buf: *mut u8, alloc: A) {
+    if core::mem::size_of::<A>() == 0 {
+      core::mem::forget(alloc);
+    } else {
+      #[allow(clippy::cast_ptr_alignment)]
+      core::ptr::write(buf.add(allocator_offset::<A, H>()).cast::<A>(), alloc);
+    }
+  }
+
+  // `try_allocate_in` creates a vector with storage for `capacity` elements whose data is aligned to `alignment`
+  //
+  // stateful allocators need somewhere to live so they always allocate, even for a capacity of `0`
+  //
+  fn try_allocate_in(
+    capacity: usize,
+    alignment: usize,
+    alloc: A,
+  ) -> Result<MiniVec<T, A, H, G>, TryReserveError> {
+    let is_over_aligned = alignment > max_align::<T, A, H>();
+
+    if core::mem::size_of::<A>() == 0 && !is_over_aligned {
+      core::mem::forget(alloc);
+
+      let mut v = Self::from_buf(Self::empty_buf());
+      v.grow(capacity)?;
+      return Ok(v);
+    }
+
+    let alignment = if is_over_aligned {
+      alignment
+    } else {
+      max_align::<T, A, H>()
+    };
+
+    let capacity = if core::mem::size_of::<T>() == 0 && capacity > 0 {
+      max_aligned_elems::<T, A, H>(alignment)
+    } else {
+      capacity
+    };
+
+    if capacity > max_aligned_elems::<T, A, H>(alignment) {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
+    }
+
+    let Some(layout) = make_aligned_layout::<T, A, H>(capacity, alignment) else {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
+    };
+
+    let p = match alloc.allocate(layout) {
+      Ok(p) => p.cast::<u8>().as_ptr(),
+      Err(_) => return Err(From::from(TryReserveErrorKind::AllocError { layout })),
+    };
+
+    let buf = unsafe { p.add(header_offset::<T, A, H>(alignment)) };
+
+    let flag = if is_over_aligned {
+      #[allow(clippy::cast_ptr_alignment)]
+      unsafe {
+        core::ptr::write_unaligned(
+          buf.sub(core::mem::size_of::<usize>()).cast::<usize>(),
+          alignment,
+        );
+      }
+
+      Self::ALIGNED_FLAG
+    } else {
+      0
+    };
+
+    unsafe {
+      Self::write_header(buf, 0, Self::stored_capacity(capacity) | flag);
+      Self::write_allocator(buf, alloc);
+    }
+
+    Ok(Self::from_buf(unsafe {
+      core::ptr::NonNull::<u8>::new_unchecked(buf)
+    }))
+  }
+
+  // creates an empty vector with the same alignment and allocator as `self`
+  //
+  fn try_with_capacity_like(&self, capacity: usize) -> Result<MiniVec<T, A, H, G>, TryReserveError>
+  where
+    A: Clone,
+  {
+    Self::try_allocate_in(capacity, self.alignment(), self.allocator().clone())
+  }
+
   fn grow(&mut self, capacity: usize) -> Result<(), TryReserveError> {
     debug_assert!(capacity >= self.len());
 
-    let old_capacity = self.capacity();
-    let new_capacity = capacity;
+    let old_capacity = self.writable_capacity();
+    let alignment = self.alignment();
+
+    // a vector of ZSTs only ever needs its header so any non-zero capacity is the maximum one
+    //
+    let new_capacity = if core::mem::size_of::<T>() == 0 && capacity > 0 {
+      max_aligned_elems::<T, A, H>(alignment)
+    } else {
+      capacity
+    };
 
     if new_capacity == old_capacity {
       return Ok(());
     }
 
-    let new_layout = make_layout::<T>(new_capacity);
+    // over-aligned vectors and vectors with stateful allocators hold on to their header so that they remember their
+    // alignment and allocator
+    //
+    let flag = self.aligned_flag();
+
+    if new_capacity == 0 && flag == 0 && core::mem::size_of::<A>() == 0 {
+      let (p, layout) = self.allocation();
+      unsafe { self.allocator().deallocate(p, layout) };
+      self.buf = Self::empty_buf();
+      return Ok(());
+    }
+
+    if new_capacity > max_aligned_elems::<T, A, H>(alignment) {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
+    }
+
+    let Some(new_layout) = make_aligned_layout::<T, A, H>(new_capacity, alignment) else {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
+    };
 
     let len = self.len();
 
-    let new_buf = {
-      let old_layout = make_layout::<T>(old_capacity);
-      unsafe { alloc::alloc::realloc(self.buf.as_ptr(), old_layout, new_layout.size()) }
+    let new_buf = if self.is_default() {
+      self.allocator().allocate(new_layout)
+    } else {
+      // a stateful allocator lives inside of the block it's being asked to reallocate so it's moved out for the
+      // duration of the call
+      //
+      let (old_buf, old_layout) = self.allocation();
+      let alloc = unsafe { core::ptr::read(self.allocator()) };
+
+      let result = if new_layout.size() > old_layout.size() {
+        unsafe { alloc.grow(old_buf, old_layout, new_layout) }
+      } else {
+        unsafe { alloc.shrink(old_buf, old_layout, new_layout) }
+      };
+
+      match result {
+        Ok(p) => {
+          unsafe {
+            Self::write_allocator(
+              p.cast::<u8>()
+                .as_ptr()
+                .add(header_offset::<T, A, H>(alignment)),
+              alloc,
+            );
+          };
+          Ok(p)
+        }
+        Err(e) => {
+          core::mem::forget(alloc);
+          Err(e)
+        }
+      }
     };
 
-    if new_buf.is_null() {
+    let Ok(new_buf) = new_buf else {
       return Err(From::from(TryReserveErrorKind::AllocError {
         layout: new_layout,
       }));
-    }
+    };
 
-    let header = Header {
-      len,
-      cap: new_capacity,
+    let new_buf = unsafe {
+      new_buf
+        .cast::<u8>()
+        .as_ptr()
+        .add(header_offset::<T, A, H>(alignment))
     };
 
-    #[allow(clippy::cast_ptr_alignment)]
-    unsafe {
-      core::ptr::write(new_buf.cast::<Header>(), header);
-    }
+    unsafe { Self::write_header(new_buf, len, Self::stored_capacity(new_capacity) | flag) };
 
     self.buf = unsafe { core::ptr::NonNull::<u8>::new_unchecked(new_buf) };
 
     Ok(())
   }
 
+  /// `allocator` returns a reference to the [`Allocator`] backing this vector.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::MiniVec::<i32>::new_in(minivec::Global);
+  /// let _alloc: &minivec::Global = vec.allocator();
+  /// ```
+  ///
+  #[must_use]
+  pub fn allocator(&self) -> &A {
+    if core::mem::size_of::<A>() == 0 {
+      return unsafe { core::ptr::NonNull::<A>::dangling().as_ref() };
+    }
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self
+        .buf
+        .as_ptr()
+        .add(allocator_offset::<A, H>())
+        .cast::<A>()
+    }
+  }
+
   /// `append` moves every element from `other` to the back of `self`. `other.is_empty()` is `true` once this operation
   /// completes and its capacity is unaffected.
   ///
@@ -236,7 +859,8 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec2, []);
   /// ```
   ///
-  pub fn append(&mut self, other: &mut MiniVec<T>) {
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn append(&mut self, other: &mut Self) {
     if other.is_empty() {
       return;
     }
@@ -359,62 +983,199 @@ impl<T> MiniVec<T> {
   ///
   #[must_use]
   pub fn capacity(&self) -> usize {
-    self.header().cap
+    // the capacity stored for ZSTs is only nominal, any vector of them can hold the maximum number of elements, even
+    // one which hasn't allocated its header yet
+    //
+    if core::mem::size_of::<T>() == 0 {
+      return max_aligned_elems::<T, A, H>(self.alignment());
+    }
+
+    self.raw_capacity()
   }
 
-  /// `clear` clears the current contents of the `MiniVec`. Afterwards, [`len()`](MiniVec::len)
-  /// will return 0. [`capacity()`](MiniVec::capacity) is not affected.
+  /// `checked_insert` is the non-panicking version of [`insert`](MiniVec::insert), returning an error instead of
+  /// panicking when `index > len()` or aborting when the vector can't grow.
   ///
-  /// Logically equivalent to calling [`minivec::MiniVec::truncate(0)`](MiniVec::truncate).
+  /// # Errors
   ///
-  /// Note: destruction order of the contained elements is not guaranteed.
+  /// Returns a `CheckedError` if `index > len()` or the vector could not grow, in which case the vector is left
+  /// untouched and `element` is dropped.
   ///
   /// # Example
   ///
   /// ```
-  /// let mut vec = minivec::mini_vec![-1; 256];
-  ///
-  /// let cap = vec.capacity();
-  ///
-  /// assert_eq!(vec.len(), 256);
-  ///
-  /// vec.clear();
+  /// let mut vec = minivec::mini_vec![0, 1, 2];
+  /// vec.checked_insert(1, 1337).unwrap();
+  /// assert_eq!(vec, [0, 1337, 1, 2]);
   ///
-  /// assert_eq!(vec.len(), 0);
-  /// assert_eq!(vec.capacity(), cap);
+  /// let err = vec.checked_insert(5, 7331).unwrap_err();
+  /// assert_eq!(err, minivec::IndexError { index: 5, len: 4 }.into());
   /// ```
   ///
-  pub fn clear(&mut self) {
-    self.truncate(0);
+  pub fn checked_insert(&mut self, index: usize, element: T) -> Result<(), CheckedError> {
+    let len = self.len();
+    if index > len {
+      return Err(From::from(IndexError { index, len }));
+    }
+
+    Ok(self.try_insert(index, element)?)
   }
 
-  /// `dedeup` "de-duplicates" all adjacent identical values in the vector.
+  /// `checked_remove` is the non-panicking version of [`remove`](MiniVec::remove), returning an error instead of
+  /// panicking when `index >= len()`.
   ///
-  /// Logically equivalent to calling [`minivec::MiniVec::dedup_by(|x, y| x == y)`](MiniVec::dedup_by).
+  /// # Errors
+  ///
+  /// Returns an `IndexError` if `index >= len()`.
   ///
   /// # Example
   ///
   /// ```
-  /// let mut v = minivec::mini_vec![1, 2, 1, 1, 3, 3, 3, 4, 5, 4];
-  /// v.dedup();
+  /// let mut vec = minivec::mini_vec![0, 1, 2];
+  /// assert_eq!(vec.checked_remove(0), Ok(0));
+  /// assert_eq!(vec, [1, 2]);
   ///
-  /// assert_eq!(v, [1, 2, 1, 3, 4, 5, 4]);
+  /// let err = vec.checked_remove(2).unwrap_err();
+  /// assert_eq!((err.index, err.len), (2, 2));
   /// ```
   ///
-  pub fn dedup(&mut self)
-  where
-    T: PartialEq,
-  {
-    self.dedup_by(|x, y| x == y);
+  pub fn checked_remove(&mut self, index: usize) -> Result<T, IndexError> {
+    let len = self.len();
+    if index >= len {
+      return Err(IndexError { index, len });
+    }
+
+    unsafe {
+      let p = self.as_mut_ptr().add(index);
+
+      let x = core::ptr::read(p);
+
+      let src = p.add(1);
+      let dst = p;
+      let count = len - index - 1;
+      core::ptr::copy(src, dst, count);
+
+      self.set_len(len - 1);
+
+      Ok(x)
+    }
   }
 
-  /// `dedup_by` "de-duplicates" all adjacent elements for which the supplied binary predicate
-  /// returns true.
+  /// `checked_split_off` is the non-panicking version of [`split_off`](MiniVec::split_off), returning an error
+  /// instead of panicking when `at > len()` or aborting when the new vector can't be allocated.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CheckedError` if `at > len()` or the new vector could not be allocated, in which case the vector is
+  /// left untouched.
   ///
   /// # Example
   ///
   /// ```
-  /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+  /// let mut vec = minivec::mini_vec![0, 1, 2, 3];
+  /// let tail = vec.checked_split_off(1).unwrap();
+  ///
+  /// assert_eq!(vec, [0]);
+  /// assert_eq!(tail, [1, 2, 3]);
+  ///
+  /// assert!(vec.checked_split_off(2).is_err());
+  /// ```
+  ///
+  pub fn checked_split_off(&mut self, at: usize) -> Result<MiniVec<T, A, H, G>, CheckedError>
+  where
+    A: Clone,
+  {
+    let len = self.len();
+    if at > len {
+      return Err(From::from(IndexError { index: at, len }));
+    }
+
+    Ok(self.try_split_off(at)?)
+  }
+
+  /// `checked_swap_remove` is the non-panicking version of [`swap_remove`](MiniVec::swap_remove), returning an error
+  /// instead of panicking when `index >= len()`.
+  ///
+  /// # Errors
+  ///
+  /// Returns an `IndexError` if `index >= len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+  /// assert_eq!(vec.checked_swap_remove(0), Ok(1));
+  /// assert_eq!(vec, [4, 2, 3]);
+  ///
+  /// assert!(vec.checked_swap_remove(3).is_err());
+  /// ```
+  ///
+  pub fn checked_swap_remove(&mut self, index: usize) -> Result<T, IndexError> {
+    let len = self.len();
+    if index >= len {
+      return Err(IndexError { index, len });
+    }
+
+    unsafe { core::ptr::swap(self.as_mut_ptr().add(len - 1), self.as_mut_ptr().add(index)) };
+
+    let x = unsafe { core::ptr::read(self.as_ptr().add(len - 1)) };
+    self.header_mut().len = H::from_usize(len - 1);
+    Ok(x)
+  }
+
+  /// `clear` clears the current contents of the `MiniVec`. Afterwards, [`len()`](MiniVec::len)
+  /// will return 0. [`capacity()`](MiniVec::capacity) is not affected.
+  ///
+  /// Logically equivalent to calling [`minivec::MiniVec::truncate(0)`](MiniVec::truncate).
+  ///
+  /// Note: destruction order of the contained elements is not guaranteed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![-1; 256];
+  ///
+  /// let cap = vec.capacity();
+  ///
+  /// assert_eq!(vec.len(), 256);
+  ///
+  /// vec.clear();
+  ///
+  /// assert_eq!(vec.len(), 0);
+  /// assert_eq!(vec.capacity(), cap);
+  /// ```
+  ///
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  /// `dedeup` "de-duplicates" all adjacent identical values in the vector.
+  ///
+  /// Logically equivalent to calling [`minivec::MiniVec::dedup_by(|x, y| x == y)`](MiniVec::dedup_by).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut v = minivec::mini_vec![1, 2, 1, 1, 3, 3, 3, 4, 5, 4];
+  /// v.dedup();
+  ///
+  /// assert_eq!(v, [1, 2, 1, 3, 4, 5, 4]);
+  /// ```
+  ///
+  pub fn dedup(&mut self)
+  where
+    T: PartialEq,
+  {
+    self.dedup_by(|x, y| x == y);
+  }
+
+  /// `dedup_by` "de-duplicates" all adjacent elements for which the supplied binary predicate
+  /// returns true.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
   ///
   /// vec.dedup_by(|x, y| *x + *y < 8);
   ///
@@ -425,78 +1186,73 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec, [1, 7, 8, 9, 10]);
   /// ```
   ///
-  #[allow(clippy::cast_sign_loss)]
   pub fn dedup_by<F>(&mut self, mut pred: F)
   where
     F: FnMut(&mut T, &mut T) -> bool,
   {
-    struct DropGuard<'a, T> {
-      read: *const T,
-      write: *mut T,
-      last: *const T,
-      len: usize,
-      vec: &'a mut MiniVec<T>,
+    // `read` and `write` are indices rather than pointers so that ZSTs are handled uniformly
+    //
+    // the elements in `[write, read)` have either been dropped or moved from so if `pred` panics, the guard shifts
+    // the unread tail down to close the gap
+    //
+    struct DropGuard<'a, T, A: Allocator, H: IndexType, G: GrowthPolicy> {
+      read: usize,
+      write: usize,
+      vec: &'a mut MiniVec<T, A, H, G>,
     }
 
-    impl<'a, T> Drop for DropGuard<'a, T> {
+    impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> Drop for DropGuard<'_, T, A, H, G> {
       fn drop(&mut self) {
+        let len = self.vec.len();
+
         if self.read != self.write {
-          let src = self.read;
-          let dst = self.write;
-          let count = unsafe { self.last.offset_from(self.read) as usize };
+          let data = self.vec.as_mut_ptr();
+          let src = unsafe { data.add(self.read) };
+          let dst = unsafe { data.add(self.write) };
+          let count = len - self.read;
           unsafe { core::ptr::copy(src, dst, count) };
         }
 
-        unsafe { self.vec.set_len(self.len) };
+        unsafe { self.vec.set_len(len - (self.read - self.write)) };
       }
     }
 
-    let mut len = self.len();
+    let len = self.len();
     if len < 2 {
       return;
     }
 
     let data = self.as_mut_ptr();
 
-    let mut read = unsafe { data.add(1) };
-    let mut write = read;
-
-    let last = unsafe { data.add(len) };
+    let mut guard = DropGuard {
+      read: 1,
+      write: 1,
+      vec: self,
+    };
 
-    while read < last {
-      let mut guard = DropGuard {
-        read,
-        write,
-        last,
-        len,
-        vec: self,
-      };
+    while guard.read < len {
+      let read = unsafe { data.add(guard.read) };
+      let last_kept = unsafe { data.add(guard.write - 1) };
 
-      let matches = unsafe { pred(&mut *read, &mut *write.sub(1)) };
+      let matches = unsafe { pred(&mut *read, &mut *last_kept) };
       if matches {
-        let v = unsafe { core::ptr::read(read) };
-        len -= 1;
-        guard.len -= 1;
-        guard.read = unsafe { guard.read.add(1) };
-
-        core::mem::drop(v);
+        guard.read += 1;
+        unsafe { core::ptr::drop_in_place(read) };
       } else {
-        if read != write {
-          let src = read;
-          let dst = write;
-          let count = 1;
-          unsafe { core::ptr::copy(src, dst, count) };
+        if guard.read != guard.write {
+          let dst = unsafe { data.add(guard.write) };
+          unsafe { core::ptr::copy_nonoverlapping(read, dst, 1) };
         }
 
-        write = unsafe { write.add(1) };
+        guard.read += 1;
+        guard.write += 1;
       }
-
-      read = unsafe { read.add(1) };
-
-      core::mem::forget(guard);
     }
 
-    unsafe { self.set_len(len) };
+    let write = guard.write;
+    core::mem::forget(guard);
+
+    unsafe { self.set_len(write) };
   }
 
   /// `dedup_by_key` "de-duplicates" all adjacent elements where `key(elem1) == key(elem2)`.
@@ -540,49 +1296,14 @@ impl<T> MiniVec<T> {
   /// assert_eq!(other_vec, [4, 5, 6, 7, 8, 9]);
   /// ```
   ///
-  pub fn drain<R>(&mut self, range: R) -> Drain<T>
+  pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, A, H, G>
   where
     R: core::ops::RangeBounds<usize>,
   {
-    let len = self.len();
-
-    let start_idx = match range.start_bound() {
-      core::ops::Bound::Included(&n) => n,
-      core::ops::Bound::Excluded(&n) => {
-        n.checked_add(1).expect("Start idx exceeded numeric limits")
-      }
-      core::ops::Bound::Unbounded => 0,
-    };
-
-    let end_idx = match range.end_bound() {
-      core::ops::Bound::Included(&n) => n.checked_add(1).expect("End idx exceeded numeric limits"),
-      core::ops::Bound::Excluded(&n) => n,
-      core::ops::Bound::Unbounded => len,
-    };
-
-    assert!(
-      (start_idx <= end_idx),
-      "start drain index (is {}) should be <= end drain index (is {})",
-      start_idx,
-      end_idx
-    );
-
-    assert!(
-      (end_idx <= len),
-      "end drain index (is {}) should be <= len (is {})",
-      end_idx,
-      len
-    );
-
-    let data = self.as_mut_ptr();
-
-    if !data.is_null() {
-      unsafe {
-        self.set_len(start_idx);
-      }
+    match self.try_drain(range) {
+      Ok(drain) => drain,
+      Err(e) => panic!("invalid drain range: {}", e),
     }
-
-    make_drain_iterator(self, data, len - end_idx, start_idx, end_idx)
   }
 
   /// `drain_filter` creates a new [`DrainFilter`](DrainFilter) iterator that when iterated will
@@ -612,7 +1333,7 @@ impl<T> MiniVec<T> {
   /// );
   /// ```
   ///
-  pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F>
+  pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F, A, H, G>
   where
     F: core::ops::FnMut(&mut T) -> bool,
   {
@@ -624,7 +1345,9 @@ impl<T> MiniVec<T> {
   /// `drain_vec` returns a new instance of a `MiniVec`, created by moving the content out of `self`.
   ///
   /// Compared to `drain` method, this is just simple swap of pointers. As result, any pointer to `self` becomes
-  /// invalid.
+  /// invalid. `self` is left without an allocation so this function never allocates, unless `self` was created by
+  /// [`with_alignment`](MiniVec::with_alignment) in which case `self` keeps a small allocation to remember its
+  /// alignment.
   ///
   /// # Example
   ///
@@ -641,102 +1364,16 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.len(), 0);
   /// assert_eq!(new_vec, []);
   /// ```
-  pub fn drain_vec(&mut self) -> Self {
-    let mut result = Self::new();
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn drain_vec(&mut self) -> Self
+  where
+    A: Clone,
+  {
+    let mut result = handle_reserve(self.try_with_capacity_like(0));
     core::mem::swap(&mut result, self);
     result
   }
 
-  /// `from_raw_part` reconstructs a `MiniVec` from a previous call to [`MiniVec::as_mut_ptr`](MiniVec::as_mut_ptr)
-  /// or the pointer from [`into_raw_parts`](MiniVec::into_raw_parts).
-  ///
-  /// # Safety
-  ///
-  /// `from_raw_part` is incredibly unsafe and can only be used with the value of
-  /// `MiniVec::as_mut_ptr`. This is because the allocation for the backing array stores metadata
-  /// at its head and is not guaranteed to be stable so users are discouraged from attempting to
-  /// support this directly.
-  ///
-  /// # Panics
-  ///
-  /// Panics in debug mode if the supplied pointer is null.
-  ///
-  /// # Example
-  ///
-  /// ```
-  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-  ///
-  /// let ptr = vec.as_mut_ptr();
-  ///
-  /// std::mem::forget(vec);
-  ///
-  /// let new_vec = unsafe { minivec::MiniVec::from_raw_part(ptr) };
-  ///
-  /// assert_eq!(new_vec, [1, 2, 3, 4]);
-  /// ```
-  ///
-  #[allow(clippy::cast_ptr_alignment)]
-  pub unsafe fn from_raw_part(ptr: *mut T) -> MiniVec<T> {
-    debug_assert!(!ptr.is_null());
-
-    let p = ptr.cast::<u8>();
-    let buf = p.sub(Self::N);
-
-    MiniVec {
-      buf: core::ptr::NonNull::<u8>::new_unchecked(buf),
-      phantom: core::marker::PhantomData,
-    }
-  }
-
-  /// `from_raw_parts` is an API-compatible version of `alloc::vec::Vec::from_raw_parts`. Because
-  /// of `MiniVec`'s optimized layout, it's not strictly required for a user to pass the length
-  /// and capacity explicitly.
-  ///
-  /// Like [`MiniVec::from_raw_part`](MiniVec::from_raw_part), this function is only safe to use
-  /// with the result of a call to [`MiniVec::as_mut_ptr()`](MiniVec::as_mut_ptr).
-  ///
-  /// # Panics
-  ///
-  /// Panics in debug mode if the supplied pointer is null.
-  ///
-  /// # Safety
-  ///
-  /// A very unsafe function that should only really be used when passing the vector to a C API.
-  ///
-  /// Does not support over-aligned allocations. The alignment of the pointer must be that of its natural alignment.
-  ///
-  /// # Example
-  ///
-  /// ```
-  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
-  /// let len = vec.len();
-  /// let cap = vec.capacity();
-  ///
-  /// let ptr = vec.as_mut_ptr();
-  ///
-  /// std::mem::forget(vec);
-  ///
-  /// let new_vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
-  ///
-  /// assert_eq!(new_vec, [1, 2, 3, 4]);
-  /// ```
-  ///
-  #[allow(clippy::cast_ptr_alignment)]
-  pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> MiniVec<T> {
-    debug_assert!(!ptr.is_null());
-
-    let p = ptr.cast::<u8>();
-    let buf = p.sub(Self::N);
-
-    debug_assert!((*buf.cast::<Header>()).len == length);
-    debug_assert!((*buf.cast::<Header>()).cap == capacity);
-
-    MiniVec {
-      buf: core::ptr::NonNull::<u8>::new_unchecked(buf),
-      phantom: core::marker::PhantomData,
-    }
-  }
-
   /// `insert` places an element at the specified index, subsequently shifting all elements to the
   /// right of the insertion index by 1
   ///
@@ -755,26 +1392,9 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec, [0, 1337, 1, 2, 3, 7331]);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn insert(&mut self, index: usize, element: T) {
-    let len = self.len();
-
-    assert!(
-      (index <= len),
-      "insertion index (is {}) should be <= len (is {})",
-      index,
-      len
-    );
-
-    if len == self.capacity() {
-      self.reserve(1);
-    }
-
-    let p = unsafe { self.as_mut_ptr().add(index) };
-    unsafe {
-      core::ptr::copy(p, p.add(1), len - index);
-      core::ptr::write(p, element);
-      self.set_len(len + 1);
-    }
+    handle_reserve(self.try_insert(index, element));
   }
 
   /// `into_raw_parts` will leak the underlying allocation and return a tuple containing a pointer
@@ -782,6 +1402,9 @@ impl<T> MiniVec<T> {
   ///
   /// The results of this function are directly compatible with [`from_raw_parts`](MiniVec::from_raw_parts).
   ///
+  /// An empty vector that never allocated returns a pointer that's only valid for zero-length accesses along with a
+  /// length and capacity of `0`. `from_raw_parts` maps it back to [`MiniVec::new()`](MiniVec::new).
+  ///
   /// # Example
   ///
   /// ```
@@ -792,7 +1415,7 @@ impl<T> MiniVec<T> {
   /// assert_eq!(len, old_len);
   /// assert_eq!(cap, old_cap);
   ///
-  /// let vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+  /// let vec = unsafe { minivec::MiniVec::<i32>::from_raw_parts(ptr, len, cap) };
   /// assert_eq!(vec, [1, 2, 3, 4, 5]);
   /// ```
   ///
@@ -841,13 +1464,13 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   #[must_use]
-  pub fn leak<'a>(vec: MiniVec<T>) -> &'a mut [T]
+  pub fn leak<'a>(vec: MiniVec<T, A, H, G>) -> &'a mut [T]
   where
     T: 'a,
   {
     let len = vec.len();
     let mut vec = core::mem::ManuallyDrop::new(vec);
-    let vec: &mut MiniVec<T> = &mut *vec;
+    let vec: &mut MiniVec<T, A, H, G> = &mut vec;
     unsafe { core::slice::from_raw_parts_mut(vec.as_mut_ptr(), len) }
   }
 
@@ -864,34 +1487,33 @@ impl<T> MiniVec<T> {
   ///
   #[must_use]
   pub fn len(&self) -> usize {
-    self.header().len
+    self.header().len.to_usize()
   }
 
-  /// `MiniVec::new` constructs an empty `MiniVec`.
+  /// `new_in` constructs an empty `MiniVec` that uses `alloc` for all of its allocations.
   ///
-  /// Unlike `Vec` in the standard library, `MiniVec::new()` _will_ allocate memory. This is a consequence
-  /// of supporting stateful allocators. Because `MiniVec` isn't a struct with any other data members aside
-  /// from a pointer, it is unable to store the user-provided Allocator which may potentially contain state.
-  /// Instead of attempting to bifurcate the implementation for ZST Allocators, it is simply easier for the
-  /// library to unconditionally allocate, even cases where the global Allocator is being used.
+  /// A zero-sized allocator is never stored so, much like [`MiniVec::new()`](MiniVec::new), this does not allocate.
+  /// A stateful allocator is stored inside of the allocation so `new_in` must immediately allocate enough space for
+  /// the header.
   ///
   /// # Panics
   ///
-  /// Panics when a zero-sized type is attempted to be used.
+  /// Panics if the underlying allocation fails.
   ///
   /// # Example
   ///
   /// ```
-  /// let mut vec = minivec::MiniVec::<i32>::new();
+  /// let mut vec = minivec::MiniVec::<i32>::new_in(minivec::Global);
+  /// assert_eq!(vec.capacity(), 0);
   ///
-  /// assert!(!vec.as_ptr().is_null());
-  /// assert_eq!(vec.len(), 0);
-  /// assert!(vec.capacity() > 0);
+  /// vec.push(1);
+  /// assert_eq!(vec, [1]);
   /// ```
   ///
   #[must_use]
-  pub fn new() -> MiniVec<T> {
-    MiniVec::with_capacity(0)
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn new_in(alloc: A) -> MiniVec<T, A, H, G> {
+    Self::with_capacity_in(0, alloc)
   }
 
   /// `pop` removes the last element from the vector, should it exist, and returns an [`Option`](core::option::Option)
@@ -939,30 +1561,9 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.len(), 128);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn push(&mut self, value: T) -> &mut T {
-    let (len, capacity) = (self.len(), self.capacity());
-    if len == capacity {
-      if let Err(TryReserveErrorKind::AllocError { layout }) = self
-        .grow(next_capacity::<T>(capacity))
-        .map_err(|e| e.kind())
-      {
-        alloc::alloc::handle_alloc_error(layout);
-      }
-    }
-
-    let len = self.len();
-    let data = self.data();
-
-    let dst = unsafe { data.add(len) };
-
-    unsafe {
-      core::ptr::write(dst, value);
-    };
-
-    let mut header = self.header_mut();
-    header.len += 1;
-
-    unsafe { &mut *dst }
+    handle_reserve(self.try_push(value))
   }
 
   /// `remove` moves the element at the specified `index` and then returns it to the user. This
@@ -983,28 +1584,9 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   pub fn remove(&mut self, index: usize) -> T {
-    let len = self.len();
-
-    assert!(
-      (index < len),
-      "removal index (is {}) should be < len (is {})",
-      index,
-      len
-    );
-
-    unsafe {
-      let p = self.as_mut_ptr().add(index);
-
-      let x = core::ptr::read(p);
-
-      let src = p.add(1);
-      let dst = p;
-      let count = len - index - 1;
-      core::ptr::copy(src, dst, count);
-
-      self.set_len(len - 1);
-
-      x
+    match self.checked_remove(index) {
+      Ok(x) => x,
+      Err(e) => panic!("invalid removal index: {}", e),
     }
   }
 
@@ -1051,12 +1633,9 @@ impl<T> MiniVec<T> {
   /// assert!(vec.capacity() >= 128);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn reserve(&mut self, additional: usize) {
-    if let Err(TryReserveErrorKind::AllocError { layout }) =
-      self.try_reserve(additional).map_err(|e| e.kind())
-    {
-      alloc::alloc::handle_alloc_error(layout);
-    }
+    handle_reserve(self.try_reserve(additional));
   }
 
   /// `reserve_exact` ensures that the capacity of the vector is exactly equal to
@@ -1072,20 +1651,9 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.capacity(), 57);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn reserve_exact(&mut self, additional: usize) {
-    let capacity = self.capacity();
-    let len = self.len();
-
-    let total_required = len + additional;
-    if capacity >= total_required {
-      return;
-    }
-
-    if let Err(TryReserveErrorKind::AllocError { layout }) =
-      self.grow(total_required).map_err(|e| e.kind())
-    {
-      alloc::alloc::handle_alloc_error(layout);
-    }
+    handle_reserve(self.try_reserve_exact(additional));
   }
 
   /// `resize` will clone the supplied `value` as many times as required until `len()` becomes
@@ -1105,24 +1673,12 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.len(), 64);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn resize(&mut self, new_len: usize, value: T)
   where
     T: Clone,
   {
-    let len = self.len();
-    match new_len.cmp(&len) {
-      core::cmp::Ordering::Equal => {}
-      core::cmp::Ordering::Greater => {
-        let num_elems = new_len - len;
-        self.reserve(num_elems);
-        for _i in 0..num_elems {
-          self.push(value.clone());
-        }
-      }
-      core::cmp::Ordering::Less => {
-        self.truncate(new_len);
-      }
-    }
+    handle_reserve(self.try_resize(new_len, value));
   }
 
   /// `resize_with` will invoke the supplied callable `f` as many times as is required until
@@ -1139,6 +1695,7 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.len(), 128);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
   where
     F: FnMut() -> T,
@@ -1182,26 +1739,21 @@ impl<T> MiniVec<T> {
 
     let data = self.as_mut_ptr();
 
-    let mut read = data;
-    let mut write = read;
+    let mut write = 0;
 
-    let last = unsafe { data.add(len) };
-
-    while read < last {
-      let should_retain = unsafe { f(&mut *read) };
+    for read in 0..len {
+      let should_retain = unsafe { f(&*data.add(read)) };
       if should_retain {
         if read != write {
           unsafe {
-            core::mem::swap(&mut *read, &mut *write);
+            core::ptr::swap(data.add(read), data.add(write));
           }
         }
-        write = unsafe { write.add(1) };
+        write += 1;
       }
-
-      read = unsafe { read.add(1) };
     }
 
-    self.truncate((write as usize - data as usize) / core::mem::size_of::<T>());
+    self.truncate(write);
   }
 
   /// `set_len` reassigns the internal `len_` data member to the user-supplied `len`.
@@ -1221,7 +1773,18 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   pub unsafe fn set_len(&mut self, len: usize) {
-    self.header_mut().len = len;
+    // only a vector of ZSTs has room for elements without an allocation so its header is allocated on demand
+    //
+    if self.is_default() {
+      if len == 0 {
+        return;
+      }
+
+      debug_assert!(core::mem::size_of::<T>() == 0);
+      handle_reserve(self.grow(len));
+    }
+
+    self.header_mut().len = H::from_usize(len);
   }
 
   /// `shrink_to` will attempt to adjust the backing allocation such that it has space for at
@@ -1249,6 +1812,7 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.capacity(), 64);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn shrink_to(&mut self, min_capacity: usize) {
     let (len, capacity) = (self.len(), self.capacity());
 
@@ -1266,11 +1830,13 @@ impl<T> MiniVec<T> {
       "Tried to shrink to a larger capacity"
     );
 
-    if let Err(TryReserveErrorKind::AllocError { layout }) =
-      self.grow(min_capacity).map_err(|e| e.kind())
-    {
-      alloc::alloc::handle_alloc_error(layout);
+    // there's no allocation to shrink, only a vector of ZSTs reports a capacity without one
+    //
+    if self.is_default() {
+      return;
     }
+
+    handle_reserve(self.grow(min_capacity));
   }
 
   /// `shrink_to_fit` will re-adjust the backing allocation such that its capacity is now equal
@@ -1279,7 +1845,7 @@ impl<T> MiniVec<T> {
   /// # Example
   ///
   /// ```
-  /// let mut vec = minivec::MiniVec::with_capacity(512);
+  /// let mut vec = minivec::MiniVec::<i32>::with_capacity(512);
   ///
   /// vec.push(1);
   /// vec.push(2);
@@ -1290,18 +1856,15 @@ impl<T> MiniVec<T> {
   /// assert_eq!(vec.capacity(), 3);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn shrink_to_fit(&mut self) {
     let len = self.len();
-    if len == self.capacity() {
+    if len == self.writable_capacity() {
       return;
     }
 
     let capacity = len;
-    if let Err(TryReserveErrorKind::AllocError { layout }) =
-      self.grow(capacity).map_err(|e| e.kind())
-    {
-      alloc::alloc::handle_alloc_error(layout);
-    }
+    handle_reserve(self.grow(capacity));
   }
 
   /// `spare_capacity_mut` returns a mutable slice to [`MaybeUninit<T>`](core::mem::MaybeUninit).
@@ -1366,57 +1929,20 @@ impl<T> MiniVec<T> {
   /// assert_eq!(y, &[2, 3, 4]);
   /// ```
   ///
-  pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<<I as IntoIterator>::IntoIter>
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn splice<R, I>(
+    &mut self,
+    range: R,
+    replace_with: I,
+  ) -> Splice<'_, <I as IntoIterator>::IntoIter, A, H, G>
   where
     I: IntoIterator<Item = T>,
     R: core::ops::RangeBounds<usize>,
   {
-    let len = self.len();
-
-    let start_idx = match range.start_bound() {
-      core::ops::Bound::Included(&n) => n,
-      core::ops::Bound::Excluded(&n) => {
-        n.checked_add(1).expect("Start idx exceeded numeric limits")
-      }
-      core::ops::Bound::Unbounded => 0,
-    };
-
-    let end_idx = match range.end_bound() {
-      core::ops::Bound::Included(&n) => n.checked_add(1).expect("End idx exceeded numeric limits"),
-      core::ops::Bound::Excluded(&n) => n,
-      core::ops::Bound::Unbounded => len,
-    };
-
-    assert!(
-      (start_idx <= end_idx),
-      "start splice index (is {}) should be <= end splice index (is {})",
-      start_idx,
-      end_idx
-    );
-
-    assert!(
-      (end_idx <= len),
-      "end splice index (is {}) should be <= len (is {})",
-      end_idx,
-      len
-    );
-
-    let data = self.as_mut_ptr();
-
-    if !data.is_null() {
-      unsafe {
-        self.set_len(start_idx);
-      }
+    match self.try_splice(range, replace_with) {
+      Ok(splice) => splice,
+      Err(e) => panic!("invalid splice range: {}", e),
     }
-
-    make_splice_iterator(
-      self,
-      data,
-      len - end_idx,
-      start_idx,
-      end_idx,
-      replace_with.into_iter(),
-    )
   }
 
   /// `split_at_spare_mut` returns a pair containing two mutable slices: one referring to the currently
@@ -1495,47 +2021,12 @@ impl<T> MiniVec<T> {
   ///
   #[allow(clippy::ptr_as_ptr)]
   #[must_use]
-  pub fn split_off(&mut self, at: usize) -> MiniVec<T> {
-    let len = self.len();
-
-    assert!(
-      (at <= len),
-      "`at` split index (is {}) should be <= len (is {})",
-      at,
-      len
-    );
-
-    if len == 0 {
-      let other = MiniVec::with_capacity(self.capacity());
-      return other;
-    }
-
-    if at == 0 {
-      let orig_cap = self.capacity();
-      let mut other = MiniVec::with_capacity(orig_cap);
-
-      core::mem::swap(self, &mut other);
-      self.reserve_exact(orig_cap);
-
-      return other;
-    }
-
-    let mut other = MiniVec::with_capacity(self.capacity());
-
-    unsafe {
-      self.set_len(at);
-      other.set_len(len - at);
-    }
-
-    let src = unsafe { self.as_ptr().add(at) };
-    let dst = other.as_mut_ptr();
-    let count = len - at;
-
-    unsafe {
-      core::ptr::copy_nonoverlapping(src, dst, count);
-    }
-
-    other
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn split_off(&mut self, at: usize) -> MiniVec<T, A, H, G>
+  where
+    A: Clone,
+  {
+    handle_reserve(self.try_split_off(at))
   }
 
   /// `swap_remove` removes the element located at `index` and replaces it with the last value
@@ -1556,20 +2047,10 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   pub fn swap_remove(&mut self, index: usize) -> T {
-    let len = self.len();
-
-    assert!(
-      (index < len),
-      "swap_remove index (is {}) should be < len (is {})",
-      index,
-      len
-    );
-
-    unsafe { core::ptr::swap(self.as_mut_ptr().add(len - 1), self.as_mut_ptr().add(index)) };
-
-    let x = unsafe { core::ptr::read(self.as_ptr().add(self.len() - 1)) };
-    self.header_mut().len -= 1;
-    x
+    match self.checked_swap_remove(index) {
+      Ok(x) => x,
+      Err(e) => panic!("invalid swap_remove index: {}", e),
+    }
   }
 
   /// `truncate` adjusts the length of the vector to be `len`. If `len` is greater than or equal
@@ -1592,7 +2073,7 @@ impl<T> MiniVec<T> {
       return;
     }
 
-    self.header_mut().len = len;
+    self.header_mut().len = H::from_usize(len);
 
     if !core::mem::needs_drop::<T>() {
       return;
@@ -1605,6 +2086,120 @@ impl<T> MiniVec<T> {
     }
   }
 
+  /// `try_drain` is the non-panicking version of [`drain`](MiniVec::drain), returning an error instead of panicking
+  /// when the supplied `range` is outside of the vector.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `RangeError` describing why `range` is invalid, in which case the vector is left untouched.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4, 5];
+  ///
+  /// let drained: Vec<_> = vec.try_drain(1..3).unwrap().collect();
+  /// assert_eq!(drained, [2, 3]);
+  /// assert_eq!(vec, [1, 4, 5]);
+  ///
+  /// assert_eq!(
+  ///   vec.try_drain(2..5).err(),
+  ///   Some(minivec::RangeError::EndOutOfBounds { end: 5, len: 3 })
+  /// );
+  /// ```
+  ///
+  pub fn try_drain<R>(&mut self, range: R) -> Result<Drain<'_, T, A, H, G>, RangeError>
+  where
+    R: core::ops::RangeBounds<usize>,
+  {
+    let len = self.len();
+    let (start_idx, end_idx) = normalize_range(&range, len)?;
+
+    unsafe {
+      self.set_len(start_idx);
+    }
+
+    Ok(make_drain_iterator(self, len - end_idx, start_idx, end_idx))
+  }
+
+  /// `try_insert` is the fallible version of [`insert`](MiniVec::insert), returning an error instead of aborting
+  /// when the vector needs to grow and the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the vector could not grow, in which case the vector is left untouched and
+  /// `element` is dropped.
+  ///
+  /// # Panics
+  ///
+  /// Will panic when `index > vec.len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![0, 1, 2, 3];
+  /// vec.try_insert(1, 1337).unwrap();
+  /// assert_eq!(vec, [0, 1337, 1, 2, 3]);
+  /// ```
+  ///
+  pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), TryReserveError> {
+    let len = self.len();
+
+    assert!(
+      (index <= len),
+      "insertion index (is {}) should be <= len (is {})",
+      index,
+      len
+    );
+
+    if len == self.writable_capacity() {
+      self.try_reserve(1)?;
+    }
+
+    let p = unsafe { self.as_mut_ptr().add(index) };
+    unsafe {
+      core::ptr::copy(p, p.add(1), len - index);
+      core::ptr::write(p, element);
+      self.set_len(len + 1);
+    }
+
+    Ok(())
+  }
+
+  /// `try_push` is the fallible version of [`push`](MiniVec::push), returning an error instead of aborting when the
+  /// vector needs to grow and the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the vector could not grow, in which case the vector is left untouched and `value`
+  /// is dropped.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::MiniVec::<i32>::new();
+  ///
+  /// *vec.try_push(1).unwrap() += 1;
+  /// assert_eq!(vec, [2]);
+  /// ```
+  ///
+  pub fn try_push(&mut self, value: T) -> Result<&mut T, TryReserveError> {
+    if self.len() == self.writable_capacity() {
+      self.try_reserve(1)?;
+    }
+
+    let len = self.len();
+    let dst = unsafe { self.data().add(len) };
+
+    unsafe {
+      core::ptr::write(dst, value);
+    };
+
+    self.header_mut().len = H::from_usize(len + 1);
+
+    Ok(unsafe { &mut *dst })
+  }
+
   /// `try_reserve` attempts to reserve space for at least `additional` elements, returning a `Result` indicating if
   /// the allocation was succesful.
   ///
@@ -1626,29 +2221,68 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
-    let capacity = self.capacity();
-    let total_required = self.len().saturating_add(additional);
+    let capacity = self.writable_capacity();
+    let total_required = self
+      .len()
+      .checked_add(additional)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
 
     if total_required <= capacity {
       return Ok(());
     }
 
-    let mut new_capacity = next_capacity::<T>(capacity);
-    while new_capacity < total_required {
-      new_capacity = next_capacity::<T>(new_capacity);
+    self.try_grow_amortized(total_required)
+  }
+
+  // grows the vector to whatever capacity the growth policy suggests for holding `total_required` elements
+  //
+  // this only looks at the capacity so it's also usable while the length is out of date, as it is in `FromIterator`
+  //
+  fn try_grow_amortized(&mut self, total_required: usize) -> Result<(), TryReserveError> {
+    if total_required
+      .checked_mul(core::mem::size_of::<T>())
+      .is_none()
+    {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
     }
 
-    let max_elems = max_elems::<T>();
+    let (elem_size, capacity) = (core::mem::size_of::<T>(), self.writable_capacity());
+    let suggested = if capacity == 0 {
+      G::initial_capacity(elem_size)
+    } else {
+      G::next_capacity(elem_size, capacity, total_required)
+    };
+
+    let new_capacity = core::cmp::max(suggested, total_required);
 
-    if !self.is_empty() && total_required > max_elems {
+    self.grow_to_at_least(new_capacity, total_required)
+  }
+
+  // requests that can't be described by a `Layout` are clamped to the largest one that can be, leaving it to the
+  // allocator to report the failure
+  //
+  fn grow_to_at_least(
+    &mut self,
+    capacity: usize,
+    total_required: usize,
+  ) -> Result<(), TryReserveError> {
+    // no amount of memory helps when a narrow index type can't count that high, a `usize` index instead leaves it to
+    // the allocator like `Vec` does
+    //
+    if H::MAX < usize::MAX && total_required > max_aligned_elems::<T, A, H>(self.alignment()) {
       return Err(From::from(TryReserveErrorKind::CapacityOverflow));
     }
 
-    if additional > max_elems {
-      new_capacity = max_elems;
+    self.grow(core::cmp::min(
+      capacity,
+      max_aligned_elems::<T, A, H>(self.alignment()),
+    ))?;
+
+    if self.capacity() < total_required {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
     }
 
-    self.grow(new_capacity)
+    Ok(())
   }
 
   /// `try_reserve_exact` attempts to reserve space for exactly `additional` elements, returning a `Result` indicating
@@ -1672,90 +2306,473 @@ impl<T> MiniVec<T> {
   /// ```
   ///
   pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
-    let capacity = self.capacity();
-    let total_required = self.len().saturating_add(additional);
+    let capacity = self.writable_capacity();
+    let total_required = self
+      .len()
+      .checked_add(additional)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
 
     if total_required <= capacity {
       return Ok(());
     }
 
-    let mut new_capacity = total_required;
-
-    let max_elems = max_elems::<T>();
-
-    if !self.is_empty() && total_required > max_elems {
+    if total_required
+      .checked_mul(core::mem::size_of::<T>())
+      .is_none()
+    {
       return Err(From::from(TryReserveErrorKind::CapacityOverflow));
     }
 
-    if total_required > max_elems {
-      new_capacity = max_elems;
-    }
-
-    self.grow(new_capacity)
+    self.grow_to_at_least(total_required, total_required)
   }
 
-  /// `with_capacity` is a static factory function that returns a `MiniVec` that contains space
-  /// for `capacity` elements.
-  ///
-  /// This function is logically equivalent to calling [`.reserve_exact()`](MiniVec::reserve_exact)
-  /// on a vector with `0` capacity.
+  /// `try_resize` is the fallible version of [`resize`](MiniVec::resize), returning an error instead of aborting when
+  /// the vector needs to grow and the allocation fails.
   ///
-  /// # Panics
+  /// # Errors
   ///
-  /// Panics if the underlying allocation fails.
+  /// Returns a `TryReserveError` if the vector could not grow, in which case the vector is left untouched.
   ///
   /// # Example
   ///
   /// ```
-  /// let mut vec = minivec::MiniVec::<i32>::with_capacity(128);
+  /// let mut vec = minivec::mini_vec![1, 2];
   ///
-  /// assert_eq!(vec.len(), 0);
-  /// assert_eq!(vec.capacity(), 128);
+  /// vec.try_resize(4, 0).unwrap();
+  /// assert_eq!(vec, [1, 2, 0, 0]);
+  ///
+  /// vec.try_resize(1, 0).unwrap();
+  /// assert_eq!(vec, [1]);
   /// ```
   ///
-  #[must_use]
-  #[allow(clippy::cast_ptr_alignment)]
-  pub fn with_capacity(capacity: usize) -> MiniVec<T> {
-    assert!(
-      core::mem::size_of::<T>() > 0,
-      "ZSTs currently not supported"
-    );
-
-    let capacity = if capacity == 0 {
-      next_capacity::<T>(0)
-    } else {
-      capacity
-    };
-
-    let layout = make_layout::<T>(capacity);
-
-    let buf = unsafe { alloc::alloc::alloc(layout) };
-    if buf.is_null() {
-      alloc::alloc::handle_alloc_error(layout);
-    }
-
-    let header = Header {
-      len: 0,
-      cap: capacity,
-    };
-
-    unsafe { core::ptr::write(buf.cast::<Header>(), header) };
-
-    MiniVec {
-      buf: unsafe { core::ptr::NonNull::new_unchecked(buf) },
-      phantom: core::marker::PhantomData,
+  pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), TryReserveError>
+  where
+    T: Clone,
+  {
+    let len = self.len();
+    match new_len.cmp(&len) {
+      core::cmp::Ordering::Equal => {}
+      core::cmp::Ordering::Greater => {
+        self.try_reserve(new_len - len)?;
+        while self.len() < new_len {
+          unsafe {
+            core::ptr::write(self.as_mut_ptr().add(self.len()), value.clone());
+            self.set_len(self.len() + 1);
+          }
+        }
+      }
+      core::cmp::Ordering::Less => {
+        self.truncate(new_len);
+      }
     }
-  }
 
-  #[doc(hidden)]
-  pub unsafe fn unsafe_write(&mut self, idx: usize, elem: T) {
-    self.data().add(idx).write(elem);
+    Ok(())
   }
-}
 
-impl<T: Clone> MiniVec<T> {
-  /// `extend_from_slice` will append each element from `elems` in a left-to-right order, cloning
-  /// each value in `elems`.
+  /// `try_splice` is the non-panicking version of [`splice`](MiniVec::splice), returning an error instead of panicking
+  /// when the supplied `range` is outside of the vector.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `RangeError` describing why `range` is invalid, in which case the vector is left untouched.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+  ///
+  /// let removed: Vec<_> = vec.try_splice(1..3, [7, 8, 9]).unwrap().collect();
+  /// assert_eq!(removed, [2, 3]);
+  /// assert_eq!(vec, [1, 7, 8, 9, 4]);
+  ///
+  /// assert!(vec.try_splice(..=5, []).is_err());
+  /// ```
+  ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn try_splice<R, I>(
+    &mut self,
+    range: R,
+    replace_with: I,
+  ) -> Result<Splice<'_, <I as IntoIterator>::IntoIter, A, H, G>, RangeError>
+  where
+    I: IntoIterator<Item = T>,
+    R: core::ops::RangeBounds<usize>,
+  {
+    let len = self.len();
+    let (start_idx, end_idx) = normalize_range(&range, len)?;
+
+    unsafe {
+      self.set_len(start_idx);
+    }
+
+    Ok(make_splice_iterator(
+      self,
+      len - end_idx,
+      start_idx,
+      end_idx,
+      replace_with.into_iter(),
+    ))
+  }
+
+  /// `try_split_off` is the fallible version of [`split_off`](MiniVec::split_off), returning an error instead of
+  /// aborting when the allocation for the returned vector fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new vector could not be allocated, in which case `self` is left untouched.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `at > len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3];
+  /// let vec2 = vec.try_split_off(1).unwrap();
+  ///
+  /// assert_eq!(vec, [1]);
+  /// assert_eq!(vec2, [2, 3]);
+  /// ```
+  ///
+  pub fn try_split_off(&mut self, at: usize) -> Result<MiniVec<T, A, H, G>, TryReserveError>
+  where
+    A: Clone,
+  {
+    let len = self.len();
+
+    assert!(
+      (at <= len),
+      "`at` split index (is {}) should be <= len (is {})",
+      at,
+      len
+    );
+
+    let mut other = self.try_with_capacity_like(self.writable_capacity())?;
+
+    if len == 0 {
+      return Ok(other);
+    }
+
+    if at == 0 {
+      core::mem::swap(self, &mut other);
+      return Ok(other);
+    }
+
+    unsafe {
+      self.set_len(at);
+      other.set_len(len - at);
+    }
+
+    let src = unsafe { self.as_ptr().add(at) };
+    let dst = other.as_mut_ptr();
+    let count = len - at;
+
+    unsafe {
+      core::ptr::copy_nonoverlapping(src, dst, count);
+    }
+
+    Ok(other)
+  }
+
+  /// `try_with_capacity_in` is the fallible version of [`with_capacity_in`](MiniVec::with_capacity_in), returning an
+  /// error instead of aborting when the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the allocation fails or if `capacity` exceeds the maximum capacity of the vector.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::MiniVec::<i32>::try_with_capacity_in(128, minivec::Global).unwrap();
+  /// assert_eq!(vec.capacity(), 128);
+  ///
+  /// let err = minivec::MiniVec::<i32>::try_with_capacity_in(usize::MAX, minivec::Global).unwrap_err();
+  /// assert_eq!(err.kind(), minivec::TryReserveErrorKind::CapacityOverflow);
+  /// ```
+  ///
+  pub fn try_with_capacity_in(
+    capacity: usize,
+    alloc: A,
+  ) -> Result<MiniVec<T, A, H, G>, TryReserveError> {
+    Self::try_allocate_in(capacity, max_align::<T, A, H>(), alloc)
+  }
+
+  /// `with_capacity_in` is the allocator-aware version of [`with_capacity`](MiniVec::with_capacity) and returns a
+  /// `MiniVec` that contains space for `capacity` elements allocated using `alloc`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the underlying allocation fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::MiniVec::<i32>::with_capacity_in(128, minivec::Global);
+  ///
+  /// assert_eq!(vec.len(), 0);
+  /// assert_eq!(vec.capacity(), 128);
+  /// ```
+  ///
+  #[must_use]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn with_capacity_in(capacity: usize, alloc: A) -> MiniVec<T, A, H, G> {
+    handle_reserve(Self::try_with_capacity_in(capacity, alloc))
+  }
+
+  #[doc(hidden)]
+  pub unsafe fn unsafe_write(&mut self, idx: usize, elem: T) {
+    self.data().add(idx).write(elem);
+  }
+}
+
+impl<T, H: IndexType, G: GrowthPolicy> MiniVec<T, Global, H, G> {
+  /// `from_raw_part` reconstructs a `MiniVec` from a previous call to [`MiniVec::as_mut_ptr`](MiniVec::as_mut_ptr)
+  /// or the pointer from [`into_raw_parts`](MiniVec::into_raw_parts).
+  ///
+  /// # Safety
+  ///
+  /// `from_raw_part` is incredibly unsafe and can only be used with the value of
+  /// `MiniVec::as_mut_ptr`. This is because the allocation for the backing array stores metadata
+  /// at its head and is not guaranteed to be stable so users are discouraged from attempting to
+  /// support this directly.
+  ///
+  /// # Panics
+  ///
+  /// Panics in debug mode if the supplied pointer is null.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+  ///
+  /// let ptr = vec.as_mut_ptr();
+  ///
+  /// std::mem::forget(vec);
+  ///
+  /// let new_vec = unsafe { minivec::MiniVec::<i32>::from_raw_part(ptr) };
+  ///
+  /// assert_eq!(new_vec, [1, 2, 3, 4]);
+  /// ```
+  ///
+  pub unsafe fn from_raw_part(ptr: *mut T) -> MiniVec<T, Global, H, G> {
+    Self::from_data(ptr)
+  }
+
+  /// `from_raw_parts` is an API-compatible version of `alloc::vec::Vec::from_raw_parts`. Because
+  /// of `MiniVec`'s optimized layout, it's not strictly required for a user to pass the length
+  /// and capacity explicitly.
+  ///
+  /// Like [`MiniVec::from_raw_part`](MiniVec::from_raw_part), this function is only safe to use
+  /// with the result of a call to [`MiniVec::as_mut_ptr()`](MiniVec::as_mut_ptr).
+  ///
+  /// # Panics
+  ///
+  /// Panics in debug mode if the supplied pointer is null.
+  ///
+  /// # Safety
+  ///
+  /// A very unsafe function that should only really be used when passing the vector to a C API.
+  ///
+  /// Vectors created by [`with_alignment`](MiniVec::with_alignment) round-trip like any other as their alignment is
+  /// stored alongside the length and capacity.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3, 4];
+  /// let len = vec.len();
+  /// let cap = vec.capacity();
+  ///
+  /// let ptr = vec.as_mut_ptr();
+  ///
+  /// std::mem::forget(vec);
+  ///
+  /// let new_vec = unsafe { minivec::MiniVec::<i32>::from_raw_parts(ptr, len, cap) };
+  ///
+  /// assert_eq!(new_vec, [1, 2, 3, 4]);
+  /// ```
+  ///
+  pub unsafe fn from_raw_parts(
+    ptr: *mut T,
+    length: usize,
+    capacity: usize,
+  ) -> MiniVec<T, Global, H, G> {
+    let v = Self::from_raw_part(ptr);
+
+    debug_assert!(v.len() == length);
+    debug_assert!(v.capacity() == capacity);
+
+    v
+  }
+
+  /// `into_boxed_mini_slice` converts the vector into a [`MiniBoxSlice`], shrinking its allocation down to the
+  /// elements and the single word storing their number.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::MiniVec::<i32>::with_capacity(16);
+  /// vec.extend_from_slice(&[1, 2, 3]);
+  ///
+  /// let slice = vec.into_boxed_mini_slice();
+  /// assert_eq!(*slice, [1, 2, 3]);
+  /// ```
+  ///
+  #[must_use]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn into_boxed_mini_slice(self) -> MiniBoxSlice<T> {
+    MiniBoxSlice::from_mini_vec(self)
+  }
+
+  /// `MiniVec::new` constructs an empty `MiniVec`.
+  ///
+  /// Like `Vec` in the standard library, `MiniVec::new()` does not allocate. Every empty `MiniVec` points at the same
+  /// static header and memory is only requested once the first element is inserted or capacity is reserved.
+  ///
+  /// Because `new` is a `const fn`, `MiniVec` can be used to initialize `static` and `const` items.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// static EMPTY: minivec::MiniVec<i32> = minivec::MiniVec::new();
+  ///
+  /// let mut vec = minivec::MiniVec::<i32>::new();
+  ///
+  /// assert!(!vec.as_ptr().is_null());
+  /// assert_eq!(vec.len(), 0);
+  /// assert_eq!(vec.capacity(), 0);
+  ///
+  /// vec.push(1);
+  /// assert!(vec.capacity() > 0);
+  /// assert_eq!(EMPTY, []);
+  /// ```
+  ///
+  #[must_use]
+  pub const fn new() -> MiniVec<T, Global, H, G> {
+    Self::from_buf(Self::empty_buf())
+  }
+
+  /// `try_from_iter` is the fallible version of [`collect()`](Iterator::collect), returning an error instead of
+  /// aborting when the vector needs to grow and the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the vector could not grow. The elements collected up until that point are dropped.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::MiniVec::<i32>::try_from_iter((0..4).map(|x| x * 2)).unwrap();
+  /// assert_eq!(vec, [0, 2, 4, 6]);
+  /// ```
+  ///
+  pub fn try_from_iter<I>(iter: I) -> Result<MiniVec<T, Global, H, G>, TryReserveError>
+  where
+    I: IntoIterator<Item = T>,
+  {
+    crate::from_iterator::try_from_iter_in(iter.into_iter(), Global)
+  }
+
+  /// `try_with_capacity` is the fallible version of [`with_capacity`](MiniVec::with_capacity), returning an error
+  /// instead of aborting when the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the allocation fails or if `capacity` exceeds the maximum capacity of the vector.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::MiniVec::<i32>::try_with_capacity(128).unwrap();
+  /// assert_eq!(vec.capacity(), 128);
+  ///
+  /// assert!(minivec::MiniVec::<i32>::try_with_capacity(usize::MAX).is_err());
+  /// ```
+  ///
+  pub fn try_with_capacity(capacity: usize) -> Result<MiniVec<T, Global, H, G>, TryReserveError> {
+    Self::try_with_capacity_in(capacity, Global)
+  }
+
+  /// `with_alignment` is a static factory function that returns a `MiniVec` that contains space for `capacity`
+  /// elements and whose backing array is aligned to `alignment` bytes.
+  ///
+  /// The alignment is stored in the allocation and is kept for the lifetime of the vector, i.e. reallocations caused
+  /// by growing or shrinking the vector as well as [`clone`](Clone::clone) and [`split_off`](MiniVec::split_off) all
+  /// produce storage with the same alignment. Because of this, an over-aligned vector always owns an allocation, even
+  /// when its capacity is `0`.
+  ///
+  /// An `alignment` less than or equal to that of `T` simply yields [`MiniVec::with_capacity(capacity)`](MiniVec::with_capacity).
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`LayoutErr`] if `alignment` is not a power of two or is less than the alignment of a pointer.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the underlying allocation fails or if the requested capacity exceeds `isize::MAX` bytes.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::MiniVec::<f32>::with_alignment(8, 32).unwrap();
+  /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+  ///
+  /// vec.extend_from_slice(&[1.0; 128]);
+  /// assert_eq!(vec.as_ptr() as usize % 32, 0);
+  ///
+  /// let err = minivec::MiniVec::<f32>::with_alignment(8, 24).unwrap_err();
+  /// assert!(matches!(err, minivec::LayoutErr::AlignmentNotDivisibleByTwo));
+  /// ```
+  ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn with_alignment(
+    capacity: usize,
+    alignment: usize,
+  ) -> Result<MiniVec<T, Global, H, G>, LayoutErr> {
+    if !alignment.is_power_of_two() {
+      return Err(LayoutErr::AlignmentNotDivisibleByTwo);
+    }
+
+    if alignment < core::mem::align_of::<*const ()>() {
+      return Err(LayoutErr::AlignmentTooSmall);
+    }
+
+    Ok(handle_reserve(Self::try_allocate_in(
+      capacity, alignment, Global,
+    )))
+  }
+
+  /// `with_capacity` is a static factory function that returns a `MiniVec` that contains space
+  /// for `capacity` elements.
+  ///
+  /// This function is logically equivalent to calling [`.reserve_exact()`](MiniVec::reserve_exact)
+  /// on a vector with `0` capacity. As such, `MiniVec::with_capacity(0)` does not allocate.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the underlying allocation fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::MiniVec::<i32>::with_capacity(128);
+  ///
+  /// assert_eq!(vec.len(), 0);
+  /// assert_eq!(vec.capacity(), 128);
+  /// ```
+  ///
+  #[must_use]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  pub fn with_capacity(capacity: usize) -> MiniVec<T, Global, H, G> {
+    Self::with_capacity_in(capacity, Global)
+  }
+}
+
+impl<T: Clone, A: Allocator, H: IndexType, G: GrowthPolicy> MiniVec<T, A, H, G> {
+  /// `extend_from_slice` will append each element from `elems` in a left-to-right order, cloning
+  /// each value in `elems`.
   ///
   /// # Example
   ///
@@ -1769,11 +2786,9 @@ impl<T: Clone> MiniVec<T> {
   /// assert_eq!(vec, [1, 2, 3, 4]);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn extend_from_slice(&mut self, elems: &[T]) {
-    self.reserve(elems.len());
-    for x in elems {
-      self.push((*x).clone());
-    }
+    handle_reserve(self.try_extend_from_slice(elems));
   }
 
   /// `extend_from_within` clones the elements contained in the provided `Range` and appends them
@@ -1792,23 +2807,115 @@ impl<T: Clone> MiniVec<T> {
   /// assert_eq!(vec, [1, 2, 3, 4, 5, 2, 3, 4]);
   /// ```
   ///
+  #[cfg(not(feature = "no_global_oom_handling"))]
   pub fn extend_from_within<Range>(&mut self, range: Range)
   where
     Range: core::ops::RangeBounds<usize>,
   {
-    struct PanicGuard<'a, T>
+    match self.try_extend_from_within(range) {
+      Ok(()) => {}
+      Err(CheckedError::Reserve(e)) => handle_reserve(Err(e)),
+      Err(e) => panic!("invalid extend_from_within range: {}", e),
+    }
+  }
+
+  /// `try_clone` is the fallible version of [`clone`](Clone::clone), returning an error instead of aborting when the
+  /// allocation for the copy fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the copy could not be allocated.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::mini_vec![1, 2, 3];
+  /// let copy = vec.try_clone().unwrap();
+  ///
+  /// assert_eq!(vec, copy);
+  /// ```
+  ///
+  pub fn try_clone(&self) -> Result<Self, TryReserveError>
+  where
+    A: Clone,
+  {
+    crate::clone::try_to_vec(self)
+  }
+
+  /// `try_extend_from_slice` is the fallible version of [`extend_from_slice`](MiniVec::extend_from_slice), returning
+  /// an error instead of aborting when the vector needs to grow and the allocation fails.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the vector could not grow, in which case the vector is left untouched.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2];
+  /// vec.try_extend_from_slice(&[3, 4]).unwrap();
+  ///
+  /// assert_eq!(vec, [1, 2, 3, 4]);
+  /// ```
+  ///
+  pub fn try_extend_from_slice(&mut self, elems: &[T]) -> Result<(), TryReserveError> {
+    self.try_reserve(elems.len())?;
+
+    for x in elems {
+      unsafe {
+        core::ptr::write(self.as_mut_ptr().add(self.len()), x.clone());
+        self.set_len(self.len() + 1);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// `try_extend_from_within` is the non-panicking version of [`extend_from_within`](MiniVec::extend_from_within),
+  /// returning an error instead of panicking when the supplied `range` is outside of the vector or aborting when the
+  /// vector can't grow.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `CheckedError` describing why `range` is invalid or why the vector could not grow, in which case the
+  /// vector is left untouched.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::mini_vec![1, 2, 3];
+  /// vec.try_extend_from_within(..2).unwrap();
+  /// assert_eq!(vec, [1, 2, 3, 1, 2]);
+  ///
+  /// assert_eq!(
+  ///   vec.try_extend_from_within(..=usize::MAX),
+  ///   Err(minivec::RangeError::EndOverflow.into())
+  /// );
+  /// ```
+  ///
+  pub fn try_extend_from_within<Range>(&mut self, range: Range) -> Result<(), CheckedError>
+  where
+    Range: core::ops::RangeBounds<usize>,
+  {
+    struct PanicGuard<'a, T, A, H, G>
     where
       T: Clone,
+      A: Allocator,
+      H: IndexType,
+      G: GrowthPolicy,
     {
       count: usize,
       start_idx: usize,
       end_idx: usize,
-      vec: &'a mut MiniVec<T>,
+      vec: &'a mut MiniVec<T, A, H, G>,
     }
 
-    impl<'a, T> Drop for PanicGuard<'a, T>
+    impl<T, A, H, G> Drop for PanicGuard<'_, T, A, H, G>
     where
       T: Clone,
+      A: Allocator,
+      H: IndexType,
+      G: GrowthPolicy,
     {
       fn drop(&mut self) {
         unsafe {
@@ -1817,9 +2924,12 @@ impl<T: Clone> MiniVec<T> {
       }
     }
 
-    impl<'a, 'b, T> PanicGuard<'a, T>
+    impl<T, A, H, G> PanicGuard<'_, T, A, H, G>
     where
       T: Clone,
+      A: Allocator,
+      H: IndexType,
+      G: GrowthPolicy,
     {
       fn extend(&mut self) {
         let count = &mut self.count;
@@ -1835,41 +2945,13 @@ impl<T: Clone> MiniVec<T> {
       }
     }
 
-    let len = self.len();
-
-    let start_idx = match range.start_bound() {
-      core::ops::Bound::Included(&n) => n,
-      core::ops::Bound::Excluded(&n) => {
-        n.checked_add(1).expect("Start idx exceeded numeric limits")
-      }
-      core::ops::Bound::Unbounded => 0,
-    };
-
-    let end_idx = match range.end_bound() {
-      core::ops::Bound::Included(&n) => n.checked_add(1).expect("End idx exceeded numeric limits"),
-      core::ops::Bound::Excluded(&n) => n,
-      core::ops::Bound::Unbounded => len,
-    };
-
-    assert!(
-      (start_idx <= end_idx),
-      "start extend_from_within index (is {}) should be <= end (is {})",
-      start_idx,
-      end_idx
-    );
-
-    assert!(
-      (end_idx <= len),
-      "end extend_from_within index (is {}) should be <= len (is {})",
-      end_idx,
-      len
-    );
+    let (start_idx, end_idx) = normalize_range(&range, self.len())?;
 
-    if len == 0 {
-      return;
+    if start_idx == end_idx {
+      return Ok(());
     }
 
-    self.reserve(end_idx - start_idx);
+    self.try_reserve(end_idx - start_idx)?;
 
     let mut guard = PanicGuard {
       count: 0,
@@ -1879,10 +2961,12 @@ impl<T: Clone> MiniVec<T> {
     };
 
     guard.extend();
+
+    Ok(())
   }
 }
 
-impl<T> MiniVec<core::mem::MaybeUninit<T>> {
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> MiniVec<core::mem::MaybeUninit<T>, A, H, G> {
   /// `assume_minivec_init` is a helper designed to make working with uninitialized memory more ergonomic.
   ///
   /// # Safety
@@ -1905,14 +2989,20 @@ impl<T> MiniVec<core::mem::MaybeUninit<T>> {
   /// ```
   ///
   #[must_use]
-  pub unsafe fn assume_minivec_init(self) -> MiniVec<T> {
-    let (ptr, len, cap) = self.into_raw_parts();
-    MiniVec::<T>::from_raw_parts(ptr.cast::<T>(), len, cap)
+  pub unsafe fn assume_minivec_init(self) -> MiniVec<T, A, H, G> {
+    let (ptr, _, _) = self.into_raw_parts();
+    MiniVec::<T, A, H, G>::from_data(ptr.cast::<T>())
   }
 }
 
-unsafe impl<T: core::marker::Send> core::marker::Send for MiniVec<T> {}
-unsafe impl<T: core::marker::Sync> core::marker::Sync for MiniVec<T> {}
+unsafe impl<T: core::marker::Send, A: Allocator + core::marker::Send, H: IndexType, G: GrowthPolicy>
+  core::marker::Send for MiniVec<T, A, H, G>
+{
+}
+unsafe impl<T: core::marker::Sync, A: Allocator + core::marker::Sync, H: IndexType, G: GrowthPolicy>
+  core::marker::Sync for MiniVec<T, A, H, G>
+{
+}
 
 /// `mini_vec!` is a macro similar in spirit to the stdlib's `vec!`.
 ///
@@ -1921,15 +3011,16 @@ unsafe impl<T: core::marker::Sync> core::marker::Sync for MiniVec<T> {}
 /// * `mini_vec![val1, val2, val3, ...]`
 /// * `mini_vec![val; num_elems]`
 ///
+#[cfg(not(feature = "no_global_oom_handling"))]
 #[macro_export]
 macro_rules! mini_vec {
     () => (
-        $crate::MiniVec::new()
+        $crate::MiniVec::<_>::new()
     );
     ($elem:expr; $n:expr) => {
         {
             let len = $n;
-            let mut tmp = $crate::MiniVec::with_capacity(len);
+            let mut tmp = $crate::MiniVec::<_>::with_capacity(len);
 
             for idx in 0..len {
                 unsafe { tmp.unsafe_write(idx, $elem.clone()) };
@@ -1945,7 +3036,7 @@ macro_rules! mini_vec {
      };
     ($($x:expr),+ $(,)?) => {
         {
-            let mut tmp = $crate::MiniVec::new();
+            let mut tmp = $crate::MiniVec::<_>::new();
             $(
                 tmp.push($x);
             )*
@@ -0,0 +1,441 @@
+use crate::{MiniVec, TryReserveError};
+
+/// `MiniBinaryHeap` is a max-heap priority queue stored in a [`MiniVec`], the counterpart of
+/// `alloc::collections::BinaryHeap`. Like the vector it wraps, it's only the size of a single pointer.
+///
+/// # Example
+///
+/// ```
+/// let mut heap = minivec::MiniBinaryHeap::new();
+/// heap.push(3);
+/// heap.push(7);
+/// heap.push(1);
+///
+/// assert_eq!(heap.peek(), Some(&7));
+/// assert_eq!(heap.pop(), Some(7));
+/// assert_eq!(heap.into_sorted_vec(), [1, 3]);
+/// ```
+///
+pub struct MiniBinaryHeap<T> {
+  data: MiniVec<T>,
+}
+
+/// `PeekMut` is a mutable reference to the greatest element of a [`MiniBinaryHeap`], returned by
+/// [`MiniBinaryHeap::peek_mut`]. The heap is restored once it's dropped, should the element have been modified.
+///
+pub struct PeekMut<'a, T: Ord> {
+  heap: &'a mut MiniBinaryHeap<T>,
+  modified: bool,
+}
+
+/// `DrainSorted` is the iterator returned by [`MiniBinaryHeap::drain_sorted`], it yields the elements from greatest
+/// to smallest and removes any remaining ones once dropped.
+///
+pub struct DrainSorted<'a, T: Ord> {
+  heap: &'a mut MiniBinaryHeap<T>,
+}
+
+impl<T: Ord> MiniBinaryHeap<T> {
+  /// `append` moves all of the elements of `other` into `self`, leaving `other` empty.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut a = minivec::MiniBinaryHeap::from(minivec::mini_vec![1, 5]);
+  /// let mut b = minivec::MiniBinaryHeap::from(minivec::mini_vec![3, 9]);
+  ///
+  /// a.append(&mut b);
+  ///
+  /// assert!(b.is_empty());
+  /// assert_eq!(a.into_sorted_vec(), [1, 3, 5, 9]);
+  /// ```
+  ///
+  pub fn append(&mut self, other: &mut MiniBinaryHeap<T>) {
+    if self.len() < other.len() {
+      core::mem::swap(self, other);
+    }
+
+    let start = self.data.len();
+    self.data.append(&mut other.data);
+
+    for idx in start..self.data.len() {
+      self.sift_up(idx);
+    }
+  }
+
+  /// `drain_sorted` returns an iterator which removes the elements in heap order, from greatest to smallest. The
+  /// elements are removed even if the iterator isn't fully consumed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut heap = minivec::MiniBinaryHeap::from(minivec::mini_vec![2, 8, 5]);
+  ///
+  /// assert_eq!(heap.drain_sorted().collect::<Vec<_>>(), [8, 5, 2]);
+  /// assert!(heap.is_empty());
+  /// ```
+  ///
+  pub fn drain_sorted(&mut self) -> DrainSorted<'_, T> {
+    DrainSorted { heap: self }
+  }
+
+  /// `into_sorted_vec` consumes the heap and returns its elements in ascending order, reusing its allocation.
+  ///
+  #[must_use]
+  pub fn into_sorted_vec(mut self) -> MiniVec<T> {
+    let mut end = self.len();
+    while end > 1 {
+      end -= 1;
+      self.data.swap(0, end);
+      self.sift_down(0, end);
+    }
+
+    self.into_vec()
+  }
+
+  /// `peek_mut` returns a mutable reference to the greatest element, should it exist. The element is moved to its
+  /// proper place in the heap once the returned [`PeekMut`] is dropped.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut heap = minivec::MiniBinaryHeap::from(minivec::mini_vec![1, 5, 3]);
+  ///
+  /// *heap.peek_mut().unwrap() = 0;
+  /// assert_eq!(heap.peek(), Some(&3));
+  /// ```
+  ///
+  pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+    if self.is_empty() {
+      return None;
+    }
+
+    Some(PeekMut {
+      heap: self,
+      modified: false,
+    })
+  }
+
+  /// `pop` removes the greatest element and returns it, should it exist.
+  ///
+  pub fn pop(&mut self) -> Option<T> {
+    let mut item = self.data.pop()?;
+
+    if !self.is_empty() {
+      core::mem::swap(&mut item, &mut self.data[0]);
+      self.sift_down(0, self.len());
+    }
+
+    Some(item)
+  }
+
+  /// `push` adds `item` to the heap.
+  ///
+  pub fn push(&mut self, item: T) {
+    self.data.push(item);
+    self.sift_up(self.len() - 1);
+  }
+
+  /// `retain` keeps only the elements for which `f` returns `true`, in no particular order.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut heap = minivec::MiniBinaryHeap::from(minivec::mini_vec![1, 2, 3, 4, 5]);
+  /// heap.retain(|x| x % 2 == 1);
+  ///
+  /// assert_eq!(heap.into_sorted_vec(), [1, 3, 5]);
+  /// ```
+  ///
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: FnMut(&T) -> bool,
+  {
+    self.data.retain(f);
+    self.rebuild();
+  }
+
+  // moves the element at `idx` towards the root until its parent is no smaller than it
+  //
+  fn sift_up(&mut self, mut idx: usize) {
+    while idx > 0 {
+      let parent = (idx - 1) / 2;
+      if self.data[idx] <= self.data[parent] {
+        break;
+      }
+
+      self.data.swap(idx, parent);
+      idx = parent;
+    }
+  }
+
+  // moves the element at `idx` towards the leaves until it's no smaller than its children, only looking at the first
+  // `end` elements
+  //
+  fn sift_down(&mut self, mut idx: usize, end: usize) {
+    loop {
+      let mut child = 2 * idx + 1;
+      if child >= end {
+        break;
+      }
+
+      if child + 1 < end && self.data[child] < self.data[child + 1] {
+        child += 1;
+      }
+
+      if self.data[idx] >= self.data[child] {
+        break;
+      }
+
+      self.data.swap(idx, child);
+      idx = child;
+    }
+  }
+
+  // turns an arbitrary vector into a heap in linear time
+  //
+  fn rebuild(&mut self) {
+    let len = self.len();
+    for idx in (0..len / 2).rev() {
+      self.sift_down(idx, len);
+    }
+  }
+}
+
+impl<T> MiniBinaryHeap<T> {
+  /// `as_slice` returns the elements in the order they're stored in, which is unspecified beyond the first one being
+  /// the greatest.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    &self.data
+  }
+
+  /// `capacity` returns the number of elements the heap can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.data.capacity()
+  }
+
+  /// `clear` drops all of the elements while keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.data.clear();
+  }
+
+  /// `into_vec` consumes the heap and returns the underlying vector, with the elements in an unspecified order. The
+  /// allocation is handed back as-is.
+  ///
+  #[must_use]
+  pub fn into_vec(self) -> MiniVec<T> {
+    self.data
+  }
+
+  /// `is_empty` returns whether or not the heap contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.data.is_empty()
+  }
+
+  /// `iter` returns an iterator over the elements, in an unspecified order.
+  ///
+  pub fn iter(&self) -> core::slice::Iter<'_, T> {
+    self.data.iter()
+  }
+
+  /// `len` returns the number of elements in the heap.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.data.len()
+  }
+
+  /// `new` creates an empty heap, which does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniBinaryHeap<T> {
+    MiniBinaryHeap {
+      data: MiniVec::new(),
+    }
+  }
+
+  /// `peek` returns a reference to the greatest element, should it exist.
+  ///
+  #[must_use]
+  pub fn peek(&self) -> Option<&T> {
+    self.data.first()
+  }
+
+  /// `reserve` ensures that the heap can hold at least `additional` more elements without reallocating.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    self.data.reserve(additional);
+  }
+
+  /// `shrink_to_fit` reallocates the heap so that its capacity matches its length.
+  ///
+  pub fn shrink_to_fit(&mut self) {
+    self.data.shrink_to_fit();
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniBinaryHeap::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.data.try_reserve(additional)
+  }
+
+  /// `with_capacity` creates an empty heap that can hold at least `capacity` elements without reallocating.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniBinaryHeap<T> {
+    MiniBinaryHeap {
+      data: MiniVec::with_capacity(capacity),
+    }
+  }
+}
+
+impl<T: Ord> PeekMut<'_, T> {
+  /// `pop` removes the peeked element from the heap and returns it.
+  ///
+  #[must_use]
+  pub fn pop(mut this: PeekMut<'_, T>) -> T {
+    // the heap is restored by `pop()` itself so there's nothing left for the destructor to do
+    //
+    this.modified = false;
+
+    match this.heap.pop() {
+      Some(item) => item,
+      None => unreachable!(),
+    }
+  }
+}
+
+impl<T: Ord> core::ops::Deref for PeekMut<'_, T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.heap.data[0]
+  }
+}
+
+impl<T: Ord> core::ops::DerefMut for PeekMut<'_, T> {
+  fn deref_mut(&mut self) -> &mut T {
+    self.modified = true;
+    &mut self.heap.data[0]
+  }
+}
+
+impl<T: Ord> Drop for PeekMut<'_, T> {
+  fn drop(&mut self) {
+    if self.modified {
+      let len = self.heap.len();
+      self.heap.sift_down(0, len);
+    }
+  }
+}
+
+impl<T: Ord + core::fmt::Debug> core::fmt::Debug for PeekMut<'_, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
+  }
+}
+
+impl<T: Ord> Iterator for DrainSorted<'_, T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.heap.pop()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.heap.len(), Some(self.heap.len()))
+  }
+}
+
+impl<T: Ord> ExactSizeIterator for DrainSorted<'_, T> {}
+
+impl<T: Ord> core::iter::FusedIterator for DrainSorted<'_, T> {}
+
+impl<T: Ord> Drop for DrainSorted<'_, T> {
+  fn drop(&mut self) {
+    self.for_each(drop);
+  }
+}
+
+impl<T: Clone> Clone for MiniBinaryHeap<T> {
+  fn clone(&self) -> Self {
+    MiniBinaryHeap {
+      data: self.data.clone(),
+    }
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniBinaryHeap<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Default for MiniBinaryHeap<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: Ord> Extend<T> for MiniBinaryHeap<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+    iter.for_each(|x| self.push(x));
+  }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for MiniBinaryHeap<T> {
+  fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+    self.extend(iter.into_iter().copied());
+  }
+}
+
+impl<T: Ord> core::iter::FromIterator<T> for MiniBinaryHeap<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<MiniVec<T>>())
+  }
+}
+
+impl<T: Ord> From<MiniVec<T>> for MiniBinaryHeap<T> {
+  fn from(vec: MiniVec<T>) -> Self {
+    let mut heap = MiniBinaryHeap { data: vec };
+    heap.rebuild();
+    heap
+  }
+}
+
+impl<T> From<MiniBinaryHeap<T>> for MiniVec<T> {
+  fn from(heap: MiniBinaryHeap<T>) -> Self {
+    heap.into_vec()
+  }
+}
+
+impl<T> IntoIterator for MiniBinaryHeap<T> {
+  type Item = T;
+  type IntoIter = crate::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.data.into_iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniBinaryHeap<T> {
+  type Item = &'a T;
+  type IntoIter = core::slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
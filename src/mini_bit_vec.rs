@@ -0,0 +1,678 @@
+use crate::r#impl::helpers::{make_prefixed_layout, prefixed_data_offset};
+use crate::{handle_reserve, Doubling, Global, GrowthPolicy};
+use crate::{Allocator, TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+const BITS: usize = core::mem::size_of::<usize>() * 8;
+
+// `len` counts bits while `cap` counts words, every bit of the last used word past `len` is kept cleared so that whole
+// words can be compared, hashed and counted
+//
+#[repr(C)]
+struct BitHeader {
+  len: usize,
+  cap: usize,
+}
+
+static EMPTY_BIT_HEADER: BitHeader = BitHeader { len: 0, cap: 0 };
+
+/// `MiniBitVec` is a growable vector of bits packed into `usize` words, using an eighth of the memory of a
+/// `MiniVec<bool>` while still only being the size of a single pointer.
+///
+/// The number of bits lives in the header of the allocation, in front of the words. An empty `MiniBitVec` doesn't
+/// allocate, and the words grow as described by [`GrowthPolicy`](crate::GrowthPolicy).
+///
+/// # Example
+///
+/// ```
+/// let mut bits = minivec::MiniBitVec::from(&[true, false, true][..]);
+///
+/// bits.push(true);
+/// bits.set(1, true);
+/// assert_eq!(bits.count_ones(), 4);
+///
+/// bits ^= &minivec::MiniBitVec::from(&[true, true, false, false][..]);
+/// assert_eq!(bits.iter_ones().collect::<Vec<_>>(), [2, 3]);
+/// ```
+///
+pub struct MiniBitVec {
+  buf: core::ptr::NonNull<u8>,
+}
+
+/// `BitIter` is an iterator over every bit of a [`MiniBitVec`], returned by [`MiniBitVec::iter`].
+///
+#[derive(Clone)]
+pub struct BitIter<'a> {
+  bits: &'a MiniBitVec,
+  idx: usize,
+  end: usize,
+}
+
+/// `IterOnes` is an iterator over the indices of the set bits of a [`MiniBitVec`], returned by
+/// [`MiniBitVec::iter_ones`].
+///
+#[derive(Clone)]
+pub struct IterOnes<'a> {
+  words: core::slice::Iter<'a, usize>,
+  base: usize,
+  word: usize,
+}
+
+const fn words_for(bits: usize) -> usize {
+  bits.div_ceil(BITS)
+}
+
+impl MiniBitVec {
+  const OFFSET: usize = prefixed_data_offset::<BitHeader, usize>();
+
+  /// `as_words` returns the words holding the bits, the bit at index `i` being bit `i % usize::BITS` of word
+  /// `i / usize::BITS`. The unused bits of the last word are always cleared.
+  ///
+  #[must_use]
+  pub fn as_words(&self) -> &[usize] {
+    unsafe { core::slice::from_raw_parts(self.data(), words_for(self.len())) }
+  }
+
+  /// `capacity` returns the number of bits the vector can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.header().cap.saturating_mul(BITS)
+  }
+
+  /// `clear` removes all of the bits, keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  /// `count_ones` returns the number of set bits.
+  ///
+  #[must_use]
+  pub fn count_ones(&self) -> usize {
+    self
+      .as_words()
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum()
+  }
+
+  /// `get` returns the bit at `index`, `None` if it's out of bounds.
+  ///
+  #[must_use]
+  pub fn get(&self, index: usize) -> Option<bool> {
+    if index < self.len() {
+      Some(self.as_words()[index / BITS] & (1 << (index % BITS)) != 0)
+    } else {
+      None
+    }
+  }
+
+  /// `insert` inserts `value` at `index`, shifting all of the bits after it up by one.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index > len()` or if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bits = minivec::MiniBitVec::from(&[true, true][..]);
+  /// bits.insert(1, false);
+  ///
+  /// assert_eq!(bits.iter().collect::<Vec<_>>(), [true, false, true]);
+  /// ```
+  ///
+  pub fn insert(&mut self, index: usize, value: bool) {
+    let len = self.len();
+
+    assert!(
+      (index <= len),
+      "insertion index (is {}) should be <= len (is {})",
+      index,
+      len
+    );
+
+    self.push(false);
+
+    let words = self.as_words_mut();
+    let (w, b) = (index / BITS, index % BITS);
+
+    for i in (w + 1..words.len()).rev() {
+      words[i] = (words[i] << 1) | (words[i - 1] >> (BITS - 1));
+    }
+
+    let low = (1 << b) - 1;
+    let word = words[w];
+    words[w] = (word & low) | ((word & !low) << 1) | (usize::from(value) << b);
+  }
+
+  /// `is_empty` returns whether or not the vector contains any bits.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `iter` returns an iterator over the bits.
+  ///
+  #[must_use]
+  pub fn iter(&self) -> BitIter<'_> {
+    BitIter {
+      bits: self,
+      idx: 0,
+      end: self.len(),
+    }
+  }
+
+  /// `iter_ones` returns an iterator over the indices of the set bits, in ascending order.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let bits: minivec::MiniBitVec = (0..200).map(|i| i % 64 == 3).collect();
+  ///
+  /// assert_eq!(bits.iter_ones().collect::<Vec<_>>(), [3, 67, 131, 195]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn iter_ones(&self) -> IterOnes<'_> {
+    IterOnes {
+      words: self.as_words().iter(),
+      base: 0,
+      word: 0,
+    }
+  }
+
+  /// `len` returns the number of bits in the vector.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.header().len
+  }
+
+  /// `new` creates an empty bit vector. It does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniBitVec {
+    MiniBitVec {
+      buf: Self::empty_buf(),
+    }
+  }
+
+  /// `pop` removes the last bit and returns it, `None` if the vector is empty.
+  ///
+  pub fn pop(&mut self) -> Option<bool> {
+    let len = self.len();
+    let value = self.get(len.checked_sub(1)?)?;
+
+    self.truncate(len - 1);
+    Some(value)
+  }
+
+  /// `push` appends `value` to the end of the vector.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn push(&mut self, value: bool) {
+    self.reserve(1);
+
+    let len = self.len();
+    unsafe {
+      let word = self.data().add(len / BITS);
+      if len.is_multiple_of(BITS) {
+        core::ptr::write(word, 0);
+      }
+
+      *word |= usize::from(value) << (len % BITS);
+    }
+
+    self.header_mut().len = len + 1;
+  }
+
+  /// `remove` removes the bit at `index` and returns it, shifting all of the bits after it down by one.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= len()`.
+  ///
+  pub fn remove(&mut self, index: usize) -> bool {
+    let len = self.len();
+
+    assert!(
+      (index < len),
+      "removal index (is {}) should be < len (is {})",
+      index,
+      len
+    );
+
+    let words = self.as_words_mut();
+    let (w, b) = (index / BITS, index % BITS);
+    let value = words[w] & (1 << b) != 0;
+
+    let low = (1 << b) - 1;
+    let word = words[w];
+    words[w] = (word & low) | ((word >> 1) & !low);
+
+    for i in w + 1..words.len() {
+      words[i - 1] |= (words[i] & 1) << (BITS - 1);
+      words[i] >>= 1;
+    }
+
+    self.header_mut().len = len - 1;
+    value
+  }
+
+  /// `reserve` ensures that the vector can hold at least `additional` more bits without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    handle_reserve(self.try_reserve(additional));
+  }
+
+  /// `resize` changes the length of the vector to `new_len`, filling any new bits with `value`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bits = minivec::MiniBitVec::new();
+  ///
+  /// bits.resize(100, true);
+  /// assert_eq!(bits.count_ones(), 100);
+  ///
+  /// bits.resize(10, false);
+  /// assert_eq!(bits.count_ones(), 10);
+  /// ```
+  ///
+  pub fn resize(&mut self, new_len: usize, value: bool) {
+    let len = self.len();
+    if new_len <= len {
+      self.truncate(new_len);
+      return;
+    }
+
+    self.reserve(new_len - len);
+
+    unsafe {
+      let data = self.data();
+      let used = words_for(len);
+      core::ptr::write_bytes(data.add(used), 0, words_for(new_len) - used);
+    }
+
+    self.header_mut().len = new_len;
+
+    if value {
+      let words = self.as_words_mut();
+      let mut idx = len;
+
+      while idx < new_len {
+        let (w, b) = (idx / BITS, idx % BITS);
+        let n = core::cmp::min(BITS - b, new_len - idx);
+
+        words[w] |= (usize::MAX >> (BITS - n)) << b;
+        idx += n;
+      }
+    }
+  }
+
+  /// `set` sets the bit at `index` to `value`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= len()`.
+  ///
+  pub fn set(&mut self, index: usize, value: bool) {
+    let len = self.len();
+
+    assert!(
+      (index < len),
+      "index (is {}) should be < len (is {})",
+      index,
+      len
+    );
+
+    let word = &mut self.as_words_mut()[index / BITS];
+    let mask = 1 << (index % BITS);
+
+    if value {
+      *word |= mask;
+    } else {
+      *word &= !mask;
+    }
+  }
+
+  /// `truncate` shortens the vector to `len` bits, doing nothing if it's already shorter.
+  ///
+  pub fn truncate(&mut self, len: usize) {
+    if len >= self.len() {
+      return;
+    }
+
+    self.header_mut().len = len;
+    self.clear_tail();
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniBitVec::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    let required = self
+      .len()
+      .checked_add(additional)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    let required = words_for(required);
+    let cap = self.header().cap;
+    if required <= cap {
+      return Ok(());
+    }
+
+    let new_cap = core::cmp::max(
+      Doubling::next_capacity(core::mem::size_of::<usize>(), cap, required),
+      required,
+    );
+
+    self.grow(new_cap)
+  }
+
+  /// `try_with_capacity` is the fallible version of [`with_capacity`](MiniBitVec::with_capacity).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the capacity overflows or the allocator fails.
+  ///
+  pub fn try_with_capacity(capacity: usize) -> Result<MiniBitVec, TryReserveError> {
+    let mut bits = Self::new();
+    bits.try_reserve(capacity)?;
+    Ok(bits)
+  }
+
+  /// `with_capacity` creates an empty bit vector that can hold at least `capacity` bits without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniBitVec {
+    handle_reserve(Self::try_with_capacity(capacity))
+  }
+
+  fn grow(&mut self, cap: usize) -> Result<(), TryReserveError> {
+    let layout =
+      make_prefixed_layout::<BitHeader, usize>(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    let len = self.len();
+    let buf = if self.is_default() {
+      Global.allocate(layout)
+    } else {
+      unsafe {
+        let old_layout =
+          make_prefixed_layout::<BitHeader, usize>(self.header().cap).unwrap_unchecked();
+        Global.grow(self.buf, old_layout, layout)
+      }
+    };
+
+    let buf = buf
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      core::ptr::write(buf.as_ptr().cast::<BitHeader>(), BitHeader { len, cap });
+    }
+
+    self.buf = buf;
+    Ok(())
+  }
+
+  // clears the bits of the last word which lie past the end of the vector
+  //
+  fn clear_tail(&mut self) {
+    let len = self.len();
+    if !len.is_multiple_of(BITS) {
+      unsafe { *self.data().add(len / BITS) &= (1 << (len % BITS)) - 1 };
+    }
+  }
+
+  fn as_words_mut(&mut self) -> &mut [usize] {
+    unsafe { core::slice::from_raw_parts_mut(self.data(), words_for(self.len())) }
+  }
+
+  fn header(&self) -> &BitHeader {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<BitHeader>()
+    }
+  }
+
+  fn header_mut(&mut self) -> &mut BitHeader {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &mut *self.buf.as_ptr().cast::<BitHeader>()
+    }
+  }
+
+  fn data(&self) -> *mut usize {
+    if self.is_default() {
+      return core::ptr::NonNull::dangling().as_ptr();
+    }
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      self.buf.as_ptr().add(Self::OFFSET).cast::<usize>()
+    }
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), Self::empty_buf().as_ptr())
+  }
+
+  const fn empty_buf() -> core::ptr::NonNull<u8> {
+    let p = core::ptr::addr_of!(EMPTY_BIT_HEADER)
+      .cast::<u8>()
+      .cast_mut();
+    unsafe { core::ptr::NonNull::new_unchecked(p) }
+  }
+}
+
+impl Drop for MiniBitVec {
+  fn drop(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    unsafe {
+      let layout = make_prefixed_layout::<BitHeader, usize>(self.header().cap).unwrap_unchecked();
+      Global.deallocate(self.buf, layout);
+    }
+  }
+}
+
+impl Clone for MiniBitVec {
+  fn clone(&self) -> Self {
+    let mut bits = Self::with_capacity(self.len());
+    if bits.is_default() {
+      return bits;
+    }
+
+    let words = self.as_words();
+    unsafe { core::ptr::copy_nonoverlapping(words.as_ptr(), bits.data(), words.len()) };
+    bits.header_mut().len = self.len();
+    bits
+  }
+}
+
+impl core::fmt::Debug for MiniBitVec {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl Default for MiniBitVec {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl core::hash::Hash for MiniBitVec {
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    state.write_usize(self.len());
+    self.as_words().hash(state);
+  }
+}
+
+impl Eq for MiniBitVec {}
+
+impl PartialEq for MiniBitVec {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.as_words() == other.as_words()
+  }
+}
+
+// the bitwise operators work on whole words, relying on the unused bits of both last words being cleared
+//
+fn zip_words(lhs: &mut MiniBitVec, rhs: &MiniBitVec, f: impl Fn(usize, usize) -> usize) {
+  assert_eq!(
+    lhs.len(),
+    rhs.len(),
+    "bit vectors of different lengths can't be combined"
+  );
+
+  for (l, &r) in lhs.as_words_mut().iter_mut().zip(rhs.as_words()) {
+    *l = f(*l, r);
+  }
+}
+
+impl core::ops::BitAndAssign<&MiniBitVec> for MiniBitVec {
+  fn bitand_assign(&mut self, rhs: &MiniBitVec) {
+    zip_words(self, rhs, |l, r| l & r);
+  }
+}
+
+impl core::ops::BitOrAssign<&MiniBitVec> for MiniBitVec {
+  fn bitor_assign(&mut self, rhs: &MiniBitVec) {
+    zip_words(self, rhs, |l, r| l | r);
+  }
+}
+
+impl core::ops::BitXorAssign<&MiniBitVec> for MiniBitVec {
+  fn bitxor_assign(&mut self, rhs: &MiniBitVec) {
+    zip_words(self, rhs, |l, r| l ^ r);
+  }
+}
+
+impl core::ops::Not for MiniBitVec {
+  type Output = MiniBitVec;
+
+  fn not(mut self) -> MiniBitVec {
+    for word in self.as_words_mut() {
+      *word = !*word;
+    }
+
+    self.clear_tail();
+    self
+  }
+}
+
+impl Extend<bool> for MiniBitVec {
+  fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+
+    for value in iter {
+      self.push(value);
+    }
+  }
+}
+
+impl core::iter::FromIterator<bool> for MiniBitVec {
+  fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+    let mut bits = Self::new();
+    bits.extend(iter);
+    bits
+  }
+}
+
+impl From<&[bool]> for MiniBitVec {
+  fn from(values: &[bool]) -> Self {
+    values.iter().copied().collect()
+  }
+}
+
+impl<'a> IntoIterator for &'a MiniBitVec {
+  type Item = bool;
+  type IntoIter = BitIter<'a>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl Iterator for BitIter<'_> {
+  type Item = bool;
+
+  fn next(&mut self) -> Option<bool> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    self.idx += 1;
+    self.bits.get(self.idx - 1)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.end - self.idx;
+    (len, Some(len))
+  }
+}
+
+impl DoubleEndedIterator for BitIter<'_> {
+  fn next_back(&mut self) -> Option<bool> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    self.end -= 1;
+    self.bits.get(self.end)
+  }
+}
+
+impl ExactSizeIterator for BitIter<'_> {}
+
+impl core::iter::FusedIterator for BitIter<'_> {}
+
+impl Iterator for IterOnes<'_> {
+  type Item = usize;
+
+  fn next(&mut self) -> Option<usize> {
+    while self.word == 0 {
+      self.word = *self.words.next()?;
+      self.base += BITS;
+    }
+
+    let idx = self.base - BITS + self.word.trailing_zeros() as usize;
+    self.word &= self.word - 1;
+    Some(idx)
+  }
+}
+
+impl core::iter::FusedIterator for IterOnes<'_> {}
+
+unsafe impl Send for MiniBitVec {}
+unsafe impl Sync for MiniBitVec {}
@@ -0,0 +1,538 @@
+use crate::r#impl::helpers::{
+  make_aligned_layout, make_prefixed_layout, max_align, prefixed_data_offset,
+};
+use crate::{handle_reserve, Allocator, Global, GrowthPolicy, IndexType, MiniVec};
+use crate::{TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+/// `MiniBoxSlice` is a fixed-size, pointer-sized boxed slice. Its allocation only stores the length in front of the
+/// elements and is sized exactly, without any slack capacity.
+///
+/// A `MiniBoxSlice` is typically created from a [`MiniVec`] through
+/// [`into_boxed_mini_slice`](MiniVec::into_boxed_mini_slice) once the data no longer needs to grow, and can be turned
+/// back into one with [`into_mini_vec`](MiniBoxSlice::into_mini_vec). Much like an empty `MiniVec`, an empty
+/// `MiniBoxSlice` does not allocate.
+///
+/// # Example
+///
+/// ```
+/// let vec = minivec::mini_vec![1, 2, 3];
+/// let slice = vec.into_boxed_mini_slice();
+///
+/// assert_eq!(*slice, [1, 2, 3]);
+/// assert_eq!(core::mem::size_of_val(&slice), core::mem::size_of::<usize>());
+///
+/// let mut vec = slice.into_mini_vec();
+/// vec.push(4);
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// ```
+///
+pub struct MiniBoxSlice<T> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<T>,
+}
+
+impl<T> MiniBoxSlice<T> {
+  const OFFSET: usize = prefixed_data_offset::<usize, T>();
+
+  /// `as_mut_ptr` returns a mutable pointer to the first element of the slice.
+  ///
+  pub fn as_mut_ptr(&mut self) -> *mut T {
+    self.data()
+  }
+
+  /// `as_mut_slice` returns a mutable slice over all of the elements.
+  ///
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    unsafe { core::slice::from_raw_parts_mut(self.data(), self.len()) }
+  }
+
+  /// `as_ptr` returns a pointer to the first element of the slice.
+  ///
+  #[must_use]
+  pub fn as_ptr(&self) -> *const T {
+    self.data()
+  }
+
+  /// `as_slice` returns a slice over all of the elements.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+  }
+
+  /// `into_mini_vec` converts the slice back into a growable [`MiniVec`] with a capacity equal to its length. The
+  /// allocation is reused, only growing by the size of the vector's header.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let slice: minivec::MiniBoxSlice<_> = (0..3).collect();
+  /// let vec = slice.into_mini_vec();
+  ///
+  /// assert_eq!(vec, [0, 1, 2]);
+  /// assert_eq!(vec.capacity(), 3);
+  /// ```
+  ///
+  #[must_use]
+  pub fn into_mini_vec(self) -> MiniVec<T> {
+    if self.is_empty() {
+      return MiniVec::new();
+    }
+
+    let mut this = core::mem::ManuallyDrop::new(self);
+    let len = this.len();
+
+    let old_layout = unsafe { make_prefixed_layout::<usize, T>(len).unwrap_unchecked() };
+    let new_layout = handle_reserve(
+      make_aligned_layout::<T, Global, usize>(len, max_align::<T, Global, usize>())
+        .ok_or(TryReserveError::from(TryReserveErrorKind::CapacityOverflow)),
+    );
+
+    if old_layout.align() != new_layout.align() {
+      let mut vec = MiniVec::with_capacity(len);
+      unsafe {
+        core::ptr::copy_nonoverlapping(this.data(), vec.as_mut_ptr(), len);
+        vec.set_len(len);
+        this.deallocate();
+      }
+
+      return vec;
+    }
+
+    // the vector's header is never smaller than our length so the elements only ever move towards the end
+    //
+    let buf = match unsafe { Global.grow(this.buf, old_layout, new_layout) } {
+      Ok(p) => p.cast::<u8>(),
+      Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+    };
+
+    unsafe {
+      let p = buf.as_ptr();
+      core::ptr::copy(
+        p.add(Self::OFFSET).cast::<T>(),
+        p.add(MiniVec::<T>::N).cast::<T>(),
+        len,
+      );
+      MiniVec::<T>::write_header(p, len, MiniVec::<T>::stored_capacity(len));
+    }
+
+    MiniVec::from_buf(buf)
+  }
+
+  /// `is_empty` returns whether or not the slice contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `len` returns the number of elements in the slice.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      *self.buf.as_ptr().cast::<usize>()
+    }
+  }
+
+  /// `new` constructs an empty `MiniBoxSlice`, which does not allocate.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let slice = minivec::MiniBoxSlice::<i32>::new();
+  /// assert!(slice.is_empty());
+  /// ```
+  ///
+  #[must_use]
+  pub const fn new() -> MiniBoxSlice<T> {
+    MiniBoxSlice {
+      buf: Self::empty_buf(),
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  // takes over the allocation of `vec`, shrinking it down to the elements, unless the two disagree on its alignment
+  //
+  pub(crate) fn from_mini_vec<H: IndexType, G: GrowthPolicy>(
+    vec: MiniVec<T, Global, H, G>,
+  ) -> MiniBoxSlice<T> {
+    let len = vec.len();
+    if len == 0 {
+      return Self::new();
+    }
+
+    let mut vec = core::mem::ManuallyDrop::new(vec);
+    let (old_buf, old_layout) = vec.allocation();
+
+    // a slice is never larger than the vector holding its elements
+    //
+    let new_layout = unsafe { make_prefixed_layout::<usize, T>(len).unwrap_unchecked() };
+
+    if old_layout.align() != new_layout.align() {
+      let slice = handle_reserve(Self::try_allocate(len));
+      unsafe {
+        core::ptr::copy_nonoverlapping(vec.as_ptr(), slice.data(), len);
+        vec.set_len(0);
+        core::mem::ManuallyDrop::drop(&mut vec);
+      }
+
+      return slice;
+    }
+
+    // the length is never stored further in than the vector's header so the elements only ever move towards the front
+    //
+    unsafe {
+      core::ptr::copy(
+        vec.as_ptr(),
+        old_buf.as_ptr().add(Self::OFFSET).cast::<T>(),
+        len,
+      );
+    }
+
+    let buf = match unsafe { Global.shrink(old_buf, old_layout, new_layout) } {
+      Ok(p) => p.cast::<u8>(),
+      Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+    };
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      core::ptr::write(buf.as_ptr().cast::<usize>(), len);
+    }
+
+    MiniBoxSlice {
+      buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  // allocates room for exactly `len` elements and records `len` in the header, the elements themselves are left
+  // uninitialized
+  //
+  fn try_allocate(len: usize) -> Result<MiniBoxSlice<T>, TryReserveError> {
+    if len == 0 {
+      return Ok(Self::new());
+    }
+
+    let layout =
+      make_prefixed_layout::<usize, T>(len).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+    let buf = Global
+      .allocate(layout)
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      core::ptr::write(buf.as_ptr().cast::<usize>(), len);
+    }
+
+    Ok(MiniBoxSlice {
+      buf,
+      phantom: core::marker::PhantomData,
+    })
+  }
+
+  // releases the allocation without dropping any of the elements
+  //
+  unsafe fn deallocate(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    let layout = make_prefixed_layout::<usize, T>(self.len()).unwrap_unchecked();
+    Global.deallocate(self.buf, layout);
+  }
+
+  fn data(&self) -> *mut T {
+    // same as `MiniVec::data()`
+    //
+    if self.is_default() {
+      return core::ptr::NonNull::dangling().as_ptr();
+    }
+
+    unsafe { self.buf.as_ptr().add(Self::OFFSET).cast::<T>() }
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), Self::empty_buf().as_ptr())
+  }
+
+  const fn empty_buf() -> core::ptr::NonNull<u8> {
+    // the leading word of the shared empty header doubles as a length of `0`
+    //
+    let p = core::ptr::addr_of!(crate::EMPTY_HEADER)
+      .cast::<u8>()
+      .cast_mut();
+    unsafe { core::ptr::NonNull::new_unchecked(p) }
+  }
+}
+
+impl<T> Drop for MiniBoxSlice<T> {
+  fn drop(&mut self) {
+    unsafe {
+      core::ptr::drop_in_place(self.as_mut_slice());
+      self.deallocate();
+    }
+  }
+}
+
+impl<T> core::ops::Deref for MiniBoxSlice<T> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    self.as_slice()
+  }
+}
+
+impl<T> core::ops::DerefMut for MiniBoxSlice<T> {
+  fn deref_mut(&mut self) -> &mut [T] {
+    self.as_mut_slice()
+  }
+}
+
+impl<T> AsRef<[T]> for MiniBoxSlice<T> {
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T> AsMut<[T]> for MiniBoxSlice<T> {
+  fn as_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<T> core::borrow::Borrow<[T]> for MiniBoxSlice<T> {
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T> core::borrow::BorrowMut<[T]> for MiniBoxSlice<T> {
+  fn borrow_mut(&mut self) -> &mut [T] {
+    self
+  }
+}
+
+impl<T: Clone> Clone for MiniBoxSlice<T> {
+  fn clone(&self) -> Self {
+    MiniBoxSlice::from(self.as_slice())
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniBoxSlice<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl<T> Default for MiniBoxSlice<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniBoxSlice<T> {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.as_slice().hash(state);
+  }
+}
+
+impl<T: Eq> Eq for MiniBoxSlice<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<MiniBoxSlice<U>> for MiniBoxSlice<T> {
+  fn eq(&self, other: &MiniBoxSlice<U>) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for MiniBoxSlice<T> {
+  fn eq(&self, other: &[U]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for MiniBoxSlice<T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: Ord> Ord for MiniBoxSlice<T> {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.as_slice().cmp(other.as_slice())
+  }
+}
+
+impl<T: PartialOrd> PartialOrd for MiniBoxSlice<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    self.as_slice().partial_cmp(other.as_slice())
+  }
+}
+
+impl<T: Clone> From<&[T]> for MiniBoxSlice<T> {
+  fn from(s: &[T]) -> Self {
+    // should a `clone()` panic, only the elements written so far are dropped
+    //
+    struct Guard<T> {
+      slice: core::mem::ManuallyDrop<MiniBoxSlice<T>>,
+      written: usize,
+    }
+
+    impl<T> Drop for Guard<T> {
+      fn drop(&mut self) {
+        unsafe {
+          core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+            self.slice.data(),
+            self.written,
+          ));
+          self.slice.deallocate();
+        }
+      }
+    }
+
+    let mut guard = Guard {
+      slice: core::mem::ManuallyDrop::new(handle_reserve(MiniBoxSlice::<T>::try_allocate(s.len()))),
+      written: 0,
+    };
+
+    for x in s {
+      unsafe { core::ptr::write(guard.slice.data().add(guard.written), x.clone()) };
+      guard.written += 1;
+    }
+
+    let slice = unsafe { core::ptr::read(core::ptr::addr_of!(*guard.slice)) };
+    core::mem::forget(guard);
+    slice
+  }
+}
+
+impl<T, H: IndexType, G: GrowthPolicy> From<MiniVec<T, Global, H, G>> for MiniBoxSlice<T> {
+  fn from(vec: MiniVec<T, Global, H, G>) -> Self {
+    vec.into_boxed_mini_slice()
+  }
+}
+
+impl<T> From<MiniBoxSlice<T>> for MiniVec<T> {
+  fn from(slice: MiniBoxSlice<T>) -> Self {
+    slice.into_mini_vec()
+  }
+}
+
+impl<T> core::iter::FromIterator<T> for MiniBoxSlice<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    iter
+      .into_iter()
+      .collect::<MiniVec<T>>()
+      .into_boxed_mini_slice()
+  }
+}
+
+impl<T> IntoIterator for MiniBoxSlice<T> {
+  type Item = T;
+  type IntoIter = BoxSliceIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    let end = self.len();
+
+    BoxSliceIntoIter {
+      slice: core::mem::ManuallyDrop::new(self),
+      pos: 0,
+      end,
+    }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniBoxSlice<T> {
+  type Item = &'a T;
+  type IntoIter = core::slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut MiniBoxSlice<T> {
+  type Item = &'a mut T;
+  type IntoIter = core::slice::IterMut<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+unsafe impl<T: Send> Send for MiniBoxSlice<T> {}
+unsafe impl<T: Sync> Sync for MiniBoxSlice<T> {}
+
+/// `BoxSliceIntoIter` is an iterator that consumes a [`MiniBoxSlice`] and transfers ownership of its elements to the
+/// caller.
+///
+pub struct BoxSliceIntoIter<T> {
+  slice: core::mem::ManuallyDrop<MiniBoxSlice<T>>,
+  pos: usize,
+  end: usize,
+}
+
+impl<T> BoxSliceIntoIter<T> {
+  /// `as_slice` returns a slice over the elements that haven't been yielded yet.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.slice.data().add(self.pos), self.end - self.pos) }
+  }
+}
+
+impl<T> Iterator for BoxSliceIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    if self.pos == self.end {
+      return None;
+    }
+
+    let x = unsafe { core::ptr::read(self.slice.data().add(self.pos)) };
+    self.pos += 1;
+    Some(x)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.end - self.pos;
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for BoxSliceIntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    if self.pos == self.end {
+      return None;
+    }
+
+    self.end -= 1;
+    Some(unsafe { core::ptr::read(self.slice.data().add(self.end)) })
+  }
+}
+
+impl<T> ExactSizeIterator for BoxSliceIntoIter<T> {}
+
+impl<T> core::iter::FusedIterator for BoxSliceIntoIter<T> {}
+
+impl<T> Drop for BoxSliceIntoIter<T> {
+  fn drop(&mut self) {
+    unsafe {
+      core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+        self.slice.data().add(self.pos),
+        self.end - self.pos,
+      ));
+      self.slice.deallocate();
+    }
+  }
+}
+
+unsafe impl<T: Send> Send for BoxSliceIntoIter<T> {}
+unsafe impl<T: Sync> Sync for BoxSliceIntoIter<T> {}
@@ -0,0 +1,420 @@
+use crate::{normalize_range, MiniVec, RefCount};
+
+/// `MiniBytes` is a cheaply cloneable and sliceable view into an immutable, reference-counted buffer of bytes, much
+/// like `bytes::Bytes`.
+///
+/// A `MiniBytes` is created by [`freeze`](MiniVec::freeze)-ing a `MiniVec<u8>`, which reuses the vector's allocation
+/// as-is: the reference count takes the place of the length in the vector's header. Clones and sub-slices only bump
+/// the reference count and never copy any bytes. A `MiniBytes` which is the only view into its buffer can be turned
+/// back into a `MiniVec<u8>` with [`try_into_mut`](MiniBytes::try_into_mut).
+///
+/// # Example
+///
+/// ```
+/// let mut vec = minivec::MiniVec::new();
+/// vec.extend_from_slice(b"hello world");
+///
+/// let mut bytes = vec.freeze();
+/// let hello = bytes.split_to(5);
+///
+/// assert_eq!(hello, b"hello"[..]);
+/// assert_eq!(bytes, b" world"[..]);
+/// assert_eq!(bytes.slice(1..), b"world"[..]);
+/// ```
+///
+pub struct MiniBytes {
+  buf: core::ptr::NonNull<u8>,
+  ptr: *const u8,
+  len: usize,
+}
+
+impl MiniBytes {
+  /// `as_slice` returns the bytes in the view.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[u8] {
+    unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
+  }
+
+  /// `clear` empties the view, leaving the underlying buffer untouched.
+  ///
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  /// `copy_from_slice` creates a new `MiniBytes` by copying `data` into a new buffer.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let bytes = minivec::MiniBytes::copy_from_slice(b"abc");
+  /// assert_eq!(bytes, b"abc"[..]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn copy_from_slice(data: &[u8]) -> MiniBytes {
+    MiniVec::from(data).freeze()
+  }
+
+  /// `is_empty` returns whether or not the view contains any bytes.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// `len` returns the number of bytes in the view.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// `new` creates an empty `MiniBytes`, which does not allocate.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let bytes = minivec::MiniBytes::new();
+  /// assert!(bytes.is_empty());
+  /// ```
+  ///
+  #[must_use]
+  pub const fn new() -> MiniBytes {
+    MiniBytes {
+      buf: MiniVec::<u8>::empty_buf(),
+      ptr: core::ptr::NonNull::<u8>::dangling().as_ptr(),
+      len: 0,
+    }
+  }
+
+  /// `slice` returns a new view of the bytes in `range`, relative to this one, that shares the same buffer.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the range is outside of the view.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let bytes = minivec::MiniBytes::copy_from_slice(b"hello world");
+  /// let world = bytes.slice(6..);
+  ///
+  /// assert_eq!(world, b"world"[..]);
+  /// assert_eq!(world.slice(..=2), b"wor"[..]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn slice<R>(&self, range: R) -> MiniBytes
+  where
+    R: core::ops::RangeBounds<usize>,
+  {
+    let (start, end) = match normalize_range(&range, self.len) {
+      Ok(range) => range,
+      Err(e) => panic!("invalid slice range: {}", e),
+    };
+
+    let mut bytes = self.clone();
+    bytes.ptr = unsafe { self.ptr.add(start) };
+    bytes.len = end - start;
+    bytes
+  }
+
+  /// `split_off` splits the view in two at `at`, returning the bytes from `at` onwards while `self` keeps the bytes
+  /// before it. Both views share the same buffer.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `at > len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bytes = minivec::MiniBytes::copy_from_slice(b"hello world");
+  /// let world = bytes.split_off(6);
+  ///
+  /// assert_eq!(bytes, b"hello "[..]);
+  /// assert_eq!(world, b"world"[..]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn split_off(&mut self, at: usize) -> MiniBytes {
+    assert!(
+      at <= self.len,
+      "split_off index (is {}) should be <= len (is {})",
+      at,
+      self.len
+    );
+
+    let tail = self.slice(at..);
+    self.len = at;
+    tail
+  }
+
+  /// `split_to` splits the view in two at `at`, returning the bytes before `at` while `self` keeps the bytes from
+  /// `at` onwards. Both views share the same buffer.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `at > len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bytes = minivec::MiniBytes::copy_from_slice(b"hello world");
+  /// let hello = bytes.split_to(5);
+  ///
+  /// assert_eq!(hello, b"hello"[..]);
+  /// assert_eq!(bytes, b" world"[..]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn split_to(&mut self, at: usize) -> MiniBytes {
+    assert!(
+      at <= self.len,
+      "split_to index (is {}) should be <= len (is {})",
+      at,
+      self.len
+    );
+
+    let head = self.slice(..at);
+    self.ptr = unsafe { self.ptr.add(at) };
+    self.len -= at;
+    head
+  }
+
+  /// `truncate` shortens the view to `len` bytes. Nothing happens if `len` is greater than or equal to the current
+  /// length.
+  ///
+  pub fn truncate(&mut self, len: usize) {
+    if len < self.len {
+      self.len = len;
+    }
+  }
+
+  /// `try_into_mut` converts the view back into a `MiniVec<u8>` holding its bytes, provided it's the only view into
+  /// its buffer. The buffer is reused, with the bytes shifted down to its start if the view doesn't begin there.
+  ///
+  /// # Errors
+  ///
+  /// Returns `self` unchanged if other views share its buffer.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bytes = minivec::MiniBytes::copy_from_slice(b"hello world");
+  /// let hello = bytes.split_to(6);
+  ///
+  /// let bytes = bytes.try_into_mut().unwrap_err();
+  /// drop(hello);
+  ///
+  /// let mut vec = bytes.try_into_mut().unwrap();
+  /// vec.push(b'!');
+  /// assert_eq!(vec, b"world!");
+  /// ```
+  ///
+  pub fn try_into_mut(self) -> Result<MiniVec<u8>, MiniBytes> {
+    if self.is_default() {
+      return Ok(MiniVec::new());
+    }
+
+    if self.ref_count().load(core::sync::atomic::Ordering::Acquire) != 1 {
+      return Err(self);
+    }
+
+    let this = core::mem::ManuallyDrop::new(self);
+    let mut vec = MiniVec::<u8>::from_buf(this.buf);
+
+    unsafe {
+      core::ptr::copy(this.ptr, vec.as_mut_ptr(), this.len);
+      vec.header_mut().len = this.len;
+    }
+
+    Ok(vec)
+  }
+
+  // the reference count lives where the length of the frozen vector used to be
+  //
+  fn ref_count(&self) -> &core::sync::atomic::AtomicUsize {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<core::sync::atomic::AtomicUsize>()
+    }
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), MiniVec::<u8>::empty_buf().as_ptr())
+  }
+}
+
+impl MiniVec<u8> {
+  /// `freeze` converts the vector into an immutable, reference-counted [`MiniBytes`] spanning all of its bytes. The
+  /// allocation is reused without copying.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::mini_vec![1, 2, 3];
+  /// let ptr = vec.as_ptr();
+  ///
+  /// let bytes = vec.freeze();
+  /// assert_eq!(bytes.as_ptr(), ptr);
+  /// assert_eq!(bytes, [1, 2, 3][..]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn freeze(self) -> MiniBytes {
+    if self.is_default() {
+      return MiniBytes::new();
+    }
+
+    let mut vec = core::mem::ManuallyDrop::new(self);
+    let len = vec.len();
+    let ptr = vec.as_ptr();
+
+    vec.header_mut().len = 1;
+
+    MiniBytes {
+      buf: vec.buf,
+      ptr,
+      len,
+    }
+  }
+}
+
+impl Drop for MiniBytes {
+  fn drop(&mut self) {
+    if self.is_default()
+      || self
+        .ref_count()
+        .fetch_sub(1, core::sync::atomic::Ordering::Release)
+        != 1
+    {
+      return;
+    }
+
+    core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+
+    // hand the allocation back to a `MiniVec` with no elements, it knows how to release it
+    //
+    let mut vec = MiniVec::<u8>::from_buf(self.buf);
+    vec.header_mut().len = 0;
+  }
+}
+
+impl Clone for MiniBytes {
+  fn clone(&self) -> Self {
+    if !self.is_default() {
+      self.ref_count().increment();
+    }
+
+    MiniBytes {
+      buf: self.buf,
+      ptr: self.ptr,
+      len: self.len,
+    }
+  }
+}
+
+impl core::ops::Deref for MiniBytes {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    self.as_slice()
+  }
+}
+
+impl AsRef<[u8]> for MiniBytes {
+  fn as_ref(&self) -> &[u8] {
+    self
+  }
+}
+
+impl core::borrow::Borrow<[u8]> for MiniBytes {
+  fn borrow(&self) -> &[u8] {
+    self
+  }
+}
+
+impl core::fmt::Debug for MiniBytes {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl Default for MiniBytes {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl core::hash::Hash for MiniBytes {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.as_slice().hash(state);
+  }
+}
+
+impl Eq for MiniBytes {}
+
+impl PartialEq for MiniBytes {
+  fn eq(&self, other: &MiniBytes) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl PartialEq<[u8]> for MiniBytes {
+  fn eq(&self, other: &[u8]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl PartialEq<&[u8]> for MiniBytes {
+  fn eq(&self, other: &&[u8]) -> bool {
+    self.as_slice() == *other
+  }
+}
+
+impl Ord for MiniBytes {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.as_slice().cmp(other.as_slice())
+  }
+}
+
+impl PartialOrd for MiniBytes {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl From<MiniVec<u8>> for MiniBytes {
+  fn from(vec: MiniVec<u8>) -> Self {
+    vec.freeze()
+  }
+}
+
+impl From<&[u8]> for MiniBytes {
+  fn from(data: &[u8]) -> Self {
+    MiniBytes::copy_from_slice(data)
+  }
+}
+
+impl core::iter::FromIterator<u8> for MiniBytes {
+  fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+    iter.into_iter().collect::<MiniVec<u8>>().freeze()
+  }
+}
+
+impl<'a> IntoIterator for &'a MiniBytes {
+  type Item = &'a u8;
+  type IntoIter = core::slice::Iter<'a, u8>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+unsafe impl Send for MiniBytes {}
+unsafe impl Sync for MiniBytes {}
@@ -0,0 +1,374 @@
+use crate::{handle_reserve, Allocator, Global, MiniVec, RefCount, TryReserveErrorKind};
+
+extern crate alloc;
+
+// every pointer a `MiniCow` holds points at something aligned to at least `align_of::<usize>()` which leaves the low
+// two bits free to tell the three states apart:
+//
+// * `OWNED`, the buffer of a `MiniVec<T>` the cow owns
+// * `BORROWED_VEC`, the buffer of a `&'a MiniVec<T>`, which already records the length in its header
+// * `BORROWED_SLICE`, an out-of-line `SliceHeader<T>` describing a `&'a [T]`, shared by the cow and all of its clones
+//
+const OWNED: usize = 0;
+const BORROWED_VEC: usize = 1;
+const BORROWED_SLICE: usize = 2;
+const TAG_MASK: usize = 3;
+
+struct SliceHeader<T> {
+  ptr: *const T,
+  len: usize,
+  refs: core::sync::atomic::AtomicUsize,
+}
+
+union Repr<T> {
+  owned: core::mem::ManuallyDrop<MiniVec<T>>,
+  raw: core::ptr::NonNull<u8>,
+}
+
+/// `MiniCow` is a clone-on-write slice that's only the size of a single pointer, the counterpart of
+/// `alloc::borrow::Cow<'a, [T]>`. It either borrows its elements or owns them in a [`MiniVec`], turning into the
+/// latter the first time it's mutated through [`to_mut`](MiniCow::to_mut).
+///
+/// A `MiniCow` borrowing a `MiniVec` reuses the header of the vector and never allocates. Borrowing a plain slice,
+/// e.g. a constant table through [`from_static`](MiniCow::from_static), needs somewhere to record its length so a
+/// small header holding the pointer and the length of the slice is allocated instead, leaving the elements where they
+/// are. The header is reference-counted, cloning such a `MiniCow` shares it instead of allocating another one.
+///
+/// # Example
+///
+/// ```
+/// static PRIMES: [u32; 4] = [2, 3, 5, 7];
+///
+/// let mut primes = minivec::MiniCow::from_static(&PRIMES);
+/// assert!(primes.is_borrowed());
+/// assert_eq!(primes, [2, 3, 5, 7]);
+///
+/// primes.to_mut().push(11);
+///
+/// assert!(primes.is_owned());
+/// assert_eq!(primes, [2, 3, 5, 7, 11]);
+/// assert_eq!(PRIMES, [2, 3, 5, 7]);
+/// assert_eq!(core::mem::size_of_val(&primes), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniCow<'a, T> {
+  repr: Repr<T>,
+  phantom: core::marker::PhantomData<&'a [T]>,
+}
+
+impl<'a, T> MiniCow<'a, T> {
+  /// `as_slice` returns the elements as a slice, whether they're borrowed or owned.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    match self.tag() {
+      OWNED => unsafe { self.repr.owned.as_slice() },
+      BORROWED_VEC => {
+        let vec = self.vec_view();
+        unsafe { core::slice::from_raw_parts(vec.as_ptr(), vec.len()) }
+      }
+      _ => unsafe {
+        let header = &*self.slice_header();
+        core::slice::from_raw_parts(header.ptr, header.len)
+      },
+    }
+  }
+
+  /// `borrowed` creates a `MiniCow` borrowing `slice`. The header recording the length of `slice` is allocated once and
+  /// then shared with every clone of the returned `MiniCow`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the allocation of the slice header fails.
+  ///
+  #[must_use]
+  pub fn borrowed(slice: &'a [T]) -> MiniCow<'a, T> {
+    let layout = alloc::alloc::Layout::new::<SliceHeader<T>>();
+    let header = handle_reserve(
+      Global
+        .allocate(layout)
+        .map_err(|_| From::from(TryReserveErrorKind::AllocError { layout })),
+    )
+    .cast::<SliceHeader<T>>();
+
+    unsafe {
+      core::ptr::write(
+        header.as_ptr(),
+        SliceHeader {
+          ptr: slice.as_ptr(),
+          len: slice.len(),
+          refs: RefCount::new(1),
+        },
+      );
+    }
+
+    Self::from_raw(header.cast::<u8>(), BORROWED_SLICE)
+  }
+
+  /// `into_owned` returns the elements as a `MiniVec`, cloning them if they're borrowed.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::mini_vec![1, 2, 3];
+  ///
+  /// let cow = minivec::MiniCow::from(&vec);
+  /// assert_eq!(cow.into_owned(), vec);
+  /// ```
+  ///
+  #[must_use]
+  pub fn into_owned(mut self) -> MiniVec<T>
+  where
+    T: Clone,
+  {
+    self.to_mut();
+
+    let mut this = core::mem::ManuallyDrop::new(self);
+    unsafe { core::mem::ManuallyDrop::take(&mut this.repr.owned) }
+  }
+
+  /// `is_borrowed` returns whether or not the elements are borrowed.
+  ///
+  #[must_use]
+  pub fn is_borrowed(&self) -> bool {
+    !self.is_owned()
+  }
+
+  /// `is_empty` returns whether or not there are any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `is_owned` returns whether or not the elements are owned.
+  ///
+  #[must_use]
+  pub fn is_owned(&self) -> bool {
+    self.tag() == OWNED
+  }
+
+  /// `len` returns the number of elements.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.as_slice().len()
+  }
+
+  /// `to_mut` returns a mutable reference to the owned `MiniVec`, cloning the elements into one first if they're
+  /// borrowed.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the allocation of the vector fails.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let vec = minivec::mini_vec![1, 2, 3];
+  ///
+  /// let mut cow = minivec::MiniCow::from(&vec);
+  /// cow.to_mut()[0] = 0;
+  ///
+  /// assert_eq!(cow, [0, 2, 3]);
+  /// assert_eq!(vec, [1, 2, 3]);
+  /// ```
+  ///
+  pub fn to_mut(&mut self) -> &mut MiniVec<T>
+  where
+    T: Clone,
+  {
+    if self.is_borrowed() {
+      let owned = MiniCow::from(MiniVec::from(self.as_slice()));
+      *self = owned;
+    }
+
+    unsafe { &mut self.repr.owned }
+  }
+
+  fn from_raw(raw: core::ptr::NonNull<u8>, tag: usize) -> MiniCow<'a, T> {
+    MiniCow {
+      repr: Repr {
+        raw: unsafe { core::ptr::NonNull::new_unchecked(raw.as_ptr().wrapping_add(tag)) },
+      },
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  fn slice_header(&self) -> *mut SliceHeader<T> {
+    debug_assert!(self.tag() == BORROWED_SLICE);
+
+    #[allow(clippy::cast_ptr_alignment)]
+    self.untagged().as_ptr().cast::<SliceHeader<T>>()
+  }
+
+  fn tag(&self) -> usize {
+    unsafe { self.repr.raw.as_ptr() as usize & TAG_MASK }
+  }
+
+  fn untagged(&self) -> core::ptr::NonNull<u8> {
+    unsafe { core::ptr::NonNull::new_unchecked(self.repr.raw.as_ptr().wrapping_sub(self.tag())) }
+  }
+
+  // the borrowed vector, which must never be dropped as the cow doesn't own it
+  //
+  fn vec_view(&self) -> core::mem::ManuallyDrop<MiniVec<T>> {
+    debug_assert!(self.tag() == BORROWED_VEC);
+    core::mem::ManuallyDrop::new(MiniVec::from_buf(self.untagged()))
+  }
+}
+
+impl<T> MiniCow<'static, T> {
+  /// `from_static` creates a `MiniCow` borrowing `slice` for the rest of the program, e.g. a constant table that's
+  /// only occasionally modified.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the allocation of the slice header fails.
+  ///
+  #[must_use]
+  pub fn from_static(slice: &'static [T]) -> MiniCow<'static, T> {
+    MiniCow::borrowed(slice)
+  }
+}
+
+impl<T> Drop for MiniCow<'_, T> {
+  fn drop(&mut self) {
+    match self.tag() {
+      OWNED => unsafe { core::mem::ManuallyDrop::drop(&mut self.repr.owned) },
+      BORROWED_VEC => {}
+      _ => unsafe {
+        if (*self.slice_header()).refs.decrement() {
+          Global.deallocate(
+            self.untagged(),
+            alloc::alloc::Layout::new::<SliceHeader<T>>(),
+          );
+        }
+      },
+    }
+  }
+}
+
+impl<T: Clone> Clone for MiniCow<'_, T> {
+  fn clone(&self) -> Self {
+    match self.tag() {
+      OWNED => MiniCow::from(unsafe { MiniVec::clone(&self.repr.owned) }),
+      BORROWED_VEC => MiniCow::from_raw(self.untagged(), BORROWED_VEC),
+      _ => {
+        unsafe { (*self.slice_header()).refs.increment() };
+        MiniCow::from_raw(self.untagged(), BORROWED_SLICE)
+      }
+    }
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniCow<'_, T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl<T> Default for MiniCow<'_, T> {
+  fn default() -> Self {
+    MiniCow::from(MiniVec::new())
+  }
+}
+
+impl<T> core::ops::Deref for MiniCow<'_, T> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    self.as_slice()
+  }
+}
+
+impl<T> AsRef<[T]> for MiniCow<'_, T> {
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T> core::borrow::Borrow<[T]> for MiniCow<'_, T> {
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniCow<'_, T> {
+  fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+    self.as_slice().hash(state);
+  }
+}
+
+impl<T: Eq> Eq for MiniCow<'_, T> {}
+
+impl<'b, T: PartialEq<U>, U> PartialEq<MiniCow<'b, U>> for MiniCow<'_, T> {
+  fn eq(&self, other: &MiniCow<'b, U>) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for MiniCow<'_, T> {
+  fn eq(&self, other: &[U]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for MiniCow<'_, T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: Ord> Ord for MiniCow<'_, T> {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.as_slice().cmp(other.as_slice())
+  }
+}
+
+impl<T: PartialOrd> PartialOrd for MiniCow<'_, T> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    self.as_slice().partial_cmp(other.as_slice())
+  }
+}
+
+impl<'a, T> From<&'a [T]> for MiniCow<'a, T> {
+  fn from(slice: &'a [T]) -> Self {
+    MiniCow::borrowed(slice)
+  }
+}
+
+impl<'a, T> From<&'a MiniVec<T>> for MiniCow<'a, T> {
+  fn from(vec: &'a MiniVec<T>) -> Self {
+    MiniCow::from_raw(vec.buf, BORROWED_VEC)
+  }
+}
+
+impl<T> From<MiniVec<T>> for MiniCow<'_, T> {
+  fn from(vec: MiniVec<T>) -> Self {
+    MiniCow {
+      repr: Repr {
+        owned: core::mem::ManuallyDrop::new(vec),
+      },
+      phantom: core::marker::PhantomData,
+    }
+  }
+}
+
+impl<T: Clone> From<MiniCow<'_, T>> for MiniVec<T> {
+  fn from(cow: MiniCow<'_, T>) -> Self {
+    cow.into_owned()
+  }
+}
+
+impl<'b, T> IntoIterator for &'b MiniCow<'_, T> {
+  type Item = &'b T;
+  type IntoIter = core::slice::Iter<'b, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+unsafe impl<T: Send + Sync> Send for MiniCow<'_, T> {}
+unsafe impl<T: Sync> Sync for MiniCow<'_, T> {}
@@ -0,0 +1,1130 @@
+use crate::r#impl::helpers::{make_prefixed_layout, prefixed_data_offset};
+use crate::{handle_reserve, Allocator, Global, TryReserveError, TryReserveErrorKind};
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+
+extern crate alloc;
+
+// every slot of the table has a control byte which is either `EMPTY`, `DELETED` or the top 7 bits of the hash of the
+// key it holds
+//
+const EMPTY: u8 = 0xff;
+const DELETED: u8 = 0x80;
+
+// the table is laid out as the header, `cap` key-value pairs and then `cap` control bytes, `cap` being a power of two
+//
+#[repr(C)]
+struct TableHeader {
+  len: usize,
+  cap: usize,
+  tombstones: usize,
+}
+
+static EMPTY_TABLE_HEADER: TableHeader = TableHeader {
+  len: 0,
+  cap: 0,
+  tombstones: 0,
+};
+
+// the allocation holding the entries, which knows nothing of how they're hashed
+//
+struct RawTable<K, V> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<(K, V)>,
+}
+
+/// `MiniHashMap` is a hash map using open addressing with linear probing whose control bytes, keys and values all live
+/// in a single allocation, next to a header holding its length, capacity and number of tombstones.
+///
+/// A map is the size of a single pointer as long as its `BuildHasher` is zero-sized, and an empty map doesn't
+/// allocate. As the crate is `no_std`, there's no default hasher and one has to be supplied through the `S` type
+/// parameter.
+///
+/// # Example
+///
+/// ```
+/// type Map<K, V> = minivec::MiniHashMap<K, V, std::collections::hash_map::RandomState>;
+///
+/// let mut map = Map::new();
+/// map.insert("apples", 3);
+/// map.insert("pears", 5);
+///
+/// assert_eq!(map.get("apples"), Some(&3));
+/// assert_eq!(map.insert("pears", 6), Some(5));
+/// assert_eq!(map.remove("apples"), Some(3));
+/// assert_eq!(map.len(), 1);
+/// ```
+///
+pub struct MiniHashMap<K, V, S> {
+  table: RawTable<K, V>,
+  hash_builder: S,
+}
+
+/// `HashEntry` is a view into a single entry of a [`MiniHashMap`], which may be either vacant or occupied. It's
+/// returned by [`MiniHashMap::entry`].
+///
+pub enum HashEntry<'a, K, V, S> {
+  /// A vacant entry.
+  Vacant(HashVacantEntry<'a, K, V, S>),
+  /// An occupied entry.
+  Occupied(HashOccupiedEntry<'a, K, V, S>),
+}
+
+/// `HashVacantEntry` is a view into a vacant entry of a [`MiniHashMap`], part of the [`HashEntry`] enum.
+///
+pub struct HashVacantEntry<'a, K, V, S> {
+  map: &'a mut MiniHashMap<K, V, S>,
+  hash: u64,
+  key: K,
+}
+
+/// `HashOccupiedEntry` is a view into an occupied entry of a [`MiniHashMap`], part of the [`HashEntry`] enum.
+///
+pub struct HashOccupiedEntry<'a, K, V, S> {
+  map: &'a mut MiniHashMap<K, V, S>,
+  idx: usize,
+}
+
+/// `HashIter` is an iterator over the entries of a [`MiniHashMap`], in an unspecified order.
+///
+pub struct HashIter<'a, K, V> {
+  table: &'a RawTable<K, V>,
+  idx: usize,
+  remaining: usize,
+}
+
+/// `HashIterMut` is an iterator over the entries of a [`MiniHashMap`] with mutable references to the values, in an
+/// unspecified order.
+///
+pub struct HashIterMut<'a, K, V> {
+  table: &'a RawTable<K, V>,
+  idx: usize,
+  remaining: usize,
+  marker: core::marker::PhantomData<&'a mut V>,
+}
+
+/// `HashIntoIter` is the iterator returned by the `IntoIterator` implementation of [`MiniHashMap`].
+///
+pub struct HashIntoIter<K, V> {
+  table: RawTable<K, V>,
+  idx: usize,
+}
+
+/// `HashDrain` is the iterator returned by [`MiniHashMap::drain`], it removes all of the entries from the map even if
+/// it isn't fully consumed.
+///
+pub struct HashDrain<'a, K, V> {
+  table: &'a mut RawTable<K, V>,
+  idx: usize,
+}
+
+impl<K, V> RawTable<K, V> {
+  const fn new() -> Self {
+    let p = core::ptr::addr_of!(EMPTY_TABLE_HEADER)
+      .cast::<u8>()
+      .cast_mut();
+
+    RawTable {
+      buf: unsafe { core::ptr::NonNull::new_unchecked(p) },
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  // `cap` must be a power of two
+  //
+  fn try_with_capacity(cap: usize) -> Result<Self, TryReserveError> {
+    if cap == 0 {
+      return Ok(Self::new());
+    }
+
+    let (layout, ctrl) = Self::layout(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+    let buf = Global
+      .allocate(layout)
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    unsafe {
+      #[allow(clippy::cast_ptr_alignment)]
+      core::ptr::write(
+        buf.as_ptr().cast::<TableHeader>(),
+        TableHeader {
+          len: 0,
+          cap,
+          tombstones: 0,
+        },
+      );
+
+      core::ptr::write_bytes(buf.as_ptr().add(ctrl), EMPTY, cap);
+    }
+
+    Ok(RawTable {
+      buf,
+      phantom: core::marker::PhantomData,
+    })
+  }
+
+  const SLOTS: usize = prefixed_data_offset::<TableHeader, (K, V)>();
+
+  // returns the layout of a table of `cap` slots along with the offset of its control bytes, `None` when it'd be too
+  // large to be described by a `Layout`
+  //
+  fn layout(cap: usize) -> Option<(alloc::alloc::Layout, usize)> {
+    let slots = make_prefixed_layout::<TableHeader, (K, V)>(cap)?;
+    slots
+      .extend(alloc::alloc::Layout::array::<u8>(cap).ok()?)
+      .ok()
+  }
+
+  // the slots sit at a fixed offset so only the control bytes move with the capacity
+  //
+  fn ctrl_ptr(&self) -> *mut u8 {
+    unsafe {
+      self
+        .buf
+        .as_ptr()
+        .add(Self::SLOTS + self.cap() * core::mem::size_of::<(K, V)>())
+    }
+  }
+
+  fn header(&self) -> &TableHeader {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<TableHeader>()
+    }
+  }
+
+  // must never be called on the shared empty header
+  //
+  fn header_mut(&mut self) -> &mut TableHeader {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &mut *self.buf.as_ptr().cast::<TableHeader>()
+    }
+  }
+
+  fn len(&self) -> usize {
+    self.header().len
+  }
+
+  fn cap(&self) -> usize {
+    self.header().cap
+  }
+
+  fn ctrl(&self, idx: usize) -> u8 {
+    debug_assert!(idx < self.cap());
+    unsafe { *self.ctrl_ptr().add(idx) }
+  }
+
+  fn set_ctrl(&mut self, idx: usize, ctrl: u8) {
+    debug_assert!(idx < self.cap());
+    unsafe { *self.ctrl_ptr().add(idx) = ctrl };
+  }
+
+  fn is_full(&self, idx: usize) -> bool {
+    self.ctrl(idx) & 0x80 == 0
+  }
+
+  fn slot(&self, idx: usize) -> *mut (K, V) {
+    debug_assert!(idx < self.cap());
+
+    unsafe { self.buf.as_ptr().add(Self::SLOTS).cast::<(K, V)>().add(idx) }
+  }
+
+  // the index of the next occupied slot at or after `idx`
+  //
+  fn next_full(&self, mut idx: usize) -> Option<usize> {
+    while idx < self.cap() {
+      if self.is_full(idx) {
+        return Some(idx);
+      }
+
+      idx += 1;
+    }
+
+    None
+  }
+
+  // marks the slot as free without dropping its entry, a tombstone is only needed if a probe sequence could run
+  // through the slot
+  //
+  fn erase(&mut self, idx: usize) {
+    let next = (idx + 1) & (self.cap() - 1);
+
+    if self.ctrl(next) == EMPTY {
+      self.set_ctrl(idx, EMPTY);
+    } else {
+      self.set_ctrl(idx, DELETED);
+      self.header_mut().tombstones += 1;
+    }
+
+    self.header_mut().len -= 1;
+  }
+
+  fn clear(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    // the table is emptied first so that a panicking destructor leaks the remaining entries instead of dropping them
+    // twice
+    //
+    let cap = self.cap();
+    let mut remaining = self.len();
+    let mut idx = 0;
+
+    let header = self.header_mut();
+    header.len = 0;
+    header.tombstones = 0;
+
+    while remaining > 0 {
+      if self.is_full(idx) {
+        self.set_ctrl(idx, EMPTY);
+        unsafe { core::ptr::drop_in_place(self.slot(idx)) };
+        remaining -= 1;
+      }
+
+      idx += 1;
+    }
+
+    unsafe { core::ptr::write_bytes(self.ctrl_ptr(), EMPTY, cap) };
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(
+      self.buf.as_ptr(),
+      core::ptr::addr_of!(EMPTY_TABLE_HEADER).cast::<u8>(),
+    )
+  }
+}
+
+impl<K, V> Drop for RawTable<K, V> {
+  fn drop(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    if core::mem::needs_drop::<(K, V)>() {
+      let mut idx = 0;
+      while let Some(i) = self.next_full(idx) {
+        unsafe { core::ptr::drop_in_place(self.slot(i)) };
+        idx = i + 1;
+      }
+    }
+
+    unsafe {
+      let (layout, _) = Self::layout(self.cap()).unwrap_unchecked();
+      Global.deallocate(self.buf, layout);
+    }
+  }
+}
+
+// only used for the control bytes, the top bit is reserved for `EMPTY` and `DELETED`
+//
+fn h2(hash: u64) -> u8 {
+  #[allow(clippy::cast_possible_truncation)]
+  let h2 = (hash >> 57) as u8;
+  h2
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn h1(hash: u64, cap: usize) -> usize {
+  (hash as usize) & (cap - 1)
+}
+
+// the number of entries and tombstones a table of `cap` slots can hold before it has to grow, keeping at least an
+// eighth of it empty so that every probe sequence ends
+//
+fn max_load(cap: usize) -> usize {
+  if cap < 8 {
+    cap.saturating_sub(1)
+  } else {
+    cap - cap / 8
+  }
+}
+
+fn capacity_for(len: usize) -> Option<usize> {
+  if len < 8 {
+    return Some(if len < 4 { 4 } else { 8 });
+  }
+
+  len
+    .checked_mul(8)?
+    .checked_div(7)?
+    .checked_add(1)?
+    .checked_next_power_of_two()
+}
+
+impl<K, V, S> MiniHashMap<K, V, S> {
+  /// `capacity` returns the number of entries the map can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    max_load(self.table.cap())
+  }
+
+  /// `clear` removes all of the entries while keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.table.clear();
+  }
+
+  /// `drain` removes all of the entries from the map, returning them through an iterator. The allocation is kept.
+  ///
+  pub fn drain(&mut self) -> HashDrain<'_, K, V> {
+    HashDrain {
+      table: &mut self.table,
+      idx: 0,
+    }
+  }
+
+  /// `hasher` returns a reference to the map's `BuildHasher`.
+  ///
+  #[must_use]
+  pub fn hasher(&self) -> &S {
+    &self.hash_builder
+  }
+
+  /// `is_empty` returns whether or not the map contains any entries.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `iter` returns an iterator over the entries, in an unspecified order.
+  ///
+  #[must_use]
+  pub fn iter(&self) -> HashIter<'_, K, V> {
+    HashIter {
+      table: &self.table,
+      idx: 0,
+      remaining: self.len(),
+    }
+  }
+
+  /// `iter_mut` returns an iterator over the entries with mutable references to the values, in an unspecified order.
+  ///
+  pub fn iter_mut(&mut self) -> HashIterMut<'_, K, V> {
+    HashIterMut {
+      table: &self.table,
+      idx: 0,
+      remaining: self.len(),
+      marker: core::marker::PhantomData,
+    }
+  }
+
+  /// `keys` returns an iterator over the keys, in an unspecified order.
+  ///
+  pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> {
+    self.iter().map(|(k, _)| k)
+  }
+
+  /// `len` returns the number of entries in the map.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.table.len()
+  }
+
+  /// `retain` keeps only the entries for which `f` returns `true`.
+  ///
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    let mut idx = 0;
+    while let Some(i) = self.table.next_full(idx) {
+      let (k, v) = unsafe { &mut *self.table.slot(i) };
+      if !f(k, v) {
+        self.table.erase(i);
+        unsafe { core::ptr::drop_in_place(self.table.slot(i)) };
+      }
+
+      idx = i + 1;
+    }
+  }
+
+  /// `values` returns an iterator over the values, in an unspecified order.
+  ///
+  pub fn values(&self) -> impl ExactSizeIterator<Item = &V> {
+    self.iter().map(|(_, v)| v)
+  }
+
+  /// `values_mut` returns an iterator over mutable references to the values, in an unspecified order.
+  ///
+  pub fn values_mut(&mut self) -> impl ExactSizeIterator<Item = &mut V> {
+    self.iter_mut().map(|(_, v)| v)
+  }
+
+  /// `with_hasher` creates an empty map which hashes its keys with `hash_builder`. It does not allocate.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::hash::BuildHasherDefault;
+  /// use std::collections::hash_map::DefaultHasher;
+  ///
+  /// let map = minivec::MiniHashMap::<u32, u32, _>::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+  /// assert_eq!(core::mem::size_of_val(&map), core::mem::size_of::<usize>());
+  /// ```
+  ///
+  pub const fn with_hasher(hash_builder: S) -> MiniHashMap<K, V, S> {
+    MiniHashMap {
+      table: RawTable::new(),
+      hash_builder,
+    }
+  }
+}
+
+impl<K, V, S: Default> MiniHashMap<K, V, S> {
+  /// `new` creates an empty map using the default value of its `BuildHasher`. It does not allocate.
+  ///
+  #[must_use]
+  pub fn new() -> MiniHashMap<K, V, S> {
+    Self::with_hasher(S::default())
+  }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> MiniHashMap<K, V, S> {
+  /// `contains_key` returns whether or not the map holds an entry for `key`.
+  ///
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    self.find(key).is_some()
+  }
+
+  /// `entry` returns the entry for `key`, for in-place manipulation. Room for one more entry is reserved up-front so
+  /// that inserting into a vacant entry never rehashes the map.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut counts = minivec::MiniHashMap::<_, _, std::collections::hash_map::RandomState>::new();
+  /// for word in "a b a c a".split(' ') {
+  ///   *counts.entry(word).or_insert(0) += 1;
+  /// }
+  ///
+  /// assert_eq!(counts["a"], 3);
+  /// assert_eq!(counts.len(), 3);
+  /// ```
+  ///
+  pub fn entry(&mut self, key: K) -> HashEntry<'_, K, V, S> {
+    let hash = self.make_hash(&key);
+
+    if let Some(idx) = self.find_hashed(hash, &key) {
+      return HashEntry::Occupied(HashOccupiedEntry { map: self, idx });
+    }
+
+    self.reserve(1);
+    HashEntry::Vacant(HashVacantEntry {
+      map: self,
+      hash,
+      key,
+    })
+  }
+
+  /// `get` returns a reference to the value for `key`, should it exist.
+  ///
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    self.get_key_value(key).map(|(_, v)| v)
+  }
+
+  /// `get_key_value` returns references to the key and value of the entry for `key`, should it exist.
+  ///
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    let idx = self.find(key)?;
+    let (k, v) = unsafe { &*self.table.slot(idx) };
+    Some((k, v))
+  }
+
+  /// `get_mut` returns a mutable reference to the value for `key`, should it exist.
+  ///
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    let idx = self.find(key)?;
+    Some(unsafe { &mut (*self.table.slot(idx)).1 })
+  }
+
+  /// `insert` inserts `value` for `key`, returning the value it replaced, should one exist. The key itself isn't
+  /// updated when it's already present.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    match self.entry(key) {
+      HashEntry::Occupied(mut entry) => Some(entry.insert(value)),
+      HashEntry::Vacant(entry) => {
+        entry.insert(value);
+        None
+      }
+    }
+  }
+
+  /// `remove` removes the entry for `key`, returning its value should it exist.
+  ///
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, v)| v)
+  }
+
+  /// `remove_entry` removes the entry for `key`, returning its key and value should it exist.
+  ///
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    let idx = self.find(key)?;
+    self.table.erase(idx);
+    Some(unsafe { core::ptr::read(self.table.slot(idx)) })
+  }
+
+  /// `reserve` ensures that the map can hold at least `additional` more entries without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    handle_reserve(self.try_reserve(additional));
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniHashMap::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    let required = self
+      .len()
+      .checked_add(additional)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    let cap = self.table.cap();
+    let occupied = required
+      .checked_add(self.table.header().tombstones)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    if occupied <= max_load(cap) {
+      return Ok(());
+    }
+
+    // a table that's mostly tombstones is rehashed at the same size instead of growing
+    //
+    let new_cap = if required <= max_load(cap) / 2 {
+      cap
+    } else {
+      capacity_for(required).ok_or(TryReserveErrorKind::CapacityOverflow)?
+    };
+
+    self.resize(new_cap)
+  }
+
+  /// `with_capacity_and_hasher` creates an empty map which can hold at least `capacity` entries without reallocating
+  /// and hashes its keys with `hash_builder`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows.
+  ///
+  pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> MiniHashMap<K, V, S> {
+    let mut map = Self::with_hasher(hash_builder);
+    map.reserve(capacity);
+    map
+  }
+
+  fn resize(&mut self, cap: usize) -> Result<(), TryReserveError> {
+    let mut table = RawTable::<K, V>::try_with_capacity(cap)?;
+
+    // each entry leaves the old table before it's placed in the new one so that a panicking `hash()` can't lead to an
+    // entry being dropped twice
+    //
+    let mut idx = 0;
+    while let Some(i) = self.table.next_full(idx) {
+      let hash = self.make_hash(unsafe { &(*self.table.slot(i)).0 });
+
+      self.table.set_ctrl(i, EMPTY);
+      self.table.header_mut().len -= 1;
+
+      let slot = Self::find_insert_slot(&table, hash);
+      table.set_ctrl(slot, h2(hash));
+      table.header_mut().len += 1;
+      unsafe { core::ptr::copy_nonoverlapping(self.table.slot(i), table.slot(slot), 1) };
+
+      idx = i + 1;
+    }
+
+    self.table = table;
+    Ok(())
+  }
+
+  fn find<Q>(&self, key: &Q) -> Option<usize>
+  where
+    K: Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+  {
+    if self.is_empty() {
+      return None;
+    }
+
+    self.find_hashed(self.make_hash(key), key)
+  }
+
+  fn find_hashed<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+  where
+    K: Borrow<Q>,
+    Q: Eq + ?Sized,
+  {
+    let cap = self.table.cap();
+    if cap == 0 {
+      return None;
+    }
+
+    let mut idx = h1(hash, cap);
+    for _ in 0..cap {
+      let ctrl = self.table.ctrl(idx);
+      if ctrl == EMPTY {
+        return None;
+      }
+
+      if ctrl == h2(hash) && unsafe { (*self.table.slot(idx)).0.borrow() } == key {
+        return Some(idx);
+      }
+
+      idx = (idx + 1) & (cap - 1);
+    }
+
+    None
+  }
+
+  // the first free slot in the probe sequence of `hash`, there's always one as tables are never completely filled
+  //
+  fn find_insert_slot(table: &RawTable<K, V>, hash: u64) -> usize {
+    let cap = table.cap();
+    let mut idx = h1(hash, cap);
+
+    while table.is_full(idx) {
+      idx = (idx + 1) & (cap - 1);
+    }
+
+    idx
+  }
+
+  fn make_hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+    self.hash_builder.hash_one(key)
+  }
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> HashEntry<'a, K, V, S> {
+  /// `and_modify` calls `f` on the value of an occupied entry before any insertion.
+  ///
+  #[must_use]
+  pub fn and_modify<F>(self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    match self {
+      HashEntry::Occupied(mut entry) => {
+        f(entry.get_mut());
+        HashEntry::Occupied(entry)
+      }
+      HashEntry::Vacant(entry) => HashEntry::Vacant(entry),
+    }
+  }
+
+  /// `key` returns a reference to the key of the entry.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    match self {
+      HashEntry::Occupied(entry) => entry.key(),
+      HashEntry::Vacant(entry) => entry.key(),
+    }
+  }
+
+  /// `or_default` inserts the default value into a vacant entry, returning a mutable reference to the entry's value.
+  ///
+  pub fn or_default(self) -> &'a mut V
+  where
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+
+  /// `or_insert` inserts `default` into a vacant entry, returning a mutable reference to the entry's value.
+  ///
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    self.or_insert_with(|| default)
+  }
+
+  /// `or_insert_with` inserts the result of `default` into a vacant entry, returning a mutable reference to the entry's
+  /// value.
+  ///
+  pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+  where
+    F: FnOnce() -> V,
+  {
+    match self {
+      HashEntry::Occupied(entry) => entry.into_mut(),
+      HashEntry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> HashVacantEntry<'a, K, V, S> {
+  /// `insert` inserts `value` for the entry's key, returning a mutable reference to it.
+  ///
+  pub fn insert(self, value: V) -> &'a mut V {
+    let table = &mut self.map.table;
+    let idx = MiniHashMap::<K, V, S>::find_insert_slot(table, self.hash);
+
+    if table.ctrl(idx) == DELETED {
+      table.header_mut().tombstones -= 1;
+    }
+
+    unsafe { core::ptr::write(table.slot(idx), (self.key, value)) };
+    table.set_ctrl(idx, h2(self.hash));
+    table.header_mut().len += 1;
+
+    unsafe { &mut (*table.slot(idx)).1 }
+  }
+
+  /// `into_key` returns the key which would have been inserted.
+  ///
+  #[must_use]
+  pub fn into_key(self) -> K {
+    self.key
+  }
+
+  /// `key` returns a reference to the key which would be inserted.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+}
+
+impl<'a, K: Hash + Eq, V, S: BuildHasher> HashOccupiedEntry<'a, K, V, S> {
+  /// `get` returns a reference to the value of the entry.
+  ///
+  #[must_use]
+  pub fn get(&self) -> &V {
+    unsafe { &(*self.map.table.slot(self.idx)).1 }
+  }
+
+  /// `get_mut` returns a mutable reference to the value of the entry.
+  ///
+  pub fn get_mut(&mut self) -> &mut V {
+    unsafe { &mut (*self.map.table.slot(self.idx)).1 }
+  }
+
+  /// `insert` replaces the value of the entry with `value`, returning the old one.
+  ///
+  pub fn insert(&mut self, value: V) -> V {
+    core::mem::replace(self.get_mut(), value)
+  }
+
+  /// `into_mut` converts the entry into a mutable reference to its value, bound to the lifetime of the map.
+  ///
+  #[must_use]
+  pub fn into_mut(self) -> &'a mut V {
+    unsafe { &mut (*self.map.table.slot(self.idx)).1 }
+  }
+
+  /// `key` returns a reference to the key of the entry.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    unsafe { &(*self.map.table.slot(self.idx)).0 }
+  }
+
+  /// `remove` removes the entry from the map, returning its value.
+  ///
+  #[must_use]
+  pub fn remove(self) -> V {
+    self.remove_entry().1
+  }
+
+  /// `remove_entry` removes the entry from the map, returning its key and value.
+  ///
+  #[must_use]
+  pub fn remove_entry(self) -> (K, V) {
+    self.map.table.erase(self.idx);
+    unsafe { core::ptr::read(self.map.table.slot(self.idx)) }
+  }
+}
+
+impl<'a, K, V> Iterator for HashIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let idx = self.table.next_full(self.idx)?;
+    self.idx = idx + 1;
+    self.remaining -= 1;
+
+    let (k, v) = unsafe { &*self.table.slot(idx) };
+    Some((k, v))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl<K, V> ExactSizeIterator for HashIter<'_, K, V> {}
+
+impl<K, V> core::iter::FusedIterator for HashIter<'_, K, V> {}
+
+impl<K, V> Clone for HashIter<'_, K, V> {
+  fn clone(&self) -> Self {
+    HashIter {
+      table: self.table,
+      idx: self.idx,
+      remaining: self.remaining,
+    }
+  }
+}
+
+impl<'a, K, V> Iterator for HashIterMut<'a, K, V> {
+  type Item = (&'a K, &'a mut V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.remaining == 0 {
+      return None;
+    }
+
+    let idx = self.table.next_full(self.idx)?;
+    self.idx = idx + 1;
+    self.remaining -= 1;
+
+    let (k, v) = unsafe { &mut *self.table.slot(idx) };
+    Some((&*k, v))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.remaining, Some(self.remaining))
+  }
+}
+
+impl<K, V> ExactSizeIterator for HashIterMut<'_, K, V> {}
+
+impl<K, V> core::iter::FusedIterator for HashIterMut<'_, K, V> {}
+
+impl<K, V> Iterator for HashIntoIter<K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<(K, V)> {
+    if self.table.len() == 0 {
+      return None;
+    }
+
+    let idx = self.table.next_full(self.idx)?;
+    self.idx = idx + 1;
+
+    // the slot is given up before the entry is moved out so the table won't drop it again
+    //
+    self.table.set_ctrl(idx, EMPTY);
+    self.table.header_mut().len -= 1;
+
+    Some(unsafe { core::ptr::read(self.table.slot(idx)) })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.table.len(), Some(self.table.len()))
+  }
+}
+
+impl<K, V> ExactSizeIterator for HashIntoIter<K, V> {}
+
+impl<K, V> core::iter::FusedIterator for HashIntoIter<K, V> {}
+
+impl<K, V> Iterator for HashDrain<'_, K, V> {
+  type Item = (K, V);
+
+  fn next(&mut self) -> Option<(K, V)> {
+    if self.table.len() == 0 {
+      return None;
+    }
+
+    let idx = self.table.next_full(self.idx)?;
+    self.idx = idx + 1;
+
+    // the slot may be part of the probe sequence of an entry that's yet to be drained so it's erased like it would be
+    // by `remove`, keeping the table usable should the drain be leaked
+    //
+    self.table.erase(idx);
+
+    Some(unsafe { core::ptr::read(self.table.slot(idx)) })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.table.len(), Some(self.table.len()))
+  }
+}
+
+impl<K, V> ExactSizeIterator for HashDrain<'_, K, V> {}
+
+impl<K, V> core::iter::FusedIterator for HashDrain<'_, K, V> {}
+
+impl<K, V> Drop for HashDrain<'_, K, V> {
+  fn drop(&mut self) {
+    // the remaining entries are dropped and the tombstones cleared along with them
+    //
+    self.table.clear();
+  }
+}
+
+impl<K: Clone, V: Clone, S: Clone> Clone for MiniHashMap<K, V, S> {
+  fn clone(&self) -> Self {
+    let cap = self.table.cap();
+    let mut table = handle_reserve(RawTable::<K, V>::try_with_capacity(cap));
+
+    // the tombstones are copied over first as the probe sequences of the entries may run through them, the entries
+    // are then added one by one so that a panicking `clone()` only drops the finished ones
+    //
+    for idx in 0..cap {
+      if self.table.ctrl(idx) == DELETED {
+        table.set_ctrl(idx, DELETED);
+        table.header_mut().tombstones += 1;
+      }
+    }
+
+    let mut idx = 0;
+    while let Some(i) = self.table.next_full(idx) {
+      let entry = unsafe { (*self.table.slot(i)).clone() };
+      unsafe { core::ptr::write(table.slot(i), entry) };
+      table.set_ctrl(i, self.table.ctrl(i));
+      table.header_mut().len += 1;
+
+      idx = i + 1;
+    }
+
+    MiniHashMap {
+      table,
+      hash_builder: self.hash_builder.clone(),
+    }
+  }
+}
+
+impl<K: core::fmt::Debug, V: core::fmt::Debug, S> core::fmt::Debug for MiniHashMap<K, V, S> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V, S: Default> Default for MiniHashMap<K, V, S> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for MiniHashMap<K, V, S> {}
+
+impl<K: Hash + Eq, V: PartialEq, S: BuildHasher> PartialEq for MiniHashMap<K, V, S> {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+  }
+}
+
+impl<K, Q, V, S> core::ops::Index<&Q> for MiniHashMap<K, V, S>
+where
+  K: Borrow<Q> + Hash + Eq,
+  Q: Hash + Eq + ?Sized,
+  S: BuildHasher,
+{
+  type Output = V;
+
+  fn index(&self, key: &Q) -> &V {
+    match self.get(key) {
+      Some(v) => v,
+      None => panic!("key not found in map"),
+    }
+  }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for MiniHashMap<K, V, S> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+
+    for (k, v) in iter {
+      self.insert(k, v);
+    }
+  }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> core::iter::FromIterator<(K, V)>
+  for MiniHashMap<K, V, S>
+{
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    let mut map = Self::new();
+    map.extend(iter);
+    map
+  }
+}
+
+impl<K, V, S> IntoIterator for MiniHashMap<K, V, S> {
+  type Item = (K, V);
+  type IntoIter = HashIntoIter<K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    HashIntoIter {
+      table: self.table,
+      idx: 0,
+    }
+  }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a MiniHashMap<K, V, S> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = HashIter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut MiniHashMap<K, V, S> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = HashIterMut<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+unsafe impl<K: Send, V: Send> Send for RawTable<K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for RawTable<K, V> {}
+unsafe impl<K: Send, V: Send> Send for HashIterMut<'_, K, V> {}
+unsafe impl<K: Sync, V: Sync> Sync for HashIterMut<'_, K, V> {}
@@ -0,0 +1,735 @@
+use crate::r#impl::helpers::{
+  header_bytes, make_aligned_layout, max_align, max_aligned_elems, next_aligned,
+};
+use crate::{handle_reserve, Doubling, Global, GrowthPolicy, Header, MiniVec};
+use crate::{Allocator, TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+// the allocation begins like the one of a `MiniVec<T>` holding every element so that `flatten` can hand it back as
+// is, the table of sequences follows the elements:
+//
+// | Header { len, cap } | elements ... | SeqHeader { len, cap } | ends ... |
+//
+// `ends[i]` is the index one past the last element of the `i`th sequence, which begins where the previous one ends
+//
+#[repr(C)]
+struct SeqHeader {
+  len: usize,
+  cap: usize,
+}
+
+/// `MiniJagged` is a vector of variable-length sequences which stores all of their elements contiguously, along with
+/// the table of where each sequence ends, in a single allocation. It's only the size of a single pointer, and unlike a
+/// `MiniVec<MiniVec<T>>` it doesn't allocate once per sequence.
+///
+/// The elements are laid out exactly like those of a `MiniVec<T>` so [`flatten`](MiniJagged::flatten) returns them
+/// without moving them.
+///
+/// The elements and the table of sequences grow independently of one another, see
+/// [`GrowthPolicy`](crate::GrowthPolicy).
+///
+/// # Example
+///
+/// ```
+/// let mut lines = minivec::MiniJagged::new();
+/// lines.push_seq(b"hello");
+/// lines.push_seq(b"");
+/// lines.push_seq(b"world");
+/// lines.extend_last(*b"!!");
+///
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines.get(2), Some(&b"world!!"[..]));
+/// assert_eq!(lines.flatten(), b"helloworld!!");
+/// ```
+///
+pub struct MiniJagged<T> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<T>,
+}
+
+/// `JaggedIter` is an iterator over the sequences of a [`MiniJagged`], returned by [`MiniJagged::iter`].
+///
+#[derive(Clone)]
+pub struct JaggedIter<'a, T> {
+  jagged: &'a MiniJagged<T>,
+  idx: usize,
+  end: usize,
+}
+
+impl<T> MiniJagged<T> {
+  const DATA: usize = header_bytes::<T, Global, usize>();
+  const ALIGN: usize = max_align::<T, Global, usize>();
+
+  /// `as_flat_slice` returns every element, sequence after sequence.
+  ///
+  #[must_use]
+  pub fn as_flat_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.data(), self.flat_len()) }
+  }
+
+  /// `clear` removes all of the sequences, keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    // the table is emptied first so that a panicking destructor leaks the remaining elements instead of dropping them
+    // twice
+    //
+    let len = self.flat_len();
+    self.header_mut().len = 0;
+    self.seq_header_mut().len = 0;
+
+    unsafe { core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.data(), len)) };
+  }
+
+  /// `extend_last` appends the elements of `iter` to the last sequence.
+  ///
+  /// # Panics
+  ///
+  /// Panics if there are no sequences or if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut words = minivec::MiniJagged::new();
+  /// words.push_seq(&['a']);
+  /// words.extend_last("bc".chars());
+  ///
+  /// assert_eq!(words[0], ['a', 'b', 'c']);
+  /// ```
+  ///
+  pub fn extend_last<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    assert!(!self.is_empty(), "extend_last called without any sequences");
+
+    let iter = iter.into_iter();
+    self.reserve(0, iter.size_hint().0);
+
+    for x in iter {
+      self.reserve(0, 1);
+
+      // the sequence is only extended once the element is in place
+      //
+      let len = self.flat_len();
+      unsafe { core::ptr::write(self.data().add(len), x) };
+
+      self.header_mut().len = len + 1;
+      let last = self.len() - 1;
+      self.ends_mut()[last] = len + 1;
+    }
+  }
+
+  /// `flat_len` returns the total number of elements across all of the sequences.
+  ///
+  #[must_use]
+  pub fn flat_len(&self) -> usize {
+    self.header().len
+  }
+
+  /// `flatten` turns the jagged vector into a `MiniVec` of its elements, sequence after sequence. The allocation is
+  /// shrunk to drop the table of sequences and handed back to the vector.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let jagged: minivec::MiniJagged<i32> = vec![vec![1, 2], vec![], vec![3]].into_iter().collect();
+  /// let ptr = jagged.as_flat_slice().as_ptr();
+  ///
+  /// let vec = jagged.flatten();
+  /// assert_eq!(vec, [1, 2, 3]);
+  /// assert_eq!(vec.as_ptr(), ptr);
+  /// ```
+  ///
+  #[must_use]
+  pub fn flatten(self) -> MiniVec<T> {
+    let this = core::mem::ManuallyDrop::new(self);
+    if this.is_default() {
+      return MiniVec::new();
+    }
+
+    let Header { len, cap } = *this.header();
+    let old_layout = Self::layout(cap, this.seq_header().cap);
+
+    if cap == 0 {
+      unsafe { Global.deallocate(this.buf, old_layout) };
+      return MiniVec::new();
+    }
+
+    // the vector's allocation is never larger than ours and, sharing its alignment, only ever requires shrinking it
+    //
+    let new_layout =
+      unsafe { make_aligned_layout::<T, Global, usize>(cap, Self::ALIGN).unwrap_unchecked() };
+    let buf = match unsafe { Global.shrink(this.buf, old_layout, new_layout) } {
+      Ok(p) => p.cast::<u8>(),
+      Err(_) => alloc::alloc::handle_alloc_error(new_layout),
+    };
+
+    unsafe { MiniVec::<T>::write_header(buf.as_ptr(), len, MiniVec::<T>::stored_capacity(cap)) };
+    MiniVec::from_buf(buf)
+  }
+
+  /// `get` returns the sequence at `index`, `None` if it's out of bounds.
+  ///
+  #[must_use]
+  pub fn get(&self, index: usize) -> Option<&[T]> {
+    let (start, end) = self.bounds(index)?;
+    Some(&self.as_flat_slice()[start..end])
+  }
+
+  /// `get_mut` returns the sequence at `index` as a mutable slice, `None` if it's out of bounds.
+  ///
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut [T]> {
+    let (start, end) = self.bounds(index)?;
+    Some(unsafe { core::slice::from_raw_parts_mut(self.data().add(start), end - start) })
+  }
+
+  /// `is_empty` returns whether or not the jagged vector contains any sequences.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `iter` returns an iterator over the sequences as slices.
+  ///
+  #[must_use]
+  pub fn iter(&self) -> JaggedIter<'_, T> {
+    JaggedIter {
+      jagged: self,
+      idx: 0,
+      end: self.len(),
+    }
+  }
+
+  /// `len` returns the number of sequences.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    if self.is_default() {
+      0
+    } else {
+      self.seq_header().len
+    }
+  }
+
+  /// `new` creates an empty jagged vector. It does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniJagged<T> {
+    MiniJagged {
+      buf: MiniVec::<T>::empty_buf(),
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `pop` removes the last sequence and returns its elements, `None` if there are no sequences.
+  ///
+  pub fn pop(&mut self) -> Option<MiniVec<T>> {
+    let len = self.len();
+    if len == 0 {
+      return None;
+    }
+
+    Some(self.remove(len - 1))
+  }
+
+  /// `push_seq` appends a copy of `seq` as the last sequence.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn push_seq(&mut self, seq: &[T])
+  where
+    T: Clone,
+  {
+    self.reserve(1, seq.len());
+    self.push_empty();
+    self.extend_last(seq.iter().cloned());
+  }
+
+  /// `remove` removes the sequence at `index` and returns its elements, shifting the elements of the sequences after it
+  /// down.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `index >= len()`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut jagged: minivec::MiniJagged<i32> = vec![vec![1], vec![2, 3], vec![4]].into_iter().collect();
+  ///
+  /// assert_eq!(jagged.remove(1), [2, 3]);
+  /// assert_eq!(jagged.iter().collect::<Vec<_>>(), [&[1][..], &[4][..]]);
+  /// ```
+  ///
+  pub fn remove(&mut self, index: usize) -> MiniVec<T> {
+    let len = self.len();
+
+    assert!(
+      (index < len),
+      "removal index (is {}) should be < len (is {})",
+      index,
+      len
+    );
+
+    let (start, end) = unsafe { self.bounds(index).unwrap_unchecked() };
+    let n = end - start;
+    let flat_len = self.flat_len();
+
+    let mut seq = MiniVec::with_capacity(n);
+
+    unsafe {
+      let data = self.data();
+      core::ptr::copy_nonoverlapping(data.add(start), seq.as_mut_ptr(), n);
+      core::ptr::copy(data.add(end), data.add(start), flat_len - end);
+      seq.set_len(n);
+    }
+
+    let ends = self.ends_mut();
+    ends.copy_within(index + 1.., index);
+    for end in &mut ends[index..len - 1] {
+      *end -= n;
+    }
+
+    self.header_mut().len = flat_len - n;
+    self.seq_header_mut().len = len - 1;
+
+    seq
+  }
+
+  /// `reserve` ensures that the jagged vector can hold at least `additional_seqs` more sequences and
+  /// `additional_elements` more elements without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn reserve(&mut self, additional_seqs: usize, additional_elements: usize) {
+    handle_reserve(self.try_reserve(additional_seqs, additional_elements));
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniJagged::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(
+    &mut self,
+    additional_seqs: usize,
+    additional_elements: usize,
+  ) -> Result<(), TryReserveError> {
+    let (cap, seq_cap) = if self.is_default() {
+      (0, 0)
+    } else {
+      (self.header().cap, self.seq_header().cap)
+    };
+
+    let required_seqs = self
+      .len()
+      .checked_add(additional_seqs)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    let required = self
+      .flat_len()
+      .checked_add(additional_elements)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    if required <= cap && required_seqs <= seq_cap {
+      return Ok(());
+    }
+
+    let max_cap = max_aligned_elems::<T, Global, usize>(Self::ALIGN);
+    if required > max_cap {
+      return Err(From::from(TryReserveErrorKind::CapacityOverflow));
+    }
+
+    let grows = |size: usize, current: usize, required: usize| {
+      if required <= current {
+        current
+      } else {
+        core::cmp::max(Doubling::next_capacity(size, current, required), required)
+      }
+    };
+
+    // a vector of ZSTs only ever needs its header so its capacity is always the maximum one
+    //
+    let new_cap = if core::mem::size_of::<T>() == 0 {
+      max_cap
+    } else {
+      core::cmp::min(grows(core::mem::size_of::<T>(), cap, required), max_cap)
+    };
+    let new_seq_cap = grows(core::mem::size_of::<usize>(), seq_cap, required_seqs);
+
+    self.grow(new_cap, new_seq_cap)
+  }
+
+  /// `with_capacity` creates an empty jagged vector which can hold at least `seqs` sequences and `elements` elements
+  /// without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows.
+  ///
+  #[must_use]
+  pub fn with_capacity(seqs: usize, elements: usize) -> MiniJagged<T> {
+    let mut jagged = Self::new();
+    if seqs > 0 || elements > 0 {
+      jagged.reserve(seqs, elements);
+    }
+
+    jagged
+  }
+
+  fn grow(&mut self, cap: usize, seq_cap: usize) -> Result<(), TryReserveError> {
+    let new_tail = Self::tail_offset(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+    let layout = new_tail
+      .checked_add(core::mem::size_of::<SeqHeader>())
+      .and_then(|n| n.checked_add(seq_cap.checked_mul(core::mem::size_of::<usize>())?))
+      .and_then(|n| alloc::alloc::Layout::from_size_align(n, Self::ALIGN).ok())
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    if self.is_default() {
+      let buf = Global
+        .allocate(layout)
+        .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+        .cast::<u8>();
+
+      unsafe {
+        MiniVec::<T>::write_header(buf.as_ptr(), 0, cap);
+
+        #[allow(clippy::cast_ptr_alignment)]
+        core::ptr::write(
+          buf.as_ptr().add(new_tail).cast::<SeqHeader>(),
+          SeqHeader {
+            len: 0,
+            cap: seq_cap,
+          },
+        );
+      }
+
+      self.buf = buf;
+      return Ok(());
+    }
+
+    let Header { len, cap: old_cap } = *self.header();
+    let SeqHeader {
+      len: seqs,
+      cap: old_seq_cap,
+    } = *self.seq_header();
+    let old_tail = unsafe { Self::tail_offset(old_cap).unwrap_unchecked() };
+    let old_layout = Self::layout(old_cap, old_seq_cap);
+
+    let buf = unsafe { Global.grow(self.buf, old_layout, layout) }
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    // the table of sequences is moved up past the new end of the elements
+    //
+    unsafe {
+      let p = buf.as_ptr();
+      core::ptr::copy(
+        p.add(old_tail + core::mem::size_of::<SeqHeader>()),
+        p.add(new_tail + core::mem::size_of::<SeqHeader>()),
+        seqs * core::mem::size_of::<usize>(),
+      );
+
+      MiniVec::<T>::write_header(p, len, cap);
+
+      #[allow(clippy::cast_ptr_alignment)]
+      core::ptr::write(
+        p.add(new_tail).cast::<SeqHeader>(),
+        SeqHeader {
+          len: seqs,
+          cap: seq_cap,
+        },
+      );
+    }
+
+    self.buf = buf;
+    Ok(())
+  }
+
+  // starts a new, empty sequence, there must already be room for it
+  //
+  fn push_empty(&mut self) {
+    let len = self.len();
+    let end = self.flat_len();
+
+    debug_assert!(len < self.seq_header().cap);
+    unsafe { core::ptr::write(self.ends_ptr().add(len), end) };
+    self.seq_header_mut().len = len + 1;
+  }
+
+  fn bounds(&self, index: usize) -> Option<(usize, usize)> {
+    let ends = self.ends();
+    let end = *ends.get(index)?;
+    let start = if index == 0 { 0 } else { ends[index - 1] };
+
+    Some((start, end))
+  }
+
+  // the offset of the table of sequences for a capacity of `cap` elements
+  //
+  fn tail_offset(cap: usize) -> Option<usize> {
+    let elems = cap.checked_mul(core::mem::size_of::<T>())?;
+    let end = Self::DATA.checked_add(elems)?;
+
+    if end > isize::MAX as usize {
+      return None;
+    }
+
+    Some(next_aligned(end, core::mem::align_of::<SeqHeader>()))
+  }
+
+  // the layout of an existing allocation, which is known to be valid
+  //
+  fn layout(cap: usize, seq_cap: usize) -> alloc::alloc::Layout {
+    unsafe {
+      let size = Self::tail_offset(cap).unwrap_unchecked()
+        + core::mem::size_of::<SeqHeader>()
+        + seq_cap * core::mem::size_of::<usize>();
+
+      alloc::alloc::Layout::from_size_align_unchecked(size, Self::ALIGN)
+    }
+  }
+
+  fn header(&self) -> &Header<usize> {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<Header<usize>>()
+    }
+  }
+
+  fn header_mut(&mut self) -> &mut Header<usize> {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &mut *self.buf.as_ptr().cast::<Header<usize>>()
+    }
+  }
+
+  // must never be called on the shared empty header, which has no table
+  //
+  fn seq_header(&self) -> &SeqHeader {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      let tail = Self::tail_offset(self.header().cap).unwrap_unchecked();
+      &*self.buf.as_ptr().add(tail).cast::<SeqHeader>()
+    }
+  }
+
+  fn seq_header_mut(&mut self) -> &mut SeqHeader {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      let tail = Self::tail_offset(self.header().cap).unwrap_unchecked();
+      &mut *self.buf.as_ptr().add(tail).cast::<SeqHeader>()
+    }
+  }
+
+  fn ends_ptr(&self) -> *mut usize {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      let tail = Self::tail_offset(self.header().cap).unwrap_unchecked();
+      self
+        .buf
+        .as_ptr()
+        .add(tail + core::mem::size_of::<SeqHeader>())
+        .cast::<usize>()
+    }
+  }
+
+  fn ends(&self) -> &[usize] {
+    if self.is_default() {
+      return &[];
+    }
+
+    unsafe { core::slice::from_raw_parts(self.ends_ptr(), self.len()) }
+  }
+
+  fn ends_mut(&mut self) -> &mut [usize] {
+    if self.is_default() {
+      return &mut [];
+    }
+
+    unsafe { core::slice::from_raw_parts_mut(self.ends_ptr(), self.len()) }
+  }
+
+  fn data(&self) -> *mut T {
+    if self.is_default() {
+      return core::ptr::NonNull::dangling().as_ptr();
+    }
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      self.buf.as_ptr().add(Self::DATA).cast::<T>()
+    }
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), MiniVec::<T>::empty_buf().as_ptr())
+  }
+}
+
+impl<T> Drop for MiniJagged<T> {
+  fn drop(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    unsafe {
+      core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+        self.data(),
+        self.flat_len(),
+      ));
+
+      let layout = Self::layout(self.header().cap, self.seq_header().cap);
+      Global.deallocate(self.buf, layout);
+    }
+  }
+}
+
+impl<T: Clone> Clone for MiniJagged<T> {
+  fn clone(&self) -> Self {
+    let mut jagged = Self::with_capacity(self.len(), self.flat_len());
+    for seq in self {
+      jagged.push_seq(seq);
+    }
+
+    jagged
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniJagged<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Default for MiniJagged<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniJagged<T> {
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    state.write_usize(self.len());
+    for seq in self {
+      seq.hash(state);
+    }
+  }
+}
+
+impl<T> core::ops::Index<usize> for MiniJagged<T> {
+  type Output = [T];
+
+  fn index(&self, index: usize) -> &[T] {
+    let len = self.len();
+    match self.get(index) {
+      Some(seq) => seq,
+      None => panic!("index (is {}) should be < len (is {})", index, len),
+    }
+  }
+}
+
+impl<T> core::ops::IndexMut<usize> for MiniJagged<T> {
+  fn index_mut(&mut self, index: usize) -> &mut [T] {
+    let len = self.len();
+    match self.get_mut(index) {
+      Some(seq) => seq,
+      None => panic!("index (is {}) should be < len (is {})", index, len),
+    }
+  }
+}
+
+impl<T: Eq> Eq for MiniJagged<T> {}
+
+impl<T: PartialEq> PartialEq for MiniJagged<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.ends() == other.ends() && self.as_flat_slice() == other.as_flat_slice()
+  }
+}
+
+impl<T, I: IntoIterator<Item = T>> Extend<I> for MiniJagged<T> {
+  fn extend<J: IntoIterator<Item = I>>(&mut self, iter: J) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0, 0);
+
+    for seq in iter {
+      self.reserve(1, 0);
+      self.push_empty();
+      self.extend_last(seq);
+    }
+  }
+}
+
+impl<T, I: IntoIterator<Item = T>> core::iter::FromIterator<I> for MiniJagged<T> {
+  fn from_iter<J: IntoIterator<Item = I>>(iter: J) -> Self {
+    let mut jagged = Self::new();
+    jagged.extend(iter);
+    jagged
+  }
+}
+
+impl<T> From<MiniJagged<T>> for MiniVec<T> {
+  fn from(jagged: MiniJagged<T>) -> Self {
+    jagged.flatten()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniJagged<T> {
+  type Item = &'a [T];
+  type IntoIter = JaggedIter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> Iterator for JaggedIter<'a, T> {
+  type Item = &'a [T];
+
+  fn next(&mut self) -> Option<&'a [T]> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    self.idx += 1;
+    self.jagged.get(self.idx - 1)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.end - self.idx;
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for JaggedIter<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    self.end -= 1;
+    self.jagged.get(self.end)
+  }
+}
+
+impl<T> ExactSizeIterator for JaggedIter<'_, T> {}
+
+impl<T> core::iter::FusedIterator for JaggedIter<'_, T> {}
+
+unsafe impl<T: Send> Send for MiniJagged<T> {}
+unsafe impl<T: Sync> Sync for MiniJagged<T> {}
@@ -0,0 +1,732 @@
+use crate::MiniVec;
+
+// the matrix shares the layout of a `MiniVec<T>` so the two convert into each other by rewriting the header, the
+// length slot holding the number of rows in its upper half and the number of columns in its lower half
+//
+const HALF: usize = core::mem::size_of::<usize>() * 4;
+const MAX_DIM: usize = (1 << HALF) - 1;
+
+/// `MiniMatrix` is a dense, row-major 2-D array that's only the size of a single pointer, the number of rows and
+/// columns living in the header of the allocation next to its capacity.
+///
+/// A `MiniMatrix<T>` shares the layout of a [`MiniVec<T>`] so converting between the two never moves the elements,
+/// see [`from_minivec`](MiniMatrix::from_minivec) and [`into_minivec`](MiniMatrix::into_minivec). In exchange, each
+/// dimension is limited to half of the bits of a `usize`, i.e. `u32::MAX` on 64-bit targets.
+///
+/// A matrix with a non-zero dimension always owns an allocation, as that's where its shape is stored.
+///
+/// # Example
+///
+/// ```
+/// let mut m = minivec::MiniMatrix::new();
+/// m.push_row([1, 2, 3]);
+/// m.push_row([4, 5, 6]);
+///
+/// assert_eq!(m.shape(), (2, 3));
+/// assert_eq!(m[(1, 2)], 6);
+///
+/// m.transpose();
+/// assert_eq!(m.shape(), (3, 2));
+/// assert_eq!(m.row(2), [3, 6]);
+/// ```
+///
+pub struct MiniMatrix<T> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<T>,
+}
+
+/// `MatrixRows` is an iterator over the rows of a [`MiniMatrix`], returned by [`MiniMatrix::iter_rows`].
+///
+#[derive(Clone)]
+pub struct MatrixRows<'a, T> {
+  data: &'a [T],
+  cols: usize,
+  rows: usize,
+}
+
+/// `MatrixColumns` is an iterator over the columns of a [`MiniMatrix`], returned by [`MiniMatrix::iter_columns`].
+///
+#[derive(Clone)]
+pub struct MatrixColumns<'a, T> {
+  matrix: &'a MiniMatrix<T>,
+  idx: usize,
+}
+
+impl<T> MiniMatrix<T> {
+  /// `as_mut_slice` returns the elements as a mutable slice, row after row.
+  ///
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    unsafe { core::slice::from_raw_parts_mut(self.data(), self.len()) }
+  }
+
+  /// `as_slice` returns the elements as a slice, row after row.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+  }
+
+  /// `capacity` returns the number of elements the matrix can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.view().capacity()
+  }
+
+  /// `column` returns an iterator over the elements of the column at `col`, from top to bottom.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `col >= cols()`.
+  ///
+  pub fn column(&self, col: usize) -> core::iter::StepBy<core::slice::Iter<'_, T>> {
+    let cols = self.cols();
+
+    assert!(
+      (col < cols),
+      "column index (is {}) should be < cols (is {})",
+      col,
+      cols
+    );
+
+    // a matrix without rows has no elements to skip
+    //
+    let data = self.as_slice();
+    data[core::cmp::min(col, data.len())..].iter().step_by(cols)
+  }
+
+  /// `column_mut` returns an iterator over mutable references to the elements of the column at `col`, from top to
+  /// bottom.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `col >= cols()`.
+  ///
+  pub fn column_mut(&mut self, col: usize) -> core::iter::StepBy<core::slice::IterMut<'_, T>> {
+    let cols = self.cols();
+
+    assert!(
+      (col < cols),
+      "column index (is {}) should be < cols (is {})",
+      col,
+      cols
+    );
+
+    let data = self.as_mut_slice();
+    let start = core::cmp::min(col, data.len());
+    data[start..].iter_mut().step_by(cols)
+  }
+
+  /// `cols` returns the number of columns.
+  ///
+  #[must_use]
+  pub fn cols(&self) -> usize {
+    self.shape().1
+  }
+
+  /// `from_minivec` turns `vec` into a matrix of `rows` rows and `cols` columns, reusing its allocation. The elements
+  /// of `vec` are taken row after row.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `vec.len() != rows * cols` or if either dimension exceeds half of the bits of a `usize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use minivec::{mini_vec, MiniMatrix, MiniVec};
+  ///
+  /// let vec = mini_vec![1, 2, 3, 4, 5, 6];
+  /// let ptr = vec.as_ptr();
+  ///
+  /// let m = MiniMatrix::from_minivec(vec, 3, 2);
+  /// assert_eq!(m.row(1), [3, 4]);
+  /// assert_eq!(m.as_slice().as_ptr(), ptr);
+  ///
+  /// assert_eq!(m.into_minivec().as_ptr(), ptr);
+  /// ```
+  ///
+  #[must_use]
+  pub fn from_minivec(mut vec: MiniVec<T>, rows: usize, cols: usize) -> MiniMatrix<T> {
+    assert!(
+      rows <= MAX_DIM && cols <= MAX_DIM,
+      "matrix dimensions (are {}x{}) should be <= {}",
+      rows,
+      cols,
+      MAX_DIM
+    );
+
+    assert!(
+      (vec.len() == rows * cols),
+      "vector len (is {}) should be rows * cols (is {})",
+      vec.len(),
+      rows * cols
+    );
+
+    if rows == 0 && cols == 0 && vec.is_default() {
+      return Self::new();
+    }
+
+    // an empty matrix still needs somewhere to keep its shape
+    //
+    if vec.is_default() {
+      vec.reserve(1);
+    }
+
+    vec.header_mut().len = (rows << HALF) | cols;
+
+    let vec = core::mem::ManuallyDrop::new(vec);
+    MiniMatrix {
+      buf: vec.buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `get` returns a reference to the element at row `row` and column `col`, `None` if it's out of bounds.
+  ///
+  #[must_use]
+  pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+    let (rows, cols) = self.shape();
+    if row < rows && col < cols {
+      Some(&self.as_slice()[row * cols + col])
+    } else {
+      None
+    }
+  }
+
+  /// `get_mut` returns a mutable reference to the element at row `row` and column `col`, `None` if it's out of bounds.
+  ///
+  pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+    let (rows, cols) = self.shape();
+    if row < rows && col < cols {
+      Some(&mut self.as_mut_slice()[row * cols + col])
+    } else {
+      None
+    }
+  }
+
+  /// `insert_column` inserts `column` as the column at `col`, shifting the columns after it to the right. `column` may
+  /// have any number of elements when the matrix has no rows and no columns, otherwise it must have one per row.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `col > cols()`, if `column` yields the wrong number of elements, if the new capacity overflows or if the
+  /// number of columns exceeds half of the bits of a `usize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut m = minivec::MiniMatrix::from_minivec(minivec::mini_vec![1, 3, 4, 6], 2, 2);
+  ///
+  /// m.insert_column(1, [2, 5]);
+  /// assert_eq!(m.shape(), (2, 3));
+  /// assert_eq!(m.as_slice(), [1, 2, 3, 4, 5, 6]);
+  /// ```
+  ///
+  pub fn insert_column<I: IntoIterator<Item = T>>(&mut self, col: usize, column: I) {
+    let (rows, cols) = self.shape();
+
+    assert!(
+      (col <= cols),
+      "insertion index (is {}) should be <= cols (is {})",
+      col,
+      cols
+    );
+
+    let mut column: MiniVec<T> = column.into_iter().collect();
+    let rows = if rows == 0 && cols == 0 {
+      column.len()
+    } else {
+      rows
+    };
+
+    assert!(
+      (column.len() == rows),
+      "column len (is {}) should be rows (is {})",
+      column.len(),
+      rows
+    );
+
+    self.edit(|vec| {
+      vec.reserve(rows);
+
+      // the rows are spread out starting from the last one, which moves the furthest, so that no row is overwritten
+      // before it's been moved
+      //
+      unsafe {
+        let p = vec.as_mut_ptr();
+        let c = column.as_ptr();
+
+        for row in (0..rows).rev() {
+          let src = p.add(row * cols);
+          let dst = p.add(row * (cols + 1));
+
+          core::ptr::copy(src.add(col), dst.add(col + 1), cols - col);
+          core::ptr::copy(src, dst, col);
+          core::ptr::write(dst.add(col), core::ptr::read(c.add(row)));
+        }
+
+        column.set_len(0);
+        vec.set_len(rows * (cols + 1));
+      }
+
+      (rows, cols + 1)
+    });
+  }
+
+  /// `into_minivec` turns the matrix into a `MiniVec` of its elements, row after row, reusing its allocation.
+  ///
+  #[must_use]
+  pub fn into_minivec(self) -> MiniVec<T> {
+    let this = core::mem::ManuallyDrop::new(self);
+    let mut vec = MiniVec::from_buf(this.buf);
+
+    if !vec.is_default() {
+      vec.header_mut().len = this.len();
+    }
+
+    vec
+  }
+
+  /// `is_empty` returns whether or not the matrix contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `iter_columns` returns an iterator over the columns, each of them being an iterator over its elements.
+  ///
+  #[must_use]
+  pub fn iter_columns(&self) -> MatrixColumns<'_, T> {
+    MatrixColumns {
+      matrix: self,
+      idx: 0,
+    }
+  }
+
+  /// `iter_rows` returns an iterator over the rows as slices.
+  ///
+  #[must_use]
+  pub fn iter_rows(&self) -> MatrixRows<'_, T> {
+    let (rows, cols) = self.shape();
+    MatrixRows {
+      data: self.as_slice(),
+      cols,
+      rows,
+    }
+  }
+
+  /// `len` returns the number of elements, `rows() * cols()`.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    let (rows, cols) = self.shape();
+    rows * cols
+  }
+
+  /// `new` creates an empty matrix with no rows and no columns. It does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniMatrix<T> {
+    MiniMatrix {
+      buf: MiniVec::<T>::empty_buf(),
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `push_row` appends `row` as the last row. `row` may have any number of elements when the matrix has no rows and
+  /// no columns, otherwise it must have one per column.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `row` yields the wrong number of elements, if the new capacity overflows or if the number of rows
+  /// exceeds half of the bits of a `usize`.
+  ///
+  pub fn push_row<I: IntoIterator<Item = T>>(&mut self, row: I) {
+    // the matrix is put back together with its old shape before panicking so that a bad row only drops itself
+    //
+    let mut mismatch = None;
+
+    self.edit(|vec| {
+      let (rows, cols) = vec.shape;
+      let len = vec.vec.len();
+
+      vec.vec.extend(row);
+      let pushed = vec.vec.len() - len;
+
+      let cols = if rows == 0 && cols == 0 { pushed } else { cols };
+      if pushed != cols {
+        vec.vec.truncate(len);
+        mismatch = Some((pushed, cols));
+        return vec.shape;
+      }
+
+      (rows + 1, cols)
+    });
+
+    if let Some((pushed, cols)) = mismatch {
+      panic!("row len (is {}) should be cols (is {})", pushed, cols);
+    }
+  }
+
+  /// `reserve` ensures that the matrix can hold at least `additional` more elements without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    self.edit(|vec| {
+      vec.reserve(additional);
+      vec.shape
+    });
+  }
+
+  /// `resize` changes the shape of the matrix to `rows` by `cols`, keeping the elements which are part of both shapes
+  /// at the same position and filling the rest with clones of `value`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows or if either dimension exceeds half of the bits of a `usize`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut m = minivec::MiniMatrix::from_minivec(minivec::mini_vec![1, 2, 3, 4], 2, 2);
+  ///
+  /// m.resize(3, 1, 0);
+  /// assert_eq!(m.as_slice(), [1, 3, 0]);
+  ///
+  /// m.resize(2, 3, 9);
+  /// assert_eq!(m.as_slice(), [1, 9, 9, 3, 9, 9]);
+  /// ```
+  ///
+  pub fn resize(&mut self, rows: usize, cols: usize, value: T)
+  where
+    T: Clone,
+  {
+    let len = rows
+      .checked_mul(cols)
+      .unwrap_or_else(|| panic!("capacity overflow"));
+
+    self.edit(|vec| {
+      let (old_rows, old_cols) = vec.shape;
+
+      // the rows keep their layout when the number of columns doesn't change
+      //
+      if cols == old_cols {
+        vec.vec.resize(len, value);
+        return (rows, cols);
+      }
+
+      let mut resized = MiniVec::with_capacity(len);
+      let mut old = vec.vec.drain(..);
+
+      for row in 0..rows {
+        for col in 0..cols {
+          match (row < old_rows && col < old_cols)
+            .then(|| old.next())
+            .flatten()
+          {
+            Some(x) => resized.push(x),
+            None => resized.push(value.clone()),
+          };
+        }
+
+        // the columns which don't fit anymore are dropped
+        //
+        if row < old_rows {
+          old
+            .by_ref()
+            .take(old_cols.saturating_sub(cols))
+            .for_each(drop);
+        }
+      }
+
+      drop(old);
+      vec.vec = resized;
+
+      (rows, cols)
+    });
+  }
+
+  /// `row` returns the row at `row` as a slice.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `row >= rows()`.
+  ///
+  #[must_use]
+  pub fn row(&self, row: usize) -> &[T] {
+    let (rows, cols) = self.shape();
+
+    assert!(
+      (row < rows),
+      "row index (is {}) should be < rows (is {})",
+      row,
+      rows
+    );
+
+    &self.as_slice()[row * cols..(row + 1) * cols]
+  }
+
+  /// `row_mut` returns the row at `row` as a mutable slice.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `row >= rows()`.
+  ///
+  pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+    let (rows, cols) = self.shape();
+
+    assert!(
+      (row < rows),
+      "row index (is {}) should be < rows (is {})",
+      row,
+      rows
+    );
+
+    &mut self.as_mut_slice()[row * cols..(row + 1) * cols]
+  }
+
+  /// `rows` returns the number of rows.
+  ///
+  #[must_use]
+  pub fn rows(&self) -> usize {
+    self.shape().0
+  }
+
+  /// `shape` returns the number of rows and the number of columns.
+  ///
+  #[must_use]
+  pub fn shape(&self) -> (usize, usize) {
+    let packed = self.view().header().len;
+    (packed >> HALF, packed & MAX_DIM)
+  }
+
+  /// `transpose` turns the rows of the matrix into its columns.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut m = minivec::MiniMatrix::from_minivec(minivec::mini_vec![1, 2, 3, 4, 5, 6], 2, 3);
+  /// m.transpose();
+  ///
+  /// assert_eq!(m.shape(), (3, 2));
+  /// assert_eq!(m.as_slice(), [1, 4, 2, 5, 3, 6]);
+  /// ```
+  ///
+  pub fn transpose(&mut self) {
+    self.edit(|vec| {
+      let (rows, cols) = vec.shape;
+      let mut transposed = MiniVec::<T>::with_capacity(vec.vec.len());
+
+      unsafe {
+        let src = vec.vec.as_ptr();
+        let dst = transposed.as_mut_ptr();
+
+        for row in 0..rows {
+          for col in 0..cols {
+            core::ptr::copy_nonoverlapping(src.add(row * cols + col), dst.add(col * rows + row), 1);
+          }
+        }
+
+        transposed.set_len(vec.vec.len());
+        vec.vec.set_len(0);
+      }
+
+      vec.vec = transposed;
+      (cols, rows)
+    });
+  }
+
+  // runs `f` on the elements as a `MiniVec` of the current shape and stores the shape it returns, the matrix is left
+  // empty should `f` panic
+  //
+  fn edit<F>(&mut self, f: F)
+  where
+    F: FnOnce(&mut Shaped<T>) -> (usize, usize),
+  {
+    let shape = self.shape();
+    let vec = core::mem::take(self).into_minivec();
+
+    let mut shaped = Shaped { vec, shape };
+    let (rows, cols) = f(&mut shaped);
+
+    *self = Self::from_minivec(shaped.vec, rows, cols);
+  }
+
+  fn data(&self) -> *mut T {
+    self.view().data()
+  }
+
+  // a view of the allocation as a vector, whose length must never be read
+  //
+  fn view(&self) -> core::mem::ManuallyDrop<MiniVec<T>> {
+    core::mem::ManuallyDrop::new(MiniVec::from_buf(self.buf))
+  }
+}
+
+// the elements of a matrix being edited, along with its shape
+//
+struct Shaped<T> {
+  vec: MiniVec<T>,
+  shape: (usize, usize),
+}
+
+impl<T> core::ops::Deref for Shaped<T> {
+  type Target = MiniVec<T>;
+
+  fn deref(&self) -> &MiniVec<T> {
+    &self.vec
+  }
+}
+
+impl<T> core::ops::DerefMut for Shaped<T> {
+  fn deref_mut(&mut self) -> &mut MiniVec<T> {
+    &mut self.vec
+  }
+}
+
+impl<T> Drop for MiniMatrix<T> {
+  fn drop(&mut self) {
+    let len = self.len();
+    let mut vec = MiniVec::<T>::from_buf(self.buf);
+
+    if !vec.is_default() {
+      vec.header_mut().len = len;
+    }
+  }
+}
+
+impl<T: Clone> Clone for MiniMatrix<T> {
+  fn clone(&self) -> Self {
+    let (rows, cols) = self.shape();
+    Self::from_minivec(MiniVec::from(self.as_slice()), rows, cols)
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniMatrix<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter_rows()).finish()
+  }
+}
+
+impl<T> Default for MiniMatrix<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniMatrix<T> {
+  fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+    self.shape().hash(state);
+    self.as_slice().hash(state);
+  }
+}
+
+impl<T> core::ops::Index<(usize, usize)> for MiniMatrix<T> {
+  type Output = T;
+
+  fn index(&self, (row, col): (usize, usize)) -> &T {
+    let (rows, cols) = self.shape();
+    match self.get(row, col) {
+      Some(x) => x,
+      None => panic!(
+        "index (is ({}, {})) should be < shape (is ({}, {}))",
+        row, col, rows, cols
+      ),
+    }
+  }
+}
+
+impl<T> core::ops::IndexMut<(usize, usize)> for MiniMatrix<T> {
+  fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+    let (rows, cols) = self.shape();
+    match self.get_mut(row, col) {
+      Some(x) => x,
+      None => panic!(
+        "index (is ({}, {})) should be < shape (is ({}, {}))",
+        row, col, rows, cols
+      ),
+    }
+  }
+}
+
+impl<T: Eq> Eq for MiniMatrix<T> {}
+
+impl<T: PartialEq> PartialEq for MiniMatrix<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.shape() == other.shape() && self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T> From<MiniMatrix<T>> for MiniVec<T> {
+  fn from(matrix: MiniMatrix<T>) -> Self {
+    matrix.into_minivec()
+  }
+}
+
+impl<'a, T> Iterator for MatrixRows<'a, T> {
+  type Item = &'a [T];
+
+  fn next(&mut self) -> Option<&'a [T]> {
+    if self.rows == 0 {
+      return None;
+    }
+
+    let (row, rest) = self.data.split_at(self.cols);
+    self.data = rest;
+    self.rows -= 1;
+
+    Some(row)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.rows, Some(self.rows))
+  }
+}
+
+impl<T> DoubleEndedIterator for MatrixRows<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.rows == 0 {
+      return None;
+    }
+
+    let (rest, row) = self.data.split_at(self.data.len() - self.cols);
+    self.data = rest;
+    self.rows -= 1;
+
+    Some(row)
+  }
+}
+
+impl<T> ExactSizeIterator for MatrixRows<'_, T> {}
+
+impl<T> core::iter::FusedIterator for MatrixRows<'_, T> {}
+
+impl<'a, T> Iterator for MatrixColumns<'a, T> {
+  type Item = core::iter::StepBy<core::slice::Iter<'a, T>>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.idx == self.matrix.cols() {
+      return None;
+    }
+
+    self.idx += 1;
+    Some(self.matrix.column(self.idx - 1))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.matrix.cols() - self.idx;
+    (len, Some(len))
+  }
+}
+
+impl<T> ExactSizeIterator for MatrixColumns<'_, T> {}
+
+impl<T> core::iter::FusedIterator for MatrixColumns<'_, T> {}
+
+unsafe impl<T: Send> Send for MiniMatrix<T> {}
+unsafe impl<T: Sync> Sync for MiniMatrix<T> {}
@@ -0,0 +1,248 @@
+use crate::{MiniVec, TryReserveError};
+
+/// `MiniRingBuffer` is a fixed-capacity ring buffer that keeps the most recent elements pushed into it. Once it's full,
+/// every [`push`](MiniRingBuffer::push) overwrites the oldest element and hands it back.
+///
+/// The elements are stored in a [`MiniVec`] allocated by [`MiniVec::with_capacity`] at construction, which never
+/// grows, next to the position of the oldest element. Until the buffer first fills up the elements are simply
+/// appended to the vector, oldest first.
+///
+/// # Example
+///
+/// ```
+/// let mut events = minivec::MiniRingBuffer::with_capacity(3);
+///
+/// assert_eq!(events.push("connect"), None);
+/// assert_eq!(events.push("send"), None);
+/// assert_eq!(events.push("recv"), None);
+/// assert_eq!(events.push("close"), Some("connect"));
+///
+/// assert_eq!(events.to_minivec(), ["send", "recv", "close"]);
+/// ```
+///
+pub struct MiniRingBuffer<T> {
+  buf: MiniVec<T>,
+  head: usize,
+  // the requested capacity, the vector's own is unbounded for zero-sized types
+  //
+  cap: usize,
+}
+
+impl<T> MiniRingBuffer<T> {
+  /// `as_slices` returns the elements as two slices, the first one holding the oldest elements.
+  ///
+  #[must_use]
+  pub fn as_slices(&self) -> (&[T], &[T]) {
+    let (newest, oldest) = self.buf.split_at(self.head);
+    (oldest, newest)
+  }
+
+  /// `capacity` returns the number of elements the buffer holds before it starts overwriting the oldest ones.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.cap
+  }
+
+  /// `clear` drops all of the elements, keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.buf.clear();
+    self.head = 0;
+  }
+
+  /// `drain_oldest` removes up to `n` of the oldest elements and returns them, oldest first.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut ring: minivec::MiniRingBuffer<_> = minivec::MiniRingBuffer::with_capacity(3);
+  /// ring.extend(1..=5);
+  ///
+  /// assert_eq!(ring.drain_oldest(2), [3, 4]);
+  /// assert_eq!(ring.drain_oldest(2), [5]);
+  /// assert!(ring.is_empty());
+  /// ```
+  ///
+  pub fn drain_oldest(&mut self, n: usize) -> MiniVec<T> {
+    self.make_contiguous();
+
+    let n = core::cmp::min(n, self.len());
+    self.buf.drain(..n).collect()
+  }
+
+  /// `is_empty` returns whether or not the buffer contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.buf.is_empty()
+  }
+
+  /// `is_full` returns whether or not the next [`push`](MiniRingBuffer::push) overwrites the oldest element.
+  ///
+  #[must_use]
+  pub fn is_full(&self) -> bool {
+    self.len() == self.capacity()
+  }
+
+  /// `iter` returns an iterator over the elements, from oldest to newest.
+  ///
+  pub fn iter(&self) -> core::iter::Chain<core::slice::Iter<'_, T>, core::slice::Iter<'_, T>> {
+    let (oldest, newest) = self.as_slices();
+    oldest.iter().chain(newest.iter())
+  }
+
+  /// `len` returns the number of elements in the buffer.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.buf.len()
+  }
+
+  /// `newest` returns a reference to the most recently pushed element, should it exist.
+  ///
+  #[must_use]
+  pub fn newest(&self) -> Option<&T> {
+    let (oldest, newest) = self.as_slices();
+    newest.last().or_else(|| oldest.last())
+  }
+
+  /// `oldest` returns a reference to the element which is overwritten next, should it exist.
+  ///
+  #[must_use]
+  pub fn oldest(&self) -> Option<&T> {
+    let (oldest, newest) = self.as_slices();
+    oldest.first().or_else(|| newest.first())
+  }
+
+  /// `push` adds `value` as the newest element, returning the oldest element when the buffer was already full. A buffer
+  /// with a capacity of `0` returns `value` itself.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut ring = minivec::MiniRingBuffer::with_capacity(2);
+  ///
+  /// assert_eq!(ring.push(1), None);
+  /// assert_eq!(ring.push(2), None);
+  /// assert_eq!(ring.push(3), Some(1));
+  /// assert_eq!(ring.oldest(), Some(&2));
+  /// ```
+  ///
+  pub fn push(&mut self, value: T) -> Option<T> {
+    let capacity = self.capacity();
+
+    if capacity == 0 {
+      return Some(value);
+    }
+
+    if self.len() < capacity {
+      // there's still room in the vector so this never reallocates
+      //
+      self.buf.push(value);
+      return None;
+    }
+
+    let evicted = core::mem::replace(&mut self.buf[self.head], value);
+    self.head = if self.head + 1 == capacity {
+      0
+    } else {
+      self.head + 1
+    };
+
+    Some(evicted)
+  }
+
+  /// `to_minivec` returns a copy of the elements, from oldest to newest.
+  ///
+  #[must_use]
+  pub fn to_minivec(&self) -> MiniVec<T>
+  where
+    T: Clone,
+  {
+    let mut vec = MiniVec::with_capacity(self.len());
+    let (oldest, newest) = self.as_slices();
+    vec.extend_from_slice(oldest);
+    vec.extend_from_slice(newest);
+    vec
+  }
+
+  /// `try_with_capacity` is the fallible version of [`with_capacity`](MiniRingBuffer::with_capacity).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the capacity overflows or the allocator fails.
+  ///
+  pub fn try_with_capacity(capacity: usize) -> Result<MiniRingBuffer<T>, TryReserveError> {
+    Ok(MiniRingBuffer {
+      buf: MiniVec::try_with_capacity(capacity)?,
+      head: 0,
+      cap: capacity,
+    })
+  }
+
+  /// `with_capacity` creates an empty buffer which holds up to `capacity` elements.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniRingBuffer<T> {
+    MiniRingBuffer {
+      buf: MiniVec::with_capacity(capacity),
+      head: 0,
+      cap: capacity,
+    }
+  }
+
+  // moves the oldest element to the front of the vector
+  //
+  fn make_contiguous(&mut self) {
+    self.buf.rotate_left(self.head);
+    self.head = 0;
+  }
+}
+
+impl<T: Clone> Clone for MiniRingBuffer<T> {
+  fn clone(&self) -> Self {
+    let mut buf = MiniVec::with_capacity(self.capacity());
+    buf.extend_from_slice(&self.buf);
+
+    MiniRingBuffer {
+      buf,
+      head: self.head,
+      cap: self.cap,
+    }
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniRingBuffer<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Extend<T> for MiniRingBuffer<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for x in iter {
+      self.push(x);
+    }
+  }
+}
+
+impl<T> From<MiniRingBuffer<T>> for MiniVec<T> {
+  fn from(mut ring: MiniRingBuffer<T>) -> Self {
+    ring.make_contiguous();
+    ring.buf
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniRingBuffer<T> {
+  type Item = &'a T;
+  type IntoIter = core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -0,0 +1,768 @@
+use crate::r#impl::helpers::{
+  header_bytes, make_aligned_layout, make_prefixed_layout, max_align, prefixed_data_offset,
+};
+use crate::{handle_reserve, Allocator, Global, GrowthPolicy, IndexType, MiniVec};
+use crate::{TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+mod sealed {
+  pub trait Sealed {}
+
+  impl Sealed for core::cell::Cell<usize> {}
+  #[cfg(target_has_atomic = "ptr")]
+  impl Sealed for core::sync::atomic::AtomicUsize {}
+}
+
+/// `RefCount` is the type a [`MiniShared`] uses for its strong and weak reference counts.
+///
+/// It's implemented for `Cell<usize>`, which backs [`MiniRc`], and `AtomicUsize`, which backs [`MiniArc`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+///
+pub trait RefCount: sealed::Sealed + 'static {
+  #[doc(hidden)]
+  fn new(n: usize) -> Self;
+
+  #[doc(hidden)]
+  fn get(&self) -> usize;
+
+  #[doc(hidden)]
+  fn increment(&self);
+
+  // returns `true` when the count dropped to zero
+  //
+  #[doc(hidden)]
+  fn decrement(&self) -> bool;
+
+  #[doc(hidden)]
+  fn increment_if_nonzero(&self) -> bool;
+
+  // atomically replaces a count of exactly `1` with `0`
+  //
+  #[doc(hidden)]
+  fn claim_unique(&self) -> bool;
+
+  // atomically replaces a count of exactly `1` with `usize::MAX`, locking it until `unlock` is called
+  //
+  #[doc(hidden)]
+  fn lock(&self) -> bool;
+
+  #[doc(hidden)]
+  fn unlock(&self);
+
+  // like `increment` but waits for the count to be unlocked first
+  //
+  #[doc(hidden)]
+  fn increment_unlocked(&self);
+}
+
+impl RefCount for core::cell::Cell<usize> {
+  fn new(n: usize) -> Self {
+    core::cell::Cell::new(n)
+  }
+
+  fn get(&self) -> usize {
+    core::cell::Cell::get(self)
+  }
+
+  fn increment(&self) {
+    let Some(n) = core::cell::Cell::get(self).checked_add(1) else {
+      panic!("reference count overflow");
+    };
+
+    self.set(n);
+  }
+
+  fn decrement(&self) -> bool {
+    let n = core::cell::Cell::get(self) - 1;
+    self.set(n);
+    n == 0
+  }
+
+  fn increment_if_nonzero(&self) -> bool {
+    if core::cell::Cell::get(self) == 0 {
+      return false;
+    }
+
+    self.increment();
+    true
+  }
+
+  fn claim_unique(&self) -> bool {
+    if core::cell::Cell::get(self) != 1 {
+      return false;
+    }
+
+    self.set(0);
+    true
+  }
+
+  fn lock(&self) -> bool {
+    if core::cell::Cell::get(self) != 1 {
+      return false;
+    }
+
+    self.set(usize::MAX);
+    true
+  }
+
+  fn unlock(&self) {
+    self.set(1);
+  }
+
+  fn increment_unlocked(&self) {
+    self.increment();
+  }
+}
+
+// the orderings mirror those of `alloc::sync::Arc`
+//
+#[cfg(target_has_atomic = "ptr")]
+impl RefCount for core::sync::atomic::AtomicUsize {
+  fn new(n: usize) -> Self {
+    core::sync::atomic::AtomicUsize::new(n)
+  }
+
+  fn get(&self) -> usize {
+    self.load(core::sync::atomic::Ordering::Acquire)
+  }
+
+  fn increment(&self) {
+    #[allow(clippy::cast_sign_loss)]
+    let max = isize::MAX as usize;
+
+    if self.fetch_add(1, core::sync::atomic::Ordering::Relaxed) > max {
+      self.fetch_sub(1, core::sync::atomic::Ordering::Relaxed);
+      panic!("reference count overflow");
+    }
+  }
+
+  fn decrement(&self) -> bool {
+    if self.fetch_sub(1, core::sync::atomic::Ordering::Release) != 1 {
+      return false;
+    }
+
+    core::sync::atomic::fence(core::sync::atomic::Ordering::Acquire);
+    true
+  }
+
+  fn increment_if_nonzero(&self) -> bool {
+    self
+      .fetch_update(
+        core::sync::atomic::Ordering::Acquire,
+        core::sync::atomic::Ordering::Relaxed,
+        |n| if n == 0 { None } else { n.checked_add(1) },
+      )
+      .is_ok()
+  }
+
+  fn claim_unique(&self) -> bool {
+    self
+      .compare_exchange(
+        1,
+        0,
+        core::sync::atomic::Ordering::Acquire,
+        core::sync::atomic::Ordering::Relaxed,
+      )
+      .is_ok()
+  }
+
+  fn lock(&self) -> bool {
+    self
+      .compare_exchange(
+        1,
+        usize::MAX,
+        core::sync::atomic::Ordering::Acquire,
+        core::sync::atomic::Ordering::Relaxed,
+      )
+      .is_ok()
+  }
+
+  fn unlock(&self) {
+    self.store(1, core::sync::atomic::Ordering::Release);
+  }
+
+  fn increment_unlocked(&self) {
+    #[allow(clippy::cast_sign_loss)]
+    let max = isize::MAX as usize;
+
+    let mut n = self.load(core::sync::atomic::Ordering::Relaxed);
+    loop {
+      if n == usize::MAX {
+        core::hint::spin_loop();
+        n = self.load(core::sync::atomic::Ordering::Relaxed);
+        continue;
+      }
+
+      assert!(n <= max, "reference count overflow");
+
+      match self.compare_exchange_weak(
+        n,
+        n + 1,
+        core::sync::atomic::Ordering::Acquire,
+        core::sync::atomic::Ordering::Relaxed,
+      ) {
+        Ok(_) => return,
+        Err(old) => n = old,
+      }
+    }
+  }
+}
+
+// unlike `MiniVec`, the header is always present and so are the counts, even when there are no elements
+//
+#[repr(C)]
+struct SharedHeader<C> {
+  strong: C,
+  weak: C,
+  len: usize,
+  cap: usize,
+}
+
+/// `MiniShared` is a reference-counted, immutable slice that's only the size of a single pointer. The strong and weak
+/// reference counts are stored in the same allocation as the elements, alongside their length and capacity.
+///
+/// It's used through its aliases, the single-threaded [`MiniRc`] and the thread-safe [`MiniArc`].
+///
+/// A `MiniShared` is created from a [`MiniVec`] by reusing its allocation whenever the vector's header has room for the
+/// reference counts and by moving the elements into a new allocation otherwise. No element is ever cloned.
+///
+/// # Example
+///
+/// ```
+/// let shared = minivec::MiniArc::from(minivec::mini_vec![1, 2, 3]);
+/// let other = shared.clone();
+///
+/// assert_eq!(*other, [1, 2, 3]);
+/// assert_eq!(minivec::MiniArc::strong_count(&shared), 2);
+/// assert_eq!(core::mem::size_of_val(&shared), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniShared<T, C: RefCount> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<(T, C)>,
+}
+
+/// `MiniWeak` is a non-owning handle to the elements of a [`MiniShared`], obtained from
+/// [`downgrade`](MiniShared::downgrade). It keeps the allocation alive but not the elements.
+///
+pub struct MiniWeak<T, C: RefCount> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<(T, C)>,
+}
+
+/// `MiniRc` is the single-threaded variant of [`MiniShared`], the counterpart of `alloc::rc::Rc<[T]>`.
+///
+pub type MiniRc<T> = MiniShared<T, core::cell::Cell<usize>>;
+
+/// `MiniRcWeak` is the [`MiniWeak`] handle of a [`MiniRc`].
+///
+pub type MiniRcWeak<T> = MiniWeak<T, core::cell::Cell<usize>>;
+
+/// `MiniArc` is the thread-safe variant of [`MiniShared`], the counterpart of `alloc::sync::Arc<[T]>`.
+///
+#[cfg(target_has_atomic = "ptr")]
+pub type MiniArc<T> = MiniShared<T, core::sync::atomic::AtomicUsize>;
+
+/// `MiniArcWeak` is the [`MiniWeak`] handle of a [`MiniArc`].
+///
+#[cfg(target_has_atomic = "ptr")]
+pub type MiniArcWeak<T> = MiniWeak<T, core::sync::atomic::AtomicUsize>;
+
+const fn layout<T, C>(cap: usize) -> Option<alloc::alloc::Layout> {
+  make_prefixed_layout::<SharedHeader<C>, T>(cap)
+}
+
+impl<T, C: RefCount> MiniShared<T, C> {
+  const OFFSET: usize = prefixed_data_offset::<SharedHeader<C>, T>();
+
+  /// `as_ptr` returns a pointer to the first element of the slice.
+  ///
+  #[must_use]
+  pub fn as_ptr(&self) -> *const T {
+    self.data()
+  }
+
+  /// `as_slice` returns a slice over all of the elements.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.data(), self.header().len) }
+  }
+
+  /// `downgrade` creates a new [`MiniWeak`] handle to the elements of `this`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let rc = minivec::MiniRc::from(minivec::mini_vec![1, 2, 3]);
+  /// let weak = minivec::MiniRc::downgrade(&rc);
+  ///
+  /// assert_eq!(*weak.upgrade().unwrap(), [1, 2, 3]);
+  ///
+  /// drop(rc);
+  /// assert!(weak.upgrade().is_none());
+  /// ```
+  ///
+  #[must_use]
+  pub fn downgrade(this: &Self) -> MiniWeak<T, C> {
+    // `is_unique` may have locked the weak count, in which case the new handle has to wait for it to finish
+    //
+    this.header().weak.increment_unlocked();
+
+    MiniWeak {
+      buf: this.buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `get_mut` returns a mutable slice over the elements if there are no other `MiniShared` or `MiniWeak` handles to
+  /// them.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut rc = minivec::MiniRc::from(minivec::mini_vec![1, 2, 3]);
+  /// minivec::MiniRc::get_mut(&mut rc).unwrap()[0] = 4;
+  ///
+  /// let other = rc.clone();
+  /// assert!(minivec::MiniRc::get_mut(&mut rc).is_none());
+  /// assert_eq!(*other, [4, 2, 3]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn get_mut(this: &mut Self) -> Option<&mut [T]> {
+    if this.is_unique() {
+      Some(unsafe { core::slice::from_raw_parts_mut(this.data(), this.header().len) })
+    } else {
+      None
+    }
+  }
+
+  /// `make_mut` returns a mutable slice over the elements, first cloning them into a new allocation if there are
+  /// other `MiniShared` or `MiniWeak` handles to them.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut rc = minivec::MiniRc::from(minivec::mini_vec![1, 2, 3]);
+  /// let other = rc.clone();
+  ///
+  /// minivec::MiniRc::make_mut(&mut rc)[0] = 4;
+  ///
+  /// assert_eq!(*rc, [4, 2, 3]);
+  /// assert_eq!(*other, [1, 2, 3]);
+  /// ```
+  ///
+  pub fn make_mut(this: &mut Self) -> &mut [T]
+  where
+    T: Clone,
+  {
+    if !this.is_unique() {
+      *this = Self::from(this.as_slice());
+    }
+
+    unsafe { core::slice::from_raw_parts_mut(this.data(), this.header().len) }
+  }
+
+  /// `ptr_eq` returns whether or not both handles point to the same allocation.
+  ///
+  #[must_use]
+  pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+    this.buf == other.buf
+  }
+
+  /// `strong_count` returns the number of `MiniShared` handles to the elements.
+  ///
+  #[must_use]
+  pub fn strong_count(this: &Self) -> usize {
+    this.header().strong.get()
+  }
+
+  /// `try_unwrap` returns the elements as a [`MiniVec`] if `this` is the only `MiniShared` handle to them, and `this`
+  /// otherwise.
+  ///
+  /// The allocation is reused when there are no outstanding `MiniWeak` handles and its layout is compatible with that
+  /// of the vector. Otherwise the elements are moved into a new vector.
+  ///
+  /// # Errors
+  ///
+  /// Returns `this` unchanged if there are other `MiniShared` handles to the elements.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let rc = minivec::MiniRc::from(minivec::mini_vec![1, 2, 3]);
+  /// let other = rc.clone();
+  ///
+  /// let rc = minivec::MiniRc::try_unwrap(rc).unwrap_err();
+  /// drop(other);
+  ///
+  /// assert_eq!(minivec::MiniRc::try_unwrap(rc).unwrap(), [1, 2, 3]);
+  /// ```
+  ///
+  pub fn try_unwrap(this: Self) -> Result<MiniVec<T>, Self> {
+    if !this.header().strong.claim_unique() {
+      return Err(this);
+    }
+
+    let this = core::mem::ManuallyDrop::new(this);
+    let (len, cap) = (this.header().len, this.header().cap);
+
+    if this.header().weak.get() == 1 && Self::reuses_vec_layout::<usize>(cap) {
+      unsafe {
+        MiniVec::<T>::write_header(this.buf.as_ptr(), len, cap);
+        return Ok(MiniVec::from_buf(this.buf));
+      }
+    }
+
+    let mut vec = MiniVec::with_capacity(len);
+    unsafe {
+      core::ptr::copy_nonoverlapping(this.data(), vec.as_mut_ptr(), len);
+      vec.set_len(len);
+    }
+
+    // the elements are gone but the implicit weak reference held by the strong ones must still be released
+    //
+    drop(MiniWeak::<T, C> {
+      buf: this.buf,
+      phantom: core::marker::PhantomData,
+    });
+
+    Ok(vec)
+  }
+
+  /// `weak_count` returns the number of `MiniWeak` handles to the elements.
+  ///
+  #[must_use]
+  pub fn weak_count(this: &Self) -> usize {
+    // a locked count means `is_unique` only just observed a count of `1`
+    //
+    match this.header().weak.get() {
+      usize::MAX => 0,
+      n => n - 1,
+    }
+  }
+
+  // allocates room for `cap` elements with both counts set to `1` and a length of `0`
+  //
+  fn try_allocate(cap: usize) -> Result<MiniShared<T, C>, TryReserveError> {
+    let layout = layout::<T, C>(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+    let buf = Global
+      .allocate(layout)
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    unsafe { Self::write_header(buf, 0, cap) };
+
+    Ok(MiniShared {
+      buf,
+      phantom: core::marker::PhantomData,
+    })
+  }
+
+  unsafe fn write_header(buf: core::ptr::NonNull<u8>, len: usize, cap: usize) {
+    #[allow(clippy::cast_ptr_alignment)]
+    core::ptr::write(
+      buf.as_ptr().cast::<SharedHeader<C>>(),
+      SharedHeader {
+        strong: C::new(1),
+        weak: C::new(1),
+        len,
+        cap,
+      },
+    );
+  }
+
+  // a vector's allocation can be shared in place when the elements begin at the same offset and the allocation is
+  // described by the same `Layout`, see `header_bytes()`
+  //
+  fn reuses_vec_layout<H: IndexType>(cap: usize) -> bool {
+    Self::OFFSET == header_bytes::<T, Global, H>()
+      && layout::<T, C>(cap)
+        == make_aligned_layout::<T, Global, H>(cap, max_align::<T, Global, H>())
+  }
+
+  // only valid while the allocation isn't shared yet
+  //
+  unsafe fn set_len(&self, len: usize) {
+    #[allow(clippy::cast_ptr_alignment)]
+    let header = self.buf.as_ptr().cast::<SharedHeader<C>>();
+    (*header).len = len;
+  }
+
+  // mirrors `alloc::sync::Arc::is_unique`, reading the two counts separately would let another strong handle
+  // `downgrade` and then drop itself in between the two reads so the weak count is locked instead, making `downgrade`
+  // wait until the strong count has been read
+  //
+  fn is_unique(&self) -> bool {
+    if !self.header().weak.lock() {
+      return false;
+    }
+
+    let unique = self.header().strong.get() == 1;
+    self.header().weak.unlock();
+    unique
+  }
+
+  fn header(&self) -> &SharedHeader<C> {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<SharedHeader<C>>()
+    }
+  }
+
+  fn data(&self) -> *mut T {
+    unsafe { self.buf.as_ptr().add(Self::OFFSET).cast::<T>() }
+  }
+}
+
+impl<T, C: RefCount> MiniWeak<T, C> {
+  /// `strong_count` returns the number of [`MiniShared`] handles to the elements.
+  ///
+  #[must_use]
+  pub fn strong_count(&self) -> usize {
+    self.header().strong.get()
+  }
+
+  /// `upgrade` attempts to create a new [`MiniShared`] handle to the elements, returning `None` if they have already
+  /// been dropped.
+  ///
+  #[must_use]
+  pub fn upgrade(&self) -> Option<MiniShared<T, C>> {
+    if !self.header().strong.increment_if_nonzero() {
+      return None;
+    }
+
+    Some(MiniShared {
+      buf: self.buf,
+      phantom: core::marker::PhantomData,
+    })
+  }
+
+  /// `weak_count` returns the number of `MiniWeak` handles to the elements, or `0` if they have already been dropped.
+  ///
+  #[must_use]
+  pub fn weak_count(&self) -> usize {
+    if self.strong_count() == 0 {
+      return 0;
+    }
+
+    self.header().weak.get() - 1
+  }
+
+  fn header(&self) -> &SharedHeader<C> {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<SharedHeader<C>>()
+    }
+  }
+}
+
+impl<T, C: RefCount> Drop for MiniShared<T, C> {
+  fn drop(&mut self) {
+    if !self.header().strong.decrement() {
+      return;
+    }
+
+    unsafe {
+      core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+        self.data(),
+        self.header().len,
+      ));
+    }
+
+    drop(MiniWeak::<T, C> {
+      buf: self.buf,
+      phantom: core::marker::PhantomData,
+    });
+  }
+}
+
+impl<T, C: RefCount> Drop for MiniWeak<T, C> {
+  fn drop(&mut self) {
+    if !self.header().weak.decrement() {
+      return;
+    }
+
+    unsafe {
+      let layout = layout::<T, C>(self.header().cap).unwrap_unchecked();
+      Global.deallocate(self.buf, layout);
+    }
+  }
+}
+
+impl<T, C: RefCount> Clone for MiniShared<T, C> {
+  fn clone(&self) -> Self {
+    self.header().strong.increment();
+
+    MiniShared {
+      buf: self.buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+}
+
+impl<T, C: RefCount> Clone for MiniWeak<T, C> {
+  fn clone(&self) -> Self {
+    self.header().weak.increment();
+
+    MiniWeak {
+      buf: self.buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+}
+
+impl<T, C: RefCount> core::ops::Deref for MiniShared<T, C> {
+  type Target = [T];
+
+  fn deref(&self) -> &[T] {
+    self.as_slice()
+  }
+}
+
+impl<T, C: RefCount> AsRef<[T]> for MiniShared<T, C> {
+  fn as_ref(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T, C: RefCount> core::borrow::Borrow<[T]> for MiniShared<T, C> {
+  fn borrow(&self) -> &[T] {
+    self
+  }
+}
+
+impl<T: core::fmt::Debug, C: RefCount> core::fmt::Debug for MiniShared<T, C> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl<T, C: RefCount> core::fmt::Debug for MiniWeak<T, C> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str("(Weak)")
+  }
+}
+
+impl<T, C: RefCount> Default for MiniShared<T, C> {
+  fn default() -> Self {
+    Self::from(MiniVec::<T>::new())
+  }
+}
+
+impl<T: core::hash::Hash, C: RefCount> core::hash::Hash for MiniShared<T, C> {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.as_slice().hash(state);
+  }
+}
+
+impl<T: Eq, C: RefCount> Eq for MiniShared<T, C> {}
+
+impl<T: PartialEq<U>, U, C: RefCount> PartialEq<MiniShared<U, C>> for MiniShared<T, C> {
+  fn eq(&self, other: &MiniShared<U, C>) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: PartialEq<U>, U, C: RefCount> PartialEq<[U]> for MiniShared<T, C> {
+  fn eq(&self, other: &[U]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: PartialEq<U>, U, C: RefCount, const N: usize> PartialEq<[U; N]> for MiniShared<T, C> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: Ord, C: RefCount> Ord for MiniShared<T, C> {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.as_slice().cmp(other.as_slice())
+  }
+}
+
+impl<T: PartialOrd, C: RefCount> PartialOrd for MiniShared<T, C> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    self.as_slice().partial_cmp(other.as_slice())
+  }
+}
+
+impl<T, C: RefCount, H: IndexType, G: GrowthPolicy> From<MiniVec<T, Global, H, G>>
+  for MiniShared<T, C>
+{
+  /// `from` shares the elements of `vec` without cloning them.
+  ///
+  /// The allocation of `vec` is only reused when its header already has room for the reference counts, otherwise the
+  /// elements are copied into a new allocation, making the conversion `O(n)`.
+  ///
+  fn from(vec: MiniVec<T, Global, H, G>) -> Self {
+    let mut vec = core::mem::ManuallyDrop::new(vec);
+    let (len, cap) = (vec.len(), vec.raw_capacity());
+
+    if !vec.is_default() && vec.aligned_flag() == 0 && Self::reuses_vec_layout::<H>(cap) {
+      unsafe { Self::write_header(vec.buf, len, cap) };
+
+      return MiniShared {
+        buf: vec.buf,
+        phantom: core::marker::PhantomData,
+      };
+    }
+
+    let shared = handle_reserve(Self::try_allocate(len));
+    unsafe {
+      core::ptr::copy_nonoverlapping(vec.as_ptr(), shared.data(), len);
+      shared.set_len(len);
+      vec.set_len(0);
+      core::mem::ManuallyDrop::drop(&mut vec);
+    }
+
+    shared
+  }
+}
+
+impl<T: Clone, C: RefCount> From<&[T]> for MiniShared<T, C> {
+  fn from(s: &[T]) -> Self {
+    // the length only grows once an element is written so a panicking `clone()` only drops the finished ones
+    //
+    let shared = handle_reserve(Self::try_allocate(s.len()));
+    for (idx, x) in s.iter().enumerate() {
+      unsafe {
+        core::ptr::write(shared.data().add(idx), x.clone());
+        shared.set_len(idx + 1);
+      }
+    }
+
+    shared
+  }
+}
+
+impl<T, C: RefCount> core::iter::FromIterator<T> for MiniShared<T, C> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<MiniVec<T>>())
+  }
+}
+
+impl<'a, T, C: RefCount> IntoIterator for &'a MiniShared<T, C> {
+  type Item = &'a T;
+  type IntoIter = core::slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T: Send + Sync> Send for MiniShared<T, core::sync::atomic::AtomicUsize> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T: Send + Sync> Sync for MiniShared<T, core::sync::atomic::AtomicUsize> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T: Send + Sync> Send for MiniWeak<T, core::sync::atomic::AtomicUsize> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<T: Send + Sync> Sync for MiniWeak<T, core::sync::atomic::AtomicUsize> {}
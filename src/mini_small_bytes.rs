@@ -0,0 +1,467 @@
+use crate::{handle_reserve, MiniVec, TryReserveError};
+
+// the pointer of a `MiniVec` always points to a header aligned to at least `align_of::<usize>()` so its lowest bit is
+// free to flag whether the word holds a pointer or the bytes themselves
+//
+// inline, the byte holding the lowest bit of the word stores `(len << 1) | 1` and the remaining bytes hold the data:
+//
+// little endian: | (len << 1) | 1 | data ... |
+// big endian:    | data ... | (len << 1) | 1 |
+//
+const WORD_BYTES: usize = core::mem::size_of::<usize>();
+
+const TAG_BYTE: usize = if cfg!(target_endian = "little") {
+  0
+} else {
+  WORD_BYTES - 1
+};
+
+const DATA_START: usize = if cfg!(target_endian = "little") { 1 } else { 0 };
+
+const INLINE_CAPACITY: usize = WORD_BYTES - 1;
+
+union Repr {
+  heap: core::mem::ManuallyDrop<MiniVec<u8>>,
+  inline: [u8; WORD_BYTES],
+}
+
+/// `MiniSmallBytes` is a growable vector of bytes which stores up to `size_of::<usize>() - 1` bytes inline, in the very
+/// word that would otherwise hold the pointer to its allocation. Short contents such as identifiers never allocate
+/// while longer ones spill over to the heap layout of a regular `MiniVec<u8>`.
+///
+/// Once spilled, a `MiniSmallBytes` keeps its allocation until [`shrink_to_fit`](MiniSmallBytes::shrink_to_fit)
+/// moves the bytes back inline.
+///
+/// # Example
+///
+/// ```
+/// let mut bytes = minivec::MiniSmallBytes::from(&b"id"[..]);
+/// bytes.push(b'_');
+/// bytes.extend_from_slice(b"0");
+///
+/// assert!(bytes.is_inline());
+/// assert_eq!(bytes, b"id_0"[..]);
+///
+/// bytes.extend_from_slice(b"123456789");
+///
+/// assert!(!bytes.is_inline());
+/// assert_eq!(bytes, b"id_0123456789"[..]);
+/// assert_eq!(core::mem::size_of_val(&bytes), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniSmallBytes {
+  repr: Repr,
+}
+
+impl MiniSmallBytes {
+  /// `INLINE_CAPACITY` is the number of bytes a `MiniSmallBytes` holds without allocating.
+  ///
+  pub const INLINE_CAPACITY: usize = INLINE_CAPACITY;
+
+  /// `as_mut_slice` returns the bytes as a mutable slice.
+  ///
+  pub fn as_mut_slice(&mut self) -> &mut [u8] {
+    if self.is_inline() {
+      let len = self.len();
+      unsafe { &mut self.repr.inline[DATA_START..][..len] }
+    } else {
+      self.heap_mut().as_mut_slice()
+    }
+  }
+
+  /// `as_slice` returns the bytes as a slice.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[u8] {
+    if self.is_inline() {
+      let len = self.len();
+      unsafe { &self.repr.inline[DATA_START..][..len] }
+    } else {
+      unsafe { self.repr.heap.as_slice() }
+    }
+  }
+
+  /// `capacity` returns the number of bytes the vector can hold without reallocating, which is
+  /// [`INLINE_CAPACITY`](MiniSmallBytes::INLINE_CAPACITY) for inline vectors.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    if self.is_inline() {
+      INLINE_CAPACITY
+    } else {
+      unsafe { self.repr.heap.capacity() }
+    }
+  }
+
+  /// `clear` removes all of the bytes, keeping any allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  /// `extend_from_slice` appends every byte of `bytes`, spilling to the heap should they not fit inline.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+    self.reserve(bytes.len());
+
+    if self.is_inline() {
+      let len = self.len();
+      unsafe { self.repr.inline[DATA_START + len..][..bytes.len()].copy_from_slice(bytes) };
+      self.set_inline_len(len + bytes.len());
+    } else {
+      self.heap_mut().extend_from_slice(bytes);
+    }
+  }
+
+  /// `is_empty` returns whether or not the vector contains any bytes.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `is_inline` returns whether the bytes are stored inline or on the heap.
+  ///
+  #[must_use]
+  pub fn is_inline(&self) -> bool {
+    unsafe { self.repr.inline[TAG_BYTE] & 1 == 1 }
+  }
+
+  /// `len` returns the number of bytes in the vector.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    if self.is_inline() {
+      unsafe { usize::from(self.repr.inline[TAG_BYTE] >> 1) }
+    } else {
+      unsafe { self.repr.heap.len() }
+    }
+  }
+
+  /// `new` creates an empty, inline vector. It does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniSmallBytes {
+    let mut inline = [0; WORD_BYTES];
+    inline[TAG_BYTE] = 1;
+
+    MiniSmallBytes {
+      repr: Repr { inline },
+    }
+  }
+
+  /// `pop` removes the last byte and returns it, `None` if the vector is empty.
+  ///
+  pub fn pop(&mut self) -> Option<u8> {
+    if !self.is_inline() {
+      return self.heap_mut().pop();
+    }
+
+    let len = self.len().checked_sub(1)?;
+    self.set_inline_len(len);
+    Some(unsafe { self.repr.inline[DATA_START + len] })
+  }
+
+  /// `push` appends `byte` to the end of the vector, spilling to the heap should it not fit inline.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn push(&mut self, byte: u8) {
+    self.extend_from_slice(&[byte]);
+  }
+
+  /// `reserve` ensures that the vector can hold at least `additional` more bytes without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    handle_reserve(self.try_reserve(additional));
+  }
+
+  /// `shrink_to_fit` moves the bytes back inline if they fit, releasing the allocation, and otherwise shrinks the
+  /// allocation to the length of the vector.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut bytes = minivec::MiniSmallBytes::from(&b"a long identifier"[..]);
+  /// bytes.truncate(3);
+  /// assert!(!bytes.is_inline());
+  ///
+  /// bytes.shrink_to_fit();
+  /// assert!(bytes.is_inline());
+  /// assert_eq!(bytes, b"a l"[..]);
+  /// ```
+  ///
+  pub fn shrink_to_fit(&mut self) {
+    if self.is_inline() {
+      return;
+    }
+
+    if self.len() > INLINE_CAPACITY {
+      self.heap_mut().shrink_to_fit();
+      return;
+    }
+
+    let inline = MiniSmallBytes::from(self.as_slice());
+    *self = inline;
+  }
+
+  /// `truncate` shortens the vector to `len` bytes. It does nothing if the vector is already shorter.
+  ///
+  pub fn truncate(&mut self, len: usize) {
+    if !self.is_inline() {
+      self.heap_mut().truncate(len);
+      return;
+    }
+
+    if len < self.len() {
+      self.set_inline_len(len);
+    }
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniSmallBytes::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    if !self.is_inline() {
+      return self.heap_mut().try_reserve(additional);
+    }
+
+    let len = self.len();
+    if additional <= INLINE_CAPACITY - len {
+      return Ok(());
+    }
+
+    // spilling doubles the inline capacity at the very least so that a few more pushes don't reallocate right away
+    //
+    let capacity = core::cmp::max(len.saturating_add(additional), 2 * INLINE_CAPACITY);
+
+    let mut vec = MiniVec::try_with_capacity(capacity)?;
+    vec.extend_from_slice(self.as_slice());
+
+    self.repr = Repr {
+      heap: core::mem::ManuallyDrop::new(vec),
+    };
+
+    Ok(())
+  }
+
+  /// `with_capacity` creates an empty vector which can hold at least `capacity` bytes without reallocating. It only
+  /// allocates if `capacity` exceeds [`INLINE_CAPACITY`](MiniSmallBytes::INLINE_CAPACITY).
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniSmallBytes {
+    let mut bytes = MiniSmallBytes::new();
+    bytes.reserve(capacity);
+    bytes
+  }
+
+  fn heap_mut(&mut self) -> &mut MiniVec<u8> {
+    debug_assert!(!self.is_inline());
+    unsafe { &mut self.repr.heap }
+  }
+
+  #[allow(clippy::cast_possible_truncation)]
+  fn set_inline_len(&mut self, len: usize) {
+    debug_assert!(self.is_inline() && len <= INLINE_CAPACITY);
+    unsafe { self.repr.inline[TAG_BYTE] = ((len << 1) | 1) as u8 };
+  }
+}
+
+impl Drop for MiniSmallBytes {
+  fn drop(&mut self) {
+    if !self.is_inline() {
+      unsafe { core::mem::ManuallyDrop::drop(&mut self.repr.heap) };
+    }
+  }
+}
+
+impl Clone for MiniSmallBytes {
+  fn clone(&self) -> Self {
+    if self.is_inline() {
+      return MiniSmallBytes {
+        repr: Repr {
+          inline: unsafe { self.repr.inline },
+        },
+      };
+    }
+
+    MiniSmallBytes::from(self.as_slice())
+  }
+}
+
+impl core::ops::Deref for MiniSmallBytes {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    self.as_slice()
+  }
+}
+
+impl core::ops::DerefMut for MiniSmallBytes {
+  fn deref_mut(&mut self) -> &mut [u8] {
+    self.as_mut_slice()
+  }
+}
+
+impl AsRef<[u8]> for MiniSmallBytes {
+  fn as_ref(&self) -> &[u8] {
+    self
+  }
+}
+
+impl AsMut<[u8]> for MiniSmallBytes {
+  fn as_mut(&mut self) -> &mut [u8] {
+    self
+  }
+}
+
+impl core::borrow::Borrow<[u8]> for MiniSmallBytes {
+  fn borrow(&self) -> &[u8] {
+    self
+  }
+}
+
+impl core::borrow::BorrowMut<[u8]> for MiniSmallBytes {
+  fn borrow_mut(&mut self) -> &mut [u8] {
+    self
+  }
+}
+
+impl core::fmt::Debug for MiniSmallBytes {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.as_slice().fmt(f)
+  }
+}
+
+impl Default for MiniSmallBytes {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl core::hash::Hash for MiniSmallBytes {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.as_slice().hash(state);
+  }
+}
+
+impl Eq for MiniSmallBytes {}
+
+impl PartialEq for MiniSmallBytes {
+  fn eq(&self, other: &MiniSmallBytes) -> bool {
+    self.as_slice() == other.as_slice()
+  }
+}
+
+impl PartialEq<[u8]> for MiniSmallBytes {
+  fn eq(&self, other: &[u8]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl PartialEq<&[u8]> for MiniSmallBytes {
+  fn eq(&self, other: &&[u8]) -> bool {
+    self.as_slice() == *other
+  }
+}
+
+impl Ord for MiniSmallBytes {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.as_slice().cmp(other.as_slice())
+  }
+}
+
+impl PartialOrd for MiniSmallBytes {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Extend<u8> for MiniSmallBytes {
+  fn extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+
+    for byte in iter {
+      self.push(byte);
+    }
+  }
+}
+
+impl<'a> Extend<&'a u8> for MiniSmallBytes {
+  fn extend<I: IntoIterator<Item = &'a u8>>(&mut self, iter: I) {
+    self.extend(iter.into_iter().copied());
+  }
+}
+
+impl From<&[u8]> for MiniSmallBytes {
+  fn from(bytes: &[u8]) -> Self {
+    let mut small = MiniSmallBytes::with_capacity(bytes.len());
+    small.extend_from_slice(bytes);
+    small
+  }
+}
+
+impl From<MiniVec<u8>> for MiniSmallBytes {
+  fn from(vec: MiniVec<u8>) -> Self {
+    // a vector without an allocation has nothing worth keeping
+    //
+    if vec.capacity() == 0 {
+      return MiniSmallBytes::new();
+    }
+
+    MiniSmallBytes {
+      repr: Repr {
+        heap: core::mem::ManuallyDrop::new(vec),
+      },
+    }
+  }
+}
+
+impl From<MiniSmallBytes> for MiniVec<u8> {
+  fn from(bytes: MiniSmallBytes) -> Self {
+    if bytes.is_inline() {
+      return MiniVec::from(bytes.as_slice());
+    }
+
+    let mut bytes = core::mem::ManuallyDrop::new(bytes);
+    unsafe { core::mem::ManuallyDrop::take(&mut bytes.repr.heap) }
+  }
+}
+
+impl core::iter::FromIterator<u8> for MiniSmallBytes {
+  fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+    let mut bytes = MiniSmallBytes::new();
+    bytes.extend(iter);
+    bytes
+  }
+}
+
+impl<'a> IntoIterator for &'a MiniSmallBytes {
+  type Item = &'a u8;
+  type IntoIter = core::slice::Iter<'a, u8>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -0,0 +1,525 @@
+use crate::r#impl::helpers::next_aligned;
+use crate::{handle_reserve, Doubling, Global, GrowthPolicy, Header, MiniVec};
+use crate::{Allocator, TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+// generates a struct-of-arrays vector whose allocation holds a single `Header { len, cap }` followed by one column of
+// `cap` elements per type parameter:
+//
+// | Header { len, cap } | A ... | B ... | ...
+//
+// every column is described by its type, its index in the element tuples and the names of its accessors
+//
+macro_rules! mini_soa {
+  (
+    $(#[$meta:meta])*
+    $name:ident[$n:literal] {
+      $($t:ident: $idx:tt, $col:ident, $col_mut:ident;)+
+    }
+  ) => {
+    $(#[$meta])*
+    pub struct $name<$($t),+> {
+      buf: core::ptr::NonNull<u8>,
+      phantom: core::marker::PhantomData<($($t,)+)>,
+    }
+
+    impl<$($t),+> $name<$($t),+> {
+      const ALIGN: usize = {
+        let mut align = core::mem::align_of::<Header<usize>>();
+        $(
+          if core::mem::align_of::<$t>() > align {
+            align = core::mem::align_of::<$t>();
+          }
+        )+
+        align
+      };
+
+      const ELEM_SIZE: usize = 0 $(+ core::mem::size_of::<$t>())+;
+
+      $(
+        #[doc = concat!("`", stringify!($col), "` returns the column of `", stringify!($t), "`s as a slice.")]
+        ///
+        #[must_use]
+        pub fn $col(&self) -> &[$t] {
+          unsafe { core::slice::from_raw_parts(self.column_ptr::<$t>($idx), self.len()) }
+        }
+
+        #[doc = concat!("`", stringify!($col_mut), "` returns the column of `", stringify!($t), "`s as a mutable slice.")]
+        ///
+        pub fn $col_mut(&mut self) -> &mut [$t] {
+          unsafe { core::slice::from_raw_parts_mut(self.column_ptr::<$t>($idx), self.len()) }
+        }
+      )+
+
+      /// `capacity` returns the number of rows the vector can hold without reallocating.
+      ///
+      #[must_use]
+      pub fn capacity(&self) -> usize {
+        self.header().cap
+      }
+
+      /// `clear` removes all of the rows, keeping the allocation.
+      ///
+      pub fn clear(&mut self) {
+        self.truncate(0);
+      }
+
+      /// `columns` returns every column as a slice.
+      ///
+      #[must_use]
+      pub fn columns(&self) -> ($(&[$t],)+) {
+        ($(self.$col(),)+)
+      }
+
+      /// `columns_mut` returns every column as a mutable slice.
+      ///
+      pub fn columns_mut(&mut self) -> ($(&mut [$t],)+) {
+        let len = self.len();
+        unsafe { ($(core::slice::from_raw_parts_mut(self.column_ptr::<$t>($idx), len),)+) }
+      }
+
+      /// `get` returns references to the elements of the row at `index`, `None` if it's out of bounds.
+      ///
+      #[must_use]
+      pub fn get(&self, index: usize) -> Option<($(&$t,)+)> {
+        if index < self.len() {
+          Some(($(&self.$col()[index],)+))
+        } else {
+          None
+        }
+      }
+
+      /// `into_columns` moves every column into its own `MiniVec`.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the allocation of any of the vectors fails.
+      ///
+      #[must_use]
+      pub fn into_columns(mut self) -> ($(MiniVec<$t>,)+) {
+        let len = self.len();
+        let mut columns = ($(MiniVec::<$t>::with_capacity(len),)+);
+
+        unsafe {
+          $(
+            core::ptr::copy_nonoverlapping(self.column_ptr::<$t>($idx), columns.$idx.as_mut_ptr(), len);
+            columns.$idx.set_len(len);
+          )+
+
+          if !self.is_default() {
+            self.header_mut().len = 0;
+          }
+        }
+
+        columns
+      }
+
+      /// `is_empty` returns whether or not the vector contains any rows.
+      ///
+      #[must_use]
+      pub fn is_empty(&self) -> bool {
+        self.len() == 0
+      }
+
+      /// `len` returns the number of rows, which is the length of every column.
+      ///
+      #[must_use]
+      pub fn len(&self) -> usize {
+        self.header().len
+      }
+
+      /// `new` creates an empty vector. It does not allocate.
+      ///
+      #[must_use]
+      pub const fn new() -> Self {
+        $name {
+          buf: MiniVec::<u8>::empty_buf(),
+          phantom: core::marker::PhantomData,
+        }
+      }
+
+      /// `pop` removes the last row and returns its elements, `None` if the vector is empty.
+      ///
+      pub fn pop(&mut self) -> Option<($($t,)+)> {
+        let len = self.len().checked_sub(1)?;
+        self.header_mut().len = len;
+
+        unsafe { Some(($(core::ptr::read(self.column_ptr::<$t>($idx).add(len)),)+)) }
+      }
+
+      /// `push` appends a row to the end of the vector, one element per column.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the new capacity overflows.
+      ///
+      pub fn push(&mut self, row: ($($t,)+)) {
+        self.reserve(1);
+
+        let len = self.len();
+        unsafe {
+          $(core::ptr::write(self.column_ptr::<$t>($idx).add(len), row.$idx);)+
+        }
+
+        self.header_mut().len = len + 1;
+      }
+
+      /// `reserve` ensures that the vector can hold at least `additional` more rows without reallocating.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the new capacity overflows.
+      ///
+      pub fn reserve(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve(additional));
+      }
+
+      /// `sort_by_key` sorts the rows by the key `f` extracts from them, permuting every column the same way. The sort
+      /// is stable.
+      ///
+      pub fn sort_by_key<K, F>(&mut self, mut f: F)
+      where
+        K: Ord,
+        F: FnMut(($(&$t,)+)) -> K,
+      {
+        let len = self.len();
+
+        // the rows are sorted by index first so that the columns are only touched once the new order is known,
+        // `perm[i]` being the index of the row which ends up at `i`
+        //
+        let mut perm: MiniVec<usize> = (0..len).collect();
+        {
+          let columns = self.columns();
+          perm.sort_by_key(|&i| f(($(&columns.$idx[i],)+)));
+        }
+
+        // the permutation is applied one cycle at a time, marking every visited row with `usize::MAX`
+        //
+        let ptrs = ($(self.column_ptr::<$t>($idx),)+);
+
+        for start in 0..len {
+          if perm[start] == usize::MAX {
+            continue;
+          }
+
+          unsafe {
+            let row = ($(core::ptr::read(ptrs.$idx.add(start)),)+);
+            let mut dst = start;
+
+            loop {
+              let src = perm[dst];
+              perm[dst] = usize::MAX;
+
+              if src == start {
+                $(core::ptr::write(ptrs.$idx.add(dst), row.$idx);)+
+                break;
+              }
+
+              $(core::ptr::copy_nonoverlapping(ptrs.$idx.add(src), ptrs.$idx.add(dst), 1);)+
+              dst = src;
+            }
+          }
+        }
+      }
+
+      /// `swap_remove` removes the row at `index` and returns its elements, replacing it with the last row.
+      ///
+      /// # Panics
+      ///
+      /// Panics if `index >= len()`.
+      ///
+      pub fn swap_remove(&mut self, index: usize) -> ($($t,)+) {
+        let len = self.len();
+
+        assert!(
+          (index < len),
+          "swap_remove index (is {}) should be < len (is {})",
+          index,
+          len
+        );
+
+        self.header_mut().len = len - 1;
+
+        unsafe {
+          let row = ($(core::ptr::read(self.column_ptr::<$t>($idx).add(index)),)+);
+          $(
+            let p = self.column_ptr::<$t>($idx);
+            core::ptr::copy(p.add(len - 1), p.add(index), 1);
+          )+
+          row
+        }
+      }
+
+      /// `truncate` shortens the vector to `len` rows, dropping the rest. It does nothing if the vector is already
+      /// shorter.
+      ///
+      pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+          return;
+        }
+
+        self.header_mut().len = len;
+
+        unsafe {
+          $(
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+              self.column_ptr::<$t>($idx).add(len),
+              old_len - len,
+            ));
+          )+
+        }
+      }
+
+      /// `try_reserve` is the fallible version of [`reserve`](Self::reserve).
+      ///
+      /// # Errors
+      ///
+      /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+      ///
+      pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self
+          .len()
+          .checked_add(additional)
+          .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+        let cap = self.capacity();
+        if required <= cap {
+          return Ok(());
+        }
+
+        // rows of ZSTs don't take up any room so an allocated vector of them can hold as many as `len` can count
+        //
+        let new_cap = if Self::ELEM_SIZE == 0 {
+          usize::MAX
+        } else {
+          core::cmp::max(Doubling::next_capacity(Self::ELEM_SIZE, cap, required), required)
+        };
+
+        self.grow(new_cap)
+      }
+
+      /// `with_capacity` creates an empty vector which can hold at least `capacity` rows without reallocating.
+      ///
+      /// # Panics
+      ///
+      /// Panics if the capacity overflows.
+      ///
+      #[must_use]
+      pub fn with_capacity(capacity: usize) -> Self {
+        let mut vec = Self::new();
+        vec.reserve(capacity);
+        vec
+      }
+
+      fn grow(&mut self, cap: usize) -> Result<(), TryReserveError> {
+        let (offsets, layout) = Self::layout(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+        let buf = Global
+          .allocate(layout)
+          .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+          .cast::<u8>();
+
+        let len = self.len();
+
+        unsafe {
+          $(
+            core::ptr::copy_nonoverlapping(
+              self.column_ptr::<$t>($idx),
+              buf.as_ptr().add(offsets[$idx]).cast::<$t>(),
+              len,
+            );
+          )+
+
+          #[allow(clippy::cast_ptr_alignment)]
+          core::ptr::write(buf.as_ptr().cast::<Header<usize>>(), Header { len, cap });
+
+          if !self.is_default() {
+            let (_, old_layout) = Self::layout(self.capacity()).unwrap_unchecked();
+            Global.deallocate(self.buf, old_layout);
+          }
+        }
+
+        self.buf = buf;
+        Ok(())
+      }
+
+      // the offset of every column for a capacity of `cap` rows along with the layout of the whole allocation, `None`
+      // when it'd be too large to be described by a `Layout`
+      //
+      fn layout(cap: usize) -> Option<([usize; $n], alloc::alloc::Layout)> {
+        let mut end = core::mem::size_of::<Header<usize>>();
+        let mut offsets = [0; $n];
+
+        $(
+          offsets[$idx] = next_aligned(end, core::mem::align_of::<$t>());
+          end = offsets[$idx].checked_add(cap.checked_mul(core::mem::size_of::<$t>())?)?;
+
+          if end > isize::MAX as usize {
+            return None;
+          }
+        )+
+
+        let layout = alloc::alloc::Layout::from_size_align(end, Self::ALIGN).ok()?;
+        Some((offsets, layout))
+      }
+
+      fn column_ptr<Elem>(&self, idx: usize) -> *mut Elem {
+        if self.is_default() {
+          return core::ptr::NonNull::dangling().as_ptr();
+        }
+
+        unsafe {
+          let (offsets, _) = Self::layout(self.capacity()).unwrap_unchecked();
+
+          #[allow(clippy::cast_ptr_alignment)]
+          self.buf.as_ptr().add(offsets[idx]).cast::<Elem>()
+        }
+      }
+
+      fn header(&self) -> &Header<usize> {
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+          &*self.buf.as_ptr().cast::<Header<usize>>()
+        }
+      }
+
+      fn header_mut(&mut self) -> &mut Header<usize> {
+        debug_assert!(!self.is_default());
+
+        #[allow(clippy::cast_ptr_alignment)]
+        unsafe {
+          &mut *self.buf.as_ptr().cast::<Header<usize>>()
+        }
+      }
+
+      fn is_default(&self) -> bool {
+        core::ptr::eq(self.buf.as_ptr(), MiniVec::<u8>::empty_buf().as_ptr())
+      }
+    }
+
+    impl<$($t),+> Drop for $name<$($t),+> {
+      fn drop(&mut self) {
+        if self.is_default() {
+          return;
+        }
+
+        self.clear();
+
+        unsafe {
+          let (_, layout) = Self::layout(self.capacity()).unwrap_unchecked();
+          Global.deallocate(self.buf, layout);
+        }
+      }
+    }
+
+    impl<$($t: Clone),+> Clone for $name<$($t),+> {
+      fn clone(&self) -> Self {
+        let mut vec = Self::with_capacity(self.len());
+        for i in 0..self.len() {
+          vec.push(($(self.$col()[i].clone(),)+));
+        }
+
+        vec
+      }
+    }
+
+    impl<$($t: core::fmt::Debug),+> core::fmt::Debug for $name<$($t),+> {
+      fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+          .entries((0..self.len()).map(|i| ($(&self.$col()[i],)+)))
+          .finish()
+      }
+    }
+
+    impl<$($t),+> Default for $name<$($t),+> {
+      fn default() -> Self {
+        Self::new()
+      }
+    }
+
+    impl<$($t: Eq),+> Eq for $name<$($t),+> {}
+
+    impl<$($t: PartialEq),+> PartialEq for $name<$($t),+> {
+      fn eq(&self, other: &Self) -> bool {
+        true $(&& self.$col() == other.$col())+
+      }
+    }
+
+    impl<$($t),+> Extend<($($t,)+)> for $name<$($t),+> {
+      fn extend<I: IntoIterator<Item = ($($t,)+)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for row in iter {
+          self.push(row);
+        }
+      }
+    }
+
+    impl<$($t),+> core::iter::FromIterator<($($t,)+)> for $name<$($t),+> {
+      fn from_iter<I: IntoIterator<Item = ($($t,)+)>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+      }
+    }
+
+    unsafe impl<$($t: Send),+> Send for $name<$($t),+> {}
+    unsafe impl<$($t: Sync),+> Sync for $name<$($t),+> {}
+  };
+}
+
+mini_soa! {
+  /// `MiniVec2` is a struct-of-arrays vector of two columns which always have the same length, stored behind a
+  /// single header in a single allocation. It's only the size of a single pointer, where a pair of parallel
+  /// `MiniVec`s would be two pointers, two headers and two allocations.
+  ///
+  /// Rows are pushed and popped as tuples while every column is available as a contiguous slice. The columns are
+  /// reallocated together and grow as described by [`GrowthPolicy`](crate::GrowthPolicy).
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut particles = minivec::MiniVec2::new();
+  /// particles.push((3.0_f32, 'c'));
+  /// particles.push((1.0, 'a'));
+  /// particles.push((2.0, 'b'));
+  ///
+  /// particles.sort_by_key(|(_, id)| *id);
+  /// assert_eq!(particles.column_a(), [1.0, 2.0, 3.0]);
+  ///
+  /// particles.column_b_mut()[0] = 'z';
+  /// assert_eq!(particles.swap_remove(0), (1.0, 'z'));
+  ///
+  /// let (positions, ids) = particles.into_columns();
+  /// assert_eq!(positions, [3.0, 2.0]);
+  /// assert_eq!(ids, ['c', 'b']);
+  /// ```
+  ///
+  MiniVec2[2] {
+    A: 0, column_a, column_a_mut;
+    B: 1, column_b, column_b_mut;
+  }
+}
+
+mini_soa! {
+  /// `MiniVec3` is the three-column version of [`MiniVec2`].
+  ///
+  MiniVec3[3] {
+    A: 0, column_a, column_a_mut;
+    B: 1, column_b, column_b_mut;
+    C: 2, column_c, column_c_mut;
+  }
+}
+
+mini_soa! {
+  /// `MiniVec4` is the four-column version of [`MiniVec2`].
+  ///
+  MiniVec4[4] {
+    A: 0, column_a, column_a_mut;
+    B: 1, column_b, column_b_mut;
+    C: 2, column_c, column_c_mut;
+    D: 3, column_d, column_d_mut;
+  }
+}
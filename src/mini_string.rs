@@ -0,0 +1,909 @@
+use crate::{handle_reserve, normalize_range, MiniVec, TryReserveError};
+
+extern crate alloc;
+
+/// `MiniString` is a growable UTF-8 string backed by a [`MiniVec<u8>`](MiniVec), making it only the size of a single
+/// pointer.
+///
+/// It mirrors the API of `alloc::string::String` and dereferences to `str` so the read-only string API is available
+/// directly. Comparisons, ordering and hashing all behave the same as they do for the equivalent `str`.
+///
+/// # Example
+///
+/// ```
+/// use minivec::MiniString;
+///
+/// let mut s = MiniString::from("hello");
+/// s.push_str(", world");
+/// s.push('!');
+///
+/// assert_eq!(s, "hello, world!");
+/// assert_eq!(core::mem::size_of::<MiniString>(), core::mem::size_of::<usize>());
+/// ```
+///
+#[derive(Clone, Default)]
+#[repr(transparent)]
+pub struct MiniString {
+  vec: MiniVec<u8>,
+}
+
+/// `FromUtf8Error` is the error type returned from [`MiniString::from_utf8`] when the supplied bytes are not valid
+/// UTF-8. The bytes can be recovered through [`into_bytes`](FromUtf8Error::into_bytes).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromUtf8Error {
+  bytes: MiniVec<u8>,
+  error: core::str::Utf8Error,
+}
+
+impl FromUtf8Error {
+  /// `as_bytes` returns the bytes that failed to convert.
+  ///
+  #[must_use]
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.bytes
+  }
+
+  /// `into_bytes` returns ownership of the bytes that failed to convert.
+  ///
+  #[must_use]
+  pub fn into_bytes(self) -> MiniVec<u8> {
+    self.bytes
+  }
+
+  /// `utf8_error` returns the underlying `Utf8Error` describing where the conversion failed.
+  ///
+  #[must_use]
+  pub fn utf8_error(&self) -> core::str::Utf8Error {
+    self.error
+  }
+}
+
+impl core::fmt::Display for FromUtf8Error {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    core::fmt::Display::fmt(&self.error, f)
+  }
+}
+
+impl MiniString {
+  /// `as_bytes` returns the contents of the string as a byte slice.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::from("abc");
+  /// assert_eq!(s.as_bytes(), b"abc");
+  /// ```
+  ///
+  #[must_use]
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.vec
+  }
+
+  /// `as_mut_str` returns a mutable string slice over the entire contents of the string.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("abc");
+  /// s.as_mut_str().make_ascii_uppercase();
+  ///
+  /// assert_eq!(s, "ABC");
+  /// ```
+  ///
+  pub fn as_mut_str(&mut self) -> &mut str {
+    unsafe { core::str::from_utf8_unchecked_mut(&mut self.vec) }
+  }
+
+  /// `as_mut_vec` returns a mutable reference to the underlying `MiniVec<u8>`.
+  ///
+  /// # Safety
+  ///
+  /// The caller must ensure the bytes are valid UTF-8 once the borrow ends.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("abc");
+  /// unsafe { s.as_mut_vec().push(b'd') };
+  ///
+  /// assert_eq!(s, "abcd");
+  /// ```
+  ///
+  pub unsafe fn as_mut_vec(&mut self) -> &mut MiniVec<u8> {
+    &mut self.vec
+  }
+
+  /// `as_str` returns a string slice over the entire contents of the string.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::from("abc");
+  /// assert_eq!(s.as_str(), "abc");
+  /// ```
+  ///
+  #[must_use]
+  pub fn as_str(&self) -> &str {
+    unsafe { core::str::from_utf8_unchecked(&self.vec) }
+  }
+
+  /// `capacity` returns the number of bytes the string can hold without reallocating.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::with_capacity(16);
+  /// assert_eq!(s.capacity(), 16);
+  /// ```
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.vec.capacity()
+  }
+
+  /// `clear` truncates the string to a length of `0`, leaving its capacity untouched.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("abc");
+  /// s.clear();
+  ///
+  /// assert!(s.is_empty());
+  /// assert_eq!(s.capacity(), 3);
+  /// ```
+  ///
+  pub fn clear(&mut self) {
+    self.vec.clear();
+  }
+
+  /// `drain` returns a [`StringDrain`] iterator which yields the `char`s in the supplied byte `range` and removes
+  /// them from the string once dropped, even if it was never iterated.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the range is out of bounds or if either end of the range does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("α is alpha, β is beta");
+  /// let beta_offset = s.find('β').unwrap();
+  ///
+  /// let t: minivec::MiniString = s.drain(..beta_offset).collect();
+  ///
+  /// assert_eq!(t, "α is alpha, ");
+  /// assert_eq!(s, "β is beta");
+  /// ```
+  ///
+  pub fn drain<R>(&mut self, range: R) -> StringDrain<'_>
+  where
+    R: core::ops::RangeBounds<usize>,
+  {
+    let (start, end) = match normalize_range(&range, self.len()) {
+      Ok(range) => range,
+      Err(e) => panic!("invalid drain range: {}", e),
+    };
+
+    assert!(
+      self.is_char_boundary(start),
+      "drain start (is {}) is not a char boundary",
+      start
+    );
+    assert!(
+      self.is_char_boundary(end),
+      "drain end (is {}) is not a char boundary",
+      end
+    );
+
+    // the iterator borrows the drained region while the pointer is only used to remove it once the iterator is
+    // dropped
+    //
+    let string: *mut MiniString = self;
+    let iter = unsafe { self.get_unchecked(start..end) }.chars();
+
+    StringDrain {
+      string,
+      start,
+      end,
+      iter,
+    }
+  }
+
+  /// `from_utf8` converts a vector of bytes into a `MiniString` without copying them, failing if the bytes are not
+  /// valid UTF-8.
+  ///
+  /// # Errors
+  ///
+  /// Returns a [`FromUtf8Error`] if `vec` contains invalid UTF-8. The original bytes can be recovered from it.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::from_utf8(minivec::mini_vec![b'h', b'i']).unwrap();
+  /// assert_eq!(s, "hi");
+  ///
+  /// let err = minivec::MiniString::from_utf8(minivec::mini_vec![0xff, b'i']).unwrap_err();
+  /// assert_eq!(err.into_bytes(), [0xff, b'i']);
+  /// ```
+  ///
+  pub fn from_utf8(vec: MiniVec<u8>) -> Result<MiniString, FromUtf8Error> {
+    match core::str::from_utf8(&vec) {
+      Ok(_) => Ok(MiniString { vec }),
+      Err(error) => Err(FromUtf8Error { bytes: vec, error }),
+    }
+  }
+
+  /// `from_utf8_lossy` converts a slice of bytes into a `MiniString`, replacing each invalid UTF-8 sequence with
+  /// `U+FFFD REPLACEMENT CHARACTER`.
+  ///
+  /// Unlike `String::from_utf8_lossy`, this always returns an owned string.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+  /// assert_eq!(s, "Hello \u{FFFD}World");
+  /// ```
+  ///
+  #[must_use]
+  pub fn from_utf8_lossy(v: &[u8]) -> MiniString {
+    let mut s = MiniString::with_capacity(v.len());
+
+    for chunk in v.utf8_chunks() {
+      s.push_str(chunk.valid());
+      if !chunk.invalid().is_empty() {
+        s.push(char::REPLACEMENT_CHARACTER);
+      }
+    }
+
+    s
+  }
+
+  /// `from_utf8_unchecked` converts a vector of bytes into a `MiniString` without checking that they're valid UTF-8.
+  ///
+  /// # Safety
+  ///
+  /// `vec` must contain valid UTF-8.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = unsafe { minivec::MiniString::from_utf8_unchecked(minivec::mini_vec![b'h', b'i']) };
+  /// assert_eq!(s, "hi");
+  /// ```
+  ///
+  #[must_use]
+  pub unsafe fn from_utf8_unchecked(vec: MiniVec<u8>) -> MiniString {
+    MiniString { vec }
+  }
+
+  /// `insert` inserts `ch` at the byte position `idx`, shifting everything after it to the right.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is greater than the length of the string or if it does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("ac");
+  /// s.insert(1, 'b');
+  ///
+  /// assert_eq!(s, "abc");
+  /// ```
+  ///
+  pub fn insert(&mut self, idx: usize, ch: char) {
+    assert!(
+      self.is_char_boundary(idx),
+      "insertion index is not a char boundary"
+    );
+
+    let mut bytes = [0; 4];
+    self.insert_bytes(idx, ch.encode_utf8(&mut bytes).as_bytes());
+  }
+
+  /// `insert_str` inserts the string slice `string` at the byte position `idx`, shifting everything after it to the
+  /// right.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is greater than the length of the string or if it does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("world");
+  /// s.insert_str(0, "hello ");
+  ///
+  /// assert_eq!(s, "hello world");
+  /// ```
+  ///
+  pub fn insert_str(&mut self, idx: usize, string: &str) {
+    assert!(
+      self.is_char_boundary(idx),
+      "insertion index is not a char boundary"
+    );
+
+    self.insert_bytes(idx, string.as_bytes());
+  }
+
+  /// `into_bytes` converts the string into its underlying `MiniVec<u8>` without copying.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::from("hi");
+  /// assert_eq!(s.into_bytes(), [b'h', b'i']);
+  /// ```
+  ///
+  #[must_use]
+  pub fn into_bytes(self) -> MiniVec<u8> {
+    self.vec
+  }
+
+  /// `new` constructs an empty `MiniString`. Much like [`MiniVec::new()`](MiniVec::new), this does not allocate.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::new();
+  /// assert!(s.is_empty());
+  /// assert_eq!(s.capacity(), 0);
+  /// ```
+  ///
+  #[must_use]
+  pub const fn new() -> MiniString {
+    MiniString {
+      vec: MiniVec::new(),
+    }
+  }
+
+  /// `pop` removes the last `char` from the string and returns it, should it exist.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("aβ");
+  ///
+  /// assert_eq!(s.pop(), Some('β'));
+  /// assert_eq!(s.pop(), Some('a'));
+  /// assert_eq!(s.pop(), None);
+  /// ```
+  ///
+  pub fn pop(&mut self) -> Option<char> {
+    let ch = self.chars().next_back()?;
+    let new_len = self.len() - ch.len_utf8();
+    unsafe { self.vec.set_len(new_len) };
+    Some(ch)
+  }
+
+  /// `push` appends `ch` to the end of the string.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::new();
+  /// s.push('a');
+  /// s.push('β');
+  ///
+  /// assert_eq!(s, "aβ");
+  /// ```
+  ///
+  pub fn push(&mut self, ch: char) {
+    let mut bytes = [0; 4];
+    self.push_str(ch.encode_utf8(&mut bytes));
+  }
+
+  /// `push_str` appends the string slice `string` to the end of the string.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("foo");
+  /// s.push_str("bar");
+  ///
+  /// assert_eq!(s, "foobar");
+  /// ```
+  ///
+  pub fn push_str(&mut self, string: &str) {
+    self.vec.extend_from_slice(string.as_bytes());
+  }
+
+  /// `remove` removes the `char` starting at the byte position `idx` and returns it.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `idx` is not less than the length of the string or if it does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("aβc");
+  ///
+  /// assert_eq!(s.remove(1), 'β');
+  /// assert_eq!(s, "ac");
+  /// ```
+  ///
+  pub fn remove(&mut self, idx: usize) -> char {
+    let Some(ch) = self[idx..].chars().next() else {
+      panic!("cannot remove a char from the end of a string");
+    };
+
+    self.vec.drain(idx..idx + ch.len_utf8());
+    ch
+  }
+
+  /// `reserve` ensures the string can hold at least `additional` more bytes, growing it as required.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::new();
+  /// s.reserve(10);
+  ///
+  /// assert!(s.capacity() >= 10);
+  /// ```
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    self.vec.reserve(additional);
+  }
+
+  /// `reserve_exact` ensures the string can hold at least `additional` more bytes, without over-allocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::new();
+  /// s.reserve_exact(10);
+  ///
+  /// assert_eq!(s.capacity(), 10);
+  /// ```
+  ///
+  pub fn reserve_exact(&mut self, additional: usize) {
+    self.vec.reserve_exact(additional);
+  }
+
+  /// `retain` removes every `char` for which `f` returns `false`, visiting each exactly once in order.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("f_o_ob_ar");
+  /// s.retain(|c| c != '_');
+  ///
+  /// assert_eq!(s, "foobar");
+  /// ```
+  ///
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(char) -> bool,
+  {
+    // should `f` panic, the string is cut short at the last retained `char` so it always remains valid UTF-8
+    //
+    struct SetLenOnDrop<'a> {
+      s: &'a mut MiniString,
+      idx: usize,
+      deleted: usize,
+    }
+
+    impl Drop for SetLenOnDrop<'_> {
+      fn drop(&mut self) {
+        let new_len = self.idx - self.deleted;
+        unsafe { self.s.vec.set_len(new_len) };
+      }
+    }
+
+    let len = self.len();
+    let mut guard = SetLenOnDrop {
+      s: self,
+      idx: 0,
+      deleted: 0,
+    };
+
+    while guard.idx < len {
+      let ch = unsafe {
+        guard
+          .s
+          .get_unchecked(guard.idx..len)
+          .chars()
+          .next()
+          .unwrap_unchecked()
+      };
+      let ch_len = ch.len_utf8();
+
+      if !f(ch) {
+        guard.deleted += ch_len;
+      } else if guard.deleted > 0 {
+        unsafe {
+          let p = guard.s.vec.as_mut_ptr();
+          core::ptr::copy(p.add(guard.idx), p.add(guard.idx - guard.deleted), ch_len);
+        }
+      }
+
+      guard.idx += ch_len;
+    }
+  }
+
+  /// `shrink_to_fit` reduces the capacity of the string to its length.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::with_capacity(32);
+  /// s.push_str("abc");
+  /// s.shrink_to_fit();
+  ///
+  /// assert_eq!(s.capacity(), 3);
+  /// ```
+  ///
+  pub fn shrink_to_fit(&mut self) {
+    self.vec.shrink_to_fit();
+  }
+
+  /// `split_off` splits the string in two at the byte position `at`, returning everything from `at` onwards.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `at` is greater than the length of the string or if it does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("Hello, World!");
+  /// let world = s.split_off(7);
+  ///
+  /// assert_eq!(s, "Hello, ");
+  /// assert_eq!(world, "World!");
+  /// ```
+  ///
+  #[must_use]
+  pub fn split_off(&mut self, at: usize) -> MiniString {
+    assert!(
+      self.is_char_boundary(at),
+      "split index is not a char boundary"
+    );
+
+    MiniString {
+      vec: self.vec.split_off(at),
+    }
+  }
+
+  /// `truncate` shortens the string to `new_len` bytes. Nothing happens if `new_len` is greater than or equal to the
+  /// string's current length.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `new_len` does not lie on a `char` boundary.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::from("hello");
+  /// s.truncate(2);
+  ///
+  /// assert_eq!(s, "he");
+  /// ```
+  ///
+  pub fn truncate(&mut self, new_len: usize) {
+    if new_len <= self.len() {
+      assert!(
+        self.is_char_boundary(new_len),
+        "new length is not a char boundary"
+      );
+      self.vec.truncate(new_len);
+    }
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniString::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the string could not grow.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut s = minivec::MiniString::new();
+  /// assert!(s.try_reserve(16).is_ok());
+  /// assert!(s.try_reserve(usize::MAX).is_err());
+  /// ```
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.vec.try_reserve(additional)
+  }
+
+  /// `with_capacity` constructs an empty `MiniString` able to hold at least `capacity` bytes without reallocating.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let s = minivec::MiniString::with_capacity(16);
+  /// assert!(s.is_empty());
+  /// assert_eq!(s.capacity(), 16);
+  /// ```
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniString {
+    MiniString {
+      vec: MiniVec::with_capacity(capacity),
+    }
+  }
+
+  fn insert_bytes(&mut self, idx: usize, bytes: &[u8]) {
+    let len = self.len();
+    let amt = bytes.len();
+    handle_reserve(self.vec.try_reserve(amt));
+
+    unsafe {
+      let p = self.vec.as_mut_ptr();
+      core::ptr::copy(p.add(idx), p.add(idx + amt), len - idx);
+      core::ptr::copy_nonoverlapping(bytes.as_ptr(), p.add(idx), amt);
+      self.vec.set_len(len + amt);
+    }
+  }
+}
+
+/// `StringDrain` is the iterator returned from [`MiniString::drain`]. It yields the `char`s of the drained range and
+/// removes the range from the string once dropped.
+///
+pub struct StringDrain<'a> {
+  string: *mut MiniString,
+  start: usize,
+  end: usize,
+  iter: core::str::Chars<'a>,
+}
+
+impl StringDrain<'_> {
+  /// `as_str` returns the part of the drained range that hasn't been yielded yet.
+  ///
+  #[must_use]
+  pub fn as_str(&self) -> &str {
+    self.iter.as_str()
+  }
+}
+
+impl Iterator for StringDrain<'_> {
+  type Item = char;
+
+  fn next(&mut self) -> Option<char> {
+    self.iter.next()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+
+  fn last(mut self) -> Option<char> {
+    self.next_back()
+  }
+}
+
+impl DoubleEndedIterator for StringDrain<'_> {
+  fn next_back(&mut self) -> Option<char> {
+    self.iter.next_back()
+  }
+}
+
+impl core::iter::FusedIterator for StringDrain<'_> {}
+
+impl Drop for StringDrain<'_> {
+  fn drop(&mut self) {
+    unsafe {
+      (*self.string).vec.drain(self.start..self.end);
+    }
+  }
+}
+
+impl core::ops::Deref for MiniString {
+  type Target = str;
+
+  fn deref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl core::ops::DerefMut for MiniString {
+  fn deref_mut(&mut self) -> &mut str {
+    self.as_mut_str()
+  }
+}
+
+impl AsRef<str> for MiniString {
+  fn as_ref(&self) -> &str {
+    self
+  }
+}
+
+impl AsRef<[u8]> for MiniString {
+  fn as_ref(&self) -> &[u8] {
+    self.as_bytes()
+  }
+}
+
+impl AsMut<str> for MiniString {
+  fn as_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
+impl core::borrow::Borrow<str> for MiniString {
+  fn borrow(&self) -> &str {
+    self
+  }
+}
+
+impl core::borrow::BorrowMut<str> for MiniString {
+  fn borrow_mut(&mut self) -> &mut str {
+    self
+  }
+}
+
+impl core::fmt::Debug for MiniString {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    core::fmt::Debug::fmt(self.as_str(), f)
+  }
+}
+
+impl core::fmt::Display for MiniString {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    core::fmt::Display::fmt(self.as_str(), f)
+  }
+}
+
+impl core::fmt::Write for MiniString {
+  fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    self.push_str(s);
+    Ok(())
+  }
+
+  fn write_char(&mut self, c: char) -> core::fmt::Result {
+    self.push(c);
+    Ok(())
+  }
+}
+
+impl core::hash::Hash for MiniString {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.as_str().hash(state);
+  }
+}
+
+impl Eq for MiniString {}
+
+impl PartialEq for MiniString {
+  fn eq(&self, other: &MiniString) -> bool {
+    self.as_str() == other.as_str()
+  }
+}
+
+impl Ord for MiniString {
+  fn cmp(&self, other: &MiniString) -> core::cmp::Ordering {
+    self.as_str().cmp(other.as_str())
+  }
+}
+
+impl PartialOrd for MiniString {
+  fn partial_cmp(&self, other: &MiniString) -> Option<core::cmp::Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+macro_rules! mini_string_partial_eq {
+  ($($t:ty),*) => {
+    $(
+      impl PartialEq<$t> for MiniString {
+        fn eq(&self, other: &$t) -> bool {
+          self.as_str() == &other[..]
+        }
+      }
+
+      impl PartialEq<MiniString> for $t {
+        fn eq(&self, other: &MiniString) -> bool {
+          &self[..] == other.as_str()
+        }
+      }
+    )*
+  };
+}
+
+mini_string_partial_eq!(str, &str, alloc::string::String);
+
+impl core::ops::Add<&str> for MiniString {
+  type Output = MiniString;
+
+  fn add(mut self, other: &str) -> MiniString {
+    self.push_str(other);
+    self
+  }
+}
+
+impl core::ops::AddAssign<&str> for MiniString {
+  fn add_assign(&mut self, other: &str) {
+    self.push_str(other);
+  }
+}
+
+impl Extend<char> for MiniString {
+  fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+    iter.for_each(|c| self.push(c));
+  }
+}
+
+impl<'a> Extend<&'a str> for MiniString {
+  fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+    iter.into_iter().for_each(|s| self.push_str(s));
+  }
+}
+
+impl core::iter::FromIterator<char> for MiniString {
+  fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> MiniString {
+    let mut s = MiniString::new();
+    s.extend(iter);
+    s
+  }
+}
+
+impl<'a> core::iter::FromIterator<&'a str> for MiniString {
+  fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> MiniString {
+    let mut s = MiniString::new();
+    s.extend(iter);
+    s
+  }
+}
+
+impl From<&str> for MiniString {
+  fn from(s: &str) -> MiniString {
+    MiniString {
+      vec: MiniVec::from(s),
+    }
+  }
+}
+
+impl From<char> for MiniString {
+  fn from(c: char) -> MiniString {
+    let mut s = MiniString::new();
+    s.push(c);
+    s
+  }
+}
+
+impl From<alloc::string::String> for MiniString {
+  fn from(s: alloc::string::String) -> MiniString {
+    MiniString::from(s.as_str())
+  }
+}
+
+impl From<MiniString> for alloc::string::String {
+  fn from(s: MiniString) -> alloc::string::String {
+    alloc::string::String::from(s.as_str())
+  }
+}
+
+impl From<MiniString> for MiniVec<u8> {
+  fn from(s: MiniString) -> MiniVec<u8> {
+    s.into_bytes()
+  }
+}
+
+impl core::str::FromStr for MiniString {
+  type Err = core::convert::Infallible;
+
+  fn from_str(s: &str) -> Result<MiniString, Self::Err> {
+    Ok(MiniString::from(s))
+  }
+}
@@ -0,0 +1,951 @@
+use crate::r#impl::helpers::{
+  header_bytes, make_aligned_layout, make_prefixed_layout, max_align, prefixed_data_offset,
+};
+use crate::{handle_reserve, normalize_range, Doubling, Global, GrowthPolicy, IndexType, MiniVec};
+use crate::{Allocator, TryReserveError, TryReserveErrorKind};
+
+extern crate alloc;
+
+// the elements live in `[head, head + len)`, wrapping around to the front of the buffer once they reach `cap`
+//
+#[repr(C)]
+struct DequeHeader {
+  head: usize,
+  len: usize,
+  cap: usize,
+}
+
+static EMPTY_DEQUE_HEADER: DequeHeader = DequeHeader {
+  head: 0,
+  len: 0,
+  cap: 0,
+};
+
+/// `MiniVecDeque` is a growable ring buffer that's only the size of a single pointer, the counterpart of
+/// `alloc::collections::VecDeque`. The position of its first element, its length and its capacity are all stored in
+/// the same allocation as the elements.
+///
+/// Elements can be pushed and popped at either end in amortized constant time. See [`GrowthPolicy`](crate::GrowthPolicy)
+/// for how the capacity grows.
+///
+/// A `MiniVecDeque` is created from a [`MiniVec`] by reusing its allocation whenever the vector's header has room for
+/// the position of the first element and by moving the elements into a new allocation otherwise. The same holds for
+/// the conversion back into a `MiniVec`.
+///
+/// # Example
+///
+/// ```
+/// let mut deque = minivec::MiniVecDeque::new();
+/// deque.push_back(2);
+/// deque.push_back(3);
+/// deque.push_front(1);
+///
+/// assert_eq!(deque, [1, 2, 3]);
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.pop_back(), Some(3));
+/// assert_eq!(core::mem::size_of_val(&deque), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct MiniVecDeque<T> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<T>,
+}
+
+/// `DequeDrain` is the iterator returned by [`MiniVecDeque::drain`], it removes the drained elements from the deque
+/// once dropped.
+///
+pub struct DequeDrain<'a, T> {
+  deque: core::ptr::NonNull<MiniVecDeque<T>>,
+  idx: usize,
+  end: usize,
+  range: (usize, usize),
+  len: usize,
+  marker: core::marker::PhantomData<&'a mut MiniVecDeque<T>>,
+}
+
+/// `DequeIntoIter` is the iterator returned by the `IntoIterator` implementation of [`MiniVecDeque`].
+///
+pub struct DequeIntoIter<T> {
+  deque: MiniVecDeque<T>,
+}
+
+const fn layout<T>(cap: usize) -> Option<alloc::alloc::Layout> {
+  make_prefixed_layout::<DequeHeader, T>(cap)
+}
+
+impl<T> MiniVecDeque<T> {
+  const OFFSET: usize = prefixed_data_offset::<DequeHeader, T>();
+
+  /// `as_mut_slices` returns the elements as two mutable slices, the first one holding the front of the deque.
+  ///
+  pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+    let (front, back) = self.slice_ranges();
+    let data = self.data();
+
+    unsafe {
+      (
+        core::slice::from_raw_parts_mut(data.add(front.0), front.1 - front.0),
+        core::slice::from_raw_parts_mut(data.add(back.0), back.1 - back.0),
+      )
+    }
+  }
+
+  /// `as_slices` returns the elements as two slices, the first one holding the front of the deque. The second slice is
+  /// empty whenever the elements are contiguous in memory.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut deque = minivec::MiniVecDeque::with_capacity(4);
+  /// deque.push_back(2);
+  /// deque.push_back(3);
+  /// deque.push_front(1);
+  ///
+  /// let (front, back) = deque.as_slices();
+  /// assert_eq!(front.len() + back.len(), 3);
+  /// assert_eq!(front[0], 1);
+  /// assert_eq!(back, [2, 3]);
+  /// ```
+  ///
+  #[must_use]
+  pub fn as_slices(&self) -> (&[T], &[T]) {
+    let (front, back) = self.slice_ranges();
+    let data = self.data();
+
+    unsafe {
+      (
+        core::slice::from_raw_parts(data.add(front.0), front.1 - front.0),
+        core::slice::from_raw_parts(data.add(back.0), back.1 - back.0),
+      )
+    }
+  }
+
+  /// `back` returns a reference to the last element, should it exist.
+  ///
+  #[must_use]
+  pub fn back(&self) -> Option<&T> {
+    self.get(self.len().wrapping_sub(1))
+  }
+
+  /// `back_mut` returns a mutable reference to the last element, should it exist.
+  ///
+  pub fn back_mut(&mut self) -> Option<&mut T> {
+    self.get_mut(self.len().wrapping_sub(1))
+  }
+
+  /// `capacity` returns the number of elements the deque can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    if core::mem::size_of::<T>() == 0 {
+      return usize::MAX;
+    }
+
+    self.header().cap
+  }
+
+  /// `clear` drops all of the elements while keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.truncate(0);
+  }
+
+  /// `contains` returns whether or not the deque holds an element equal to `x`.
+  ///
+  pub fn contains(&self, x: &T) -> bool
+  where
+    T: PartialEq,
+  {
+    let (front, back) = self.as_slices();
+    front.contains(x) || back.contains(x)
+  }
+
+  /// `drain` removes the elements in `range` from the deque, returning them through an iterator. The elements are
+  /// removed even if the iterator isn't fully consumed.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the range is decreasing or goes past the end of the deque.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut deque: minivec::MiniVecDeque<_> = (1..=5).collect();
+  /// let drained: Vec<_> = deque.drain(1..3).collect();
+  ///
+  /// assert_eq!(drained, [2, 3]);
+  /// assert_eq!(deque, [1, 4, 5]);
+  /// ```
+  ///
+  pub fn drain<R>(&mut self, range: R) -> DequeDrain<'_, T>
+  where
+    R: core::ops::RangeBounds<usize>,
+  {
+    let len = self.len();
+    let (start, end) = match normalize_range(&range, len) {
+      Ok(range) => range,
+      Err(e) => panic!("invalid drain range: {}", e),
+    };
+
+    // the elements after the drained ones are forgotten about until the iterator is dropped so that leaking it can
+    // never expose a moved-out element
+    //
+    if !self.is_default() {
+      self.header_mut().len = start;
+    }
+
+    DequeDrain {
+      deque: core::ptr::NonNull::from(self),
+      idx: start,
+      end,
+      range: (start, end),
+      len,
+      marker: core::marker::PhantomData,
+    }
+  }
+
+  /// `front` returns a reference to the first element, should it exist.
+  ///
+  #[must_use]
+  pub fn front(&self) -> Option<&T> {
+    self.get(0)
+  }
+
+  /// `front_mut` returns a mutable reference to the first element, should it exist.
+  ///
+  pub fn front_mut(&mut self) -> Option<&mut T> {
+    self.get_mut(0)
+  }
+
+  /// `get` returns a reference to the element at position `index`, counting from the front, should it exist.
+  ///
+  #[must_use]
+  pub fn get(&self, index: usize) -> Option<&T> {
+    if index >= self.len() {
+      return None;
+    }
+
+    Some(unsafe { &*self.data().add(self.physical(index)) })
+  }
+
+  /// `get_mut` returns a mutable reference to the element at position `index`, counting from the front, should it
+  /// exist.
+  ///
+  pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+    if index >= self.len() {
+      return None;
+    }
+
+    Some(unsafe { &mut *self.data().add(self.physical(index)) })
+  }
+
+  /// `is_empty` returns whether or not the deque contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `iter` returns an iterator over the elements, from front to back.
+  ///
+  pub fn iter(&self) -> core::iter::Chain<core::slice::Iter<'_, T>, core::slice::Iter<'_, T>> {
+    let (front, back) = self.as_slices();
+    front.iter().chain(back.iter())
+  }
+
+  /// `iter_mut` returns an iterator over mutable references to the elements, from front to back.
+  ///
+  pub fn iter_mut(
+    &mut self,
+  ) -> core::iter::Chain<core::slice::IterMut<'_, T>, core::slice::IterMut<'_, T>> {
+    let (front, back) = self.as_mut_slices();
+    front.iter_mut().chain(back.iter_mut())
+  }
+
+  /// `len` returns the number of elements in the deque.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.header().len
+  }
+
+  /// `make_contiguous` rearranges the elements so that they're contiguous in memory and returns them as a single
+  /// slice. The elements are already contiguous when [`as_slices`](MiniVecDeque::as_slices) returns an empty second
+  /// slice, in which case nothing is moved.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut deque = minivec::MiniVecDeque::with_capacity(4);
+  /// deque.push_back(2);
+  /// deque.push_back(3);
+  /// deque.push_front(1);
+  ///
+  /// deque.make_contiguous().sort_by(|a, b| b.cmp(a));
+  /// assert_eq!(deque.as_slices(), (&[3, 2, 1][..], &[][..]));
+  /// ```
+  ///
+  pub fn make_contiguous(&mut self) -> &mut [T] {
+    if self.is_contiguous() {
+      let (front, _) = self.as_mut_slices();
+      return front;
+    }
+
+    // the slots outside of the elements are uninitialized so the whole buffer is rotated as raw memory, bringing the
+    // front of the deque to the front of the buffer
+    //
+    let (head, len, cap) = (self.header().head, self.len(), self.header().cap);
+    unsafe {
+      core::slice::from_raw_parts_mut(self.data().cast::<core::mem::MaybeUninit<T>>(), cap)
+        .rotate_left(head);
+
+      self.header_mut().head = 0;
+      core::slice::from_raw_parts_mut(self.data(), len)
+    }
+  }
+
+  /// `new` creates an empty deque, which does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniVecDeque<T> {
+    MiniVecDeque {
+      buf: Self::empty_buf(),
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `pop_back` removes the last element and returns it, should it exist.
+  ///
+  pub fn pop_back(&mut self) -> Option<T> {
+    let len = self.len();
+    if len == 0 {
+      return None;
+    }
+
+    let idx = self.physical(len - 1);
+    self.header_mut().len = len - 1;
+
+    Some(unsafe { core::ptr::read(self.data().add(idx)) })
+  }
+
+  /// `pop_front` removes the first element and returns it, should it exist.
+  ///
+  pub fn pop_front(&mut self) -> Option<T> {
+    let len = self.len();
+    if len == 0 {
+      return None;
+    }
+
+    let head = self.header().head;
+    let header = self.header_mut();
+    header.head = if head + 1 == header.cap { 0 } else { head + 1 };
+    header.len = len - 1;
+
+    Some(unsafe { core::ptr::read(self.data().add(head)) })
+  }
+
+  /// `push_back` appends `value` to the back of the deque.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows `usize`.
+  ///
+  pub fn push_back(&mut self, value: T) {
+    handle_reserve(self.try_push_back(value).map_err(|(_, e)| e));
+  }
+
+  /// `push_front` prepends `value` to the front of the deque.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows `usize`.
+  ///
+  pub fn push_front(&mut self, value: T) {
+    handle_reserve(self.try_push_front(value).map_err(|(_, e)| e));
+  }
+
+  /// `reserve` ensures that the deque can hold at least `additional` more elements without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the new capacity overflows `usize`.
+  ///
+  pub fn reserve(&mut self, additional: usize) {
+    handle_reserve(self.try_reserve(additional));
+  }
+
+  /// `truncate` drops the elements past the first `len` ones. Nothing happens if `len` is greater than or equal to the
+  /// current length.
+  ///
+  pub fn truncate(&mut self, len: usize) {
+    let old_len = self.len();
+    if len >= old_len {
+      return;
+    }
+
+    // the length is updated first so that a panicking destructor leaks the remaining elements instead of dropping
+    // them twice
+    //
+    self.header_mut().len = len;
+
+    let data = self.data();
+    for idx in len..old_len {
+      unsafe { core::ptr::drop_in_place(data.add(self.physical(idx))) };
+    }
+  }
+
+  /// `try_push_back` is the fallible version of [`push_back`](MiniVecDeque::push_back), handing `value` back
+  /// alongside the error when the deque can't grow.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_push_back(&mut self, value: T) -> Result<(), (T, TryReserveError)> {
+    if let Err(e) = self.try_reserve(1) {
+      return Err((value, e));
+    }
+
+    let len = self.len();
+    unsafe { core::ptr::write(self.data().add(self.physical(len)), value) };
+    self.header_mut().len = len + 1;
+
+    Ok(())
+  }
+
+  /// `try_push_front` is the fallible version of [`push_front`](MiniVecDeque::push_front), handing `value` back
+  /// alongside the error when the deque can't grow.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_push_front(&mut self, value: T) -> Result<(), (T, TryReserveError)> {
+    if let Err(e) = self.try_reserve(1) {
+      return Err((value, e));
+    }
+
+    let header = self.header_mut();
+    header.head = if header.head == 0 {
+      header.cap - 1
+    } else {
+      header.head - 1
+    };
+    header.len += 1;
+
+    let head = header.head;
+    unsafe { core::ptr::write(self.data().add(head), value) };
+
+    Ok(())
+  }
+
+  /// `try_reserve` is the fallible version of [`reserve`](MiniVecDeque::reserve).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    let required = self
+      .len()
+      .checked_add(additional)
+      .ok_or(TryReserveErrorKind::CapacityOverflow)?;
+
+    if !self.is_default() && required <= self.header().cap {
+      return Ok(());
+    }
+
+    let cap = if core::mem::size_of::<T>() == 0 {
+      usize::MAX
+    } else {
+      core::cmp::max(
+        Doubling::next_capacity(core::mem::size_of::<T>(), self.header().cap, required),
+        required,
+      )
+    };
+
+    self.grow(cap)
+  }
+
+  /// `try_with_capacity` is the fallible version of [`with_capacity`](MiniVecDeque::with_capacity).
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the capacity overflows or the allocator fails.
+  ///
+  pub fn try_with_capacity(capacity: usize) -> Result<MiniVecDeque<T>, TryReserveError> {
+    let mut deque = Self::new();
+    if capacity > 0 {
+      deque.grow(capacity)?;
+    }
+
+    Ok(deque)
+  }
+
+  /// `with_capacity` creates an empty deque that can hold at least `capacity` elements without reallocating.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the capacity overflows `usize`.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniVecDeque<T> {
+    handle_reserve(Self::try_with_capacity(capacity))
+  }
+
+  // moves the elements into a new allocation of `cap` elements, front first
+  //
+  fn grow(&mut self, cap: usize) -> Result<(), TryReserveError> {
+    let mut other = Self::try_allocate(cap)?;
+    let len = self.len();
+
+    unsafe {
+      let (front, back) = self.as_slices();
+      core::ptr::copy_nonoverlapping(front.as_ptr(), other.data(), front.len());
+      core::ptr::copy_nonoverlapping(back.as_ptr(), other.data().add(front.len()), back.len());
+
+      other.header_mut().len = len;
+      if !self.is_default() {
+        self.header_mut().len = 0;
+      }
+    }
+
+    core::mem::swap(self, &mut other);
+    Ok(())
+  }
+
+  fn try_allocate(cap: usize) -> Result<MiniVecDeque<T>, TryReserveError> {
+    let layout = layout::<T>(cap).ok_or(TryReserveErrorKind::CapacityOverflow)?;
+    let buf = Global
+      .allocate(layout)
+      .map_err(|_| TryReserveErrorKind::AllocError { layout })?
+      .cast::<u8>();
+
+    unsafe { Self::write_header(buf, 0, 0, cap) };
+
+    Ok(MiniVecDeque {
+      buf,
+      phantom: core::marker::PhantomData,
+    })
+  }
+
+  unsafe fn write_header(buf: core::ptr::NonNull<u8>, head: usize, len: usize, cap: usize) {
+    #[allow(clippy::cast_ptr_alignment)]
+    core::ptr::write(
+      buf.as_ptr().cast::<DequeHeader>(),
+      DequeHeader { head, len, cap },
+    );
+  }
+
+  // a vector's allocation can be turned into a deque in place when the elements begin at the same offset and the
+  // allocation is described by the same `Layout`, see `header_bytes()`
+  //
+  fn reuses_vec_layout<H: IndexType>(cap: usize) -> bool {
+    Self::OFFSET == header_bytes::<T, Global, H>()
+      && layout::<T>(cap) == make_aligned_layout::<T, Global, H>(cap, max_align::<T, Global, H>())
+  }
+
+  // maps a position relative to the front of the deque onto its slot in the buffer
+  //
+  fn physical(&self, idx: usize) -> usize {
+    let DequeHeader { head, cap, .. } = *self.header();
+
+    if idx >= cap - head {
+      idx - (cap - head)
+    } else {
+      head + idx
+    }
+  }
+
+  fn is_contiguous(&self) -> bool {
+    let DequeHeader { head, len, cap } = *self.header();
+    len <= cap - head
+  }
+
+  // the buffer slots holding the front and the back of the deque
+  //
+  fn slice_ranges(&self) -> ((usize, usize), (usize, usize)) {
+    let DequeHeader { head, len, cap } = *self.header();
+
+    if len <= cap - head {
+      ((head, head + len), (0, 0))
+    } else {
+      ((head, cap), (0, len - (cap - head)))
+    }
+  }
+
+  fn header(&self) -> &DequeHeader {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.buf.as_ptr().cast::<DequeHeader>()
+    }
+  }
+
+  // must never be called on the shared empty header
+  //
+  fn header_mut(&mut self) -> &mut DequeHeader {
+    debug_assert!(!self.is_default());
+
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &mut *self.buf.as_ptr().cast::<DequeHeader>()
+    }
+  }
+
+  fn data(&self) -> *mut T {
+    // same as `MiniVec::data()`
+    //
+    if self.is_default() {
+      return core::ptr::NonNull::dangling().as_ptr();
+    }
+
+    unsafe { self.buf.as_ptr().add(Self::OFFSET).cast::<T>() }
+  }
+
+  fn is_default(&self) -> bool {
+    core::ptr::eq(self.buf.as_ptr(), Self::empty_buf().as_ptr())
+  }
+
+  const fn empty_buf() -> core::ptr::NonNull<u8> {
+    let p = core::ptr::addr_of!(EMPTY_DEQUE_HEADER)
+      .cast::<u8>()
+      .cast_mut();
+    unsafe { core::ptr::NonNull::new_unchecked(p) }
+  }
+}
+
+impl<T> Drop for MiniVecDeque<T> {
+  fn drop(&mut self) {
+    if self.is_default() {
+      return;
+    }
+
+    let (front, back) = self.as_mut_slices();
+    unsafe {
+      core::ptr::drop_in_place(front);
+      core::ptr::drop_in_place(back);
+
+      let layout = layout::<T>(self.header().cap).unwrap_unchecked();
+      Global.deallocate(self.buf, layout);
+    }
+  }
+}
+
+impl<T> Iterator for DequeDrain<'_, T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    let deque = unsafe { self.deque.as_ref() };
+    let p = unsafe { deque.data().add(deque.physical(self.idx)) };
+    self.idx += 1;
+
+    Some(unsafe { core::ptr::read(p) })
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = self.end - self.idx;
+    (len, Some(len))
+  }
+}
+
+impl<T> DoubleEndedIterator for DequeDrain<'_, T> {
+  fn next_back(&mut self) -> Option<T> {
+    if self.idx == self.end {
+      return None;
+    }
+
+    self.end -= 1;
+    let deque = unsafe { self.deque.as_ref() };
+
+    Some(unsafe { core::ptr::read(deque.data().add(deque.physical(self.end))) })
+  }
+}
+
+impl<T> ExactSizeIterator for DequeDrain<'_, T> {}
+
+impl<T> core::iter::FusedIterator for DequeDrain<'_, T> {}
+
+impl<T> Drop for DequeDrain<'_, T> {
+  fn drop(&mut self) {
+    self.for_each(drop);
+
+    let deque = unsafe { self.deque.as_mut() };
+    if deque.is_default() {
+      return;
+    }
+
+    // the gap left behind by the drained elements is closed by moving whichever side of it holds fewer elements
+    //
+    let (start, end) = self.range;
+    let drained = end - start;
+    let back_len = self.len - end;
+    let data = deque.data();
+
+    unsafe {
+      if start < back_len {
+        for idx in (0..start).rev() {
+          core::ptr::copy(
+            data.add(deque.physical(idx)),
+            data.add(deque.physical(idx + drained)),
+            1,
+          );
+        }
+
+        let head = deque.physical(drained);
+        deque.header_mut().head = head;
+      } else {
+        for idx in 0..back_len {
+          core::ptr::copy(
+            data.add(deque.physical(end + idx)),
+            data.add(deque.physical(start + idx)),
+            1,
+          );
+        }
+      }
+    }
+
+    deque.header_mut().len = self.len - drained;
+  }
+}
+
+impl<T> Iterator for DequeIntoIter<T> {
+  type Item = T;
+
+  fn next(&mut self) -> Option<T> {
+    self.deque.pop_front()
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (self.deque.len(), Some(self.deque.len()))
+  }
+}
+
+impl<T> DoubleEndedIterator for DequeIntoIter<T> {
+  fn next_back(&mut self) -> Option<T> {
+    self.deque.pop_back()
+  }
+}
+
+impl<T> ExactSizeIterator for DequeIntoIter<T> {}
+
+impl<T> core::iter::FusedIterator for DequeIntoIter<T> {}
+
+impl<T: Clone> Clone for MiniVecDeque<T> {
+  fn clone(&self) -> Self {
+    self.iter().cloned().collect()
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniVecDeque<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_list().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Default for MiniVecDeque<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+// hashes the same way as a slice of the elements would, regardless of where the buffer wraps around
+//
+impl<T: core::hash::Hash> core::hash::Hash for MiniVecDeque<T> {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    state.write_usize(self.len());
+    self.iter().for_each(|x| x.hash(state));
+  }
+}
+
+impl<T> core::ops::Index<usize> for MiniVecDeque<T> {
+  type Output = T;
+
+  fn index(&self, index: usize) -> &T {
+    match self.get(index) {
+      Some(x) => x,
+      None => panic!("index out of bounds"),
+    }
+  }
+}
+
+impl<T> core::ops::IndexMut<usize> for MiniVecDeque<T> {
+  fn index_mut(&mut self, index: usize) -> &mut T {
+    match self.get_mut(index) {
+      Some(x) => x,
+      None => panic!("index out of bounds"),
+    }
+  }
+}
+
+impl<T: Eq> Eq for MiniVecDeque<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<MiniVecDeque<U>> for MiniVecDeque<T> {
+  fn eq(&self, other: &MiniVecDeque<U>) -> bool {
+    self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for MiniVecDeque<T> {
+  fn eq(&self, other: &[U]) -> bool {
+    let (front, back) = self.as_slices();
+    self.len() == other.len() && front == &other[..front.len()] && back == &other[front.len()..]
+  }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for MiniVecDeque<T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    *self == other[..]
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<MiniVec<U>> for MiniVecDeque<T> {
+  fn eq(&self, other: &MiniVec<U>) -> bool {
+    *self == other[..]
+  }
+}
+
+impl<T: Ord> Ord for MiniVecDeque<T> {
+  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+    self.iter().cmp(other.iter())
+  }
+}
+
+impl<T: PartialOrd> PartialOrd for MiniVecDeque<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+    self.iter().partial_cmp(other.iter())
+  }
+}
+
+impl<T> Extend<T> for MiniVecDeque<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    let iter = iter.into_iter();
+    self.reserve(iter.size_hint().0);
+    iter.for_each(|x| self.push_back(x));
+  }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for MiniVecDeque<T> {
+  fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+    self.extend(iter.into_iter().copied());
+  }
+}
+
+impl<T> core::iter::FromIterator<T> for MiniVecDeque<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<MiniVec<T>>())
+  }
+}
+
+impl<T, H: IndexType, G: GrowthPolicy> From<MiniVec<T, Global, H, G>> for MiniVecDeque<T> {
+  /// `from` turns `vec` into a deque holding the same elements, in the same order.
+  ///
+  /// The allocation of `vec` is only reused when its header has room for the position of the first element, otherwise
+  /// the elements are copied into a new allocation, which takes `O(n)` time.
+  ///
+  fn from(vec: MiniVec<T, Global, H, G>) -> Self {
+    let mut vec = core::mem::ManuallyDrop::new(vec);
+    let (len, cap) = (vec.len(), vec.raw_capacity());
+
+    if !vec.is_default() && vec.aligned_flag() == 0 && Self::reuses_vec_layout::<H>(cap) {
+      unsafe { Self::write_header(vec.buf, 0, len, cap) };
+
+      return MiniVecDeque {
+        buf: vec.buf,
+        phantom: core::marker::PhantomData,
+      };
+    }
+
+    let mut deque = Self::with_capacity(len);
+    unsafe {
+      core::ptr::copy_nonoverlapping(vec.as_ptr(), deque.data(), len);
+      if len > 0 {
+        deque.header_mut().len = len;
+      }
+
+      vec.set_len(0);
+      core::mem::ManuallyDrop::drop(&mut vec);
+    }
+
+    deque
+  }
+}
+
+impl<T> From<MiniVecDeque<T>> for MiniVec<T> {
+  /// `from` turns `deque` into a vector holding the same elements, front to back.
+  ///
+  /// Like the conversion in the other direction, the allocation is only reused for element types aligned to at least
+  /// 32 bytes and the elements are copied into a new allocation otherwise.
+  ///
+  fn from(deque: MiniVecDeque<T>) -> Self {
+    let mut deque = core::mem::ManuallyDrop::new(deque);
+    deque.make_contiguous();
+
+    let DequeHeader { head, len, cap } = *deque.header();
+
+    if !deque.is_default() && MiniVecDeque::<T>::reuses_vec_layout::<usize>(cap) {
+      unsafe {
+        core::ptr::copy(deque.data().add(head), deque.data(), len);
+        MiniVec::<T>::write_header(deque.buf.as_ptr(), len, cap);
+        return MiniVec::from_buf(deque.buf);
+      }
+    }
+
+    let mut vec = MiniVec::with_capacity(len);
+    unsafe {
+      core::ptr::copy_nonoverlapping(deque.data().add(head), vec.as_mut_ptr(), len);
+      vec.set_len(len);
+
+      if len > 0 {
+        deque.header_mut().len = 0;
+      }
+      core::mem::ManuallyDrop::drop(&mut deque);
+    }
+
+    vec
+  }
+}
+
+impl<T, const N: usize> From<[T; N]> for MiniVecDeque<T> {
+  fn from(arr: [T; N]) -> Self {
+    IntoIterator::into_iter(arr).collect()
+  }
+}
+
+impl<T> IntoIterator for MiniVecDeque<T> {
+  type Item = T;
+  type IntoIter = DequeIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    DequeIntoIter { deque: self }
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniVecDeque<T> {
+  type Item = &'a T;
+  type IntoIter = core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a mut MiniVecDeque<T> {
+  type Item = &'a mut T;
+  type IntoIter = core::iter::Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
+
+unsafe impl<T: Send> Send for MiniVecDeque<T> {}
+unsafe impl<T: Sync> Sync for MiniVecDeque<T> {}
+unsafe impl<T: Send> Send for DequeDrain<'_, T> {}
+unsafe impl<T: Sync> Sync for DequeDrain<'_, T> {}
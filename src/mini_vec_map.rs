@@ -0,0 +1,656 @@
+use crate::{MiniVec, TryReserveError};
+
+use core::borrow::Borrow;
+use core::ops::{Bound, RangeBounds};
+
+/// `MiniVecMap` is an ordered map which keeps its entries sorted by key in a single [`MiniVec`], the counterpart of
+/// `alloc::collections::BTreeMap` for maps small enough that a binary search beats chasing tree nodes.
+///
+/// Lookups are `O(log n)` while insertions and removals are `O(n)` as they shift the entries after them. Bulk
+/// construction through `FromIterator` sorts the entries once instead, keeping the last value of any duplicated key.
+///
+/// # Example
+///
+/// ```
+/// let mut ages: minivec::MiniVecMap<_, _> = [("carol", 31), ("alice", 28)].iter().copied().collect();
+/// ages.insert("bob", 45);
+///
+/// assert_eq!(ages.get("alice"), Some(&28));
+/// assert_eq!(ages.keys().copied().collect::<Vec<_>>(), ["alice", "bob", "carol"]);
+///
+/// *ages.entry("alice").or_insert(0) += 1;
+/// assert_eq!(ages["alice"], 29);
+/// ```
+///
+pub struct MiniVecMap<K, V> {
+  entries: MiniVec<(K, V)>,
+}
+
+/// `Entry` is a view into a single entry of a [`MiniVecMap`], which may be either vacant or occupied. It's returned by
+/// [`MiniVecMap::entry`].
+///
+pub enum Entry<'a, K, V> {
+  /// A vacant entry.
+  Vacant(VacantEntry<'a, K, V>),
+  /// An occupied entry.
+  Occupied(OccupiedEntry<'a, K, V>),
+}
+
+/// `VacantEntry` is a view into a vacant entry of a [`MiniVecMap`], part of the [`Entry`] enum.
+///
+pub struct VacantEntry<'a, K, V> {
+  map: &'a mut MiniVecMap<K, V>,
+  key: K,
+  idx: usize,
+}
+
+/// `OccupiedEntry` is a view into an occupied entry of a [`MiniVecMap`], part of the [`Entry`] enum.
+///
+pub struct OccupiedEntry<'a, K, V> {
+  map: &'a mut MiniVecMap<K, V>,
+  idx: usize,
+}
+
+/// `VecMapIter` is an iterator over the entries of a [`MiniVecMap`], in ascending order of their keys.
+///
+#[derive(Clone)]
+pub struct VecMapIter<'a, K, V> {
+  iter: core::slice::Iter<'a, (K, V)>,
+}
+
+/// `VecMapIterMut` is an iterator over the entries of a [`MiniVecMap`] with mutable references to the values, in
+/// ascending order of their keys.
+///
+pub struct VecMapIterMut<'a, K, V> {
+  iter: core::slice::IterMut<'a, (K, V)>,
+}
+
+impl<K: Ord, V> MiniVecMap<K, V> {
+  /// `contains_key` returns whether or not the map holds an entry for `key`.
+  ///
+  pub fn contains_key<Q>(&self, key: &Q) -> bool
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.search(key).is_ok()
+  }
+
+  /// `entry` returns the entry for `key`, for in-place manipulation.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut counts = minivec::MiniVecMap::new();
+  /// for word in "a b a c a".split(' ') {
+  ///   *counts.entry(word).or_insert(0) += 1;
+  /// }
+  ///
+  /// assert_eq!(counts["a"], 3);
+  /// assert_eq!(counts.len(), 3);
+  /// ```
+  ///
+  pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+    match self.search(&key) {
+      Ok(idx) => Entry::Occupied(OccupiedEntry { map: self, idx }),
+      Err(idx) => Entry::Vacant(VacantEntry {
+        map: self,
+        key,
+        idx,
+      }),
+    }
+  }
+
+  /// `get` returns a reference to the value for `key`, should it exist.
+  ///
+  pub fn get<Q>(&self, key: &Q) -> Option<&V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.get_key_value(key).map(|(_, v)| v)
+  }
+
+  /// `get_key_value` returns references to the key and value of the entry for `key`, should it exist.
+  ///
+  pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let idx = self.search(key).ok()?;
+    let (k, v) = &self.entries[idx];
+    Some((k, v))
+  }
+
+  /// `get_mut` returns a mutable reference to the value for `key`, should it exist.
+  ///
+  pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let idx = self.search(key).ok()?;
+    Some(&mut self.entries[idx].1)
+  }
+
+  /// `insert` inserts `value` for `key`, returning the value it replaced, should one exist. The key itself isn't
+  /// updated when it's already present.
+  ///
+  pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+    match self.search(&key) {
+      Ok(idx) => Some(core::mem::replace(&mut self.entries[idx].1, value)),
+      Err(idx) => {
+        self.entries.insert(idx, (key, value));
+        None
+      }
+    }
+  }
+
+  /// `range` returns an iterator over the entries whose keys lie in `range`, in ascending order.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the start of the range is greater than its end, or if both bounds are equal and excluded.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let map: minivec::MiniVecMap<_, _> = (0..10).map(|x| (x, x * x)).collect();
+  ///
+  /// assert_eq!(map.range(3..6).map(|(_, v)| *v).collect::<Vec<_>>(), [9, 16, 25]);
+  /// assert_eq!(map.range(..=1).count(), 2);
+  /// ```
+  ///
+  pub fn range<Q, R>(&self, range: R) -> VecMapIter<'_, K, V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+  {
+    let (start, end) = range_indices(&self.entries, |(k, _)| k.borrow(), &range);
+
+    VecMapIter {
+      iter: self.entries[start..end].iter(),
+    }
+  }
+
+  /// `remove` removes the entry for `key`, returning its value should it exist.
+  ///
+  pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.remove_entry(key).map(|(_, v)| v)
+  }
+
+  /// `remove_entry` removes the entry for `key`, returning its key and value should it exist.
+  ///
+  pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let idx = self.search(key).ok()?;
+    Some(self.entries.remove(idx))
+  }
+
+  fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+  where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.entries.binary_search_by(|(k, _)| k.borrow().cmp(key))
+  }
+}
+
+impl<K, V> MiniVecMap<K, V> {
+  /// `as_slice` returns the entries as a slice, sorted by key.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[(K, V)] {
+    &self.entries
+  }
+
+  /// `capacity` returns the number of entries the map can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.entries.capacity()
+  }
+
+  /// `clear` removes all of the entries while keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.entries.clear();
+  }
+
+  /// `first_key_value` returns the entry with the smallest key, should it exist.
+  ///
+  #[must_use]
+  pub fn first_key_value(&self) -> Option<(&K, &V)> {
+    self.entries.first().map(|(k, v)| (k, v))
+  }
+
+  /// `is_empty` returns whether or not the map contains any entries.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  /// `iter` returns an iterator over the entries, in ascending order of their keys.
+  ///
+  #[must_use]
+  pub fn iter(&self) -> VecMapIter<'_, K, V> {
+    VecMapIter {
+      iter: self.entries.iter(),
+    }
+  }
+
+  /// `iter_mut` returns an iterator over the entries with mutable references to the values, in ascending order of
+  /// their keys.
+  ///
+  pub fn iter_mut(&mut self) -> VecMapIterMut<'_, K, V> {
+    VecMapIterMut {
+      iter: self.entries.iter_mut(),
+    }
+  }
+
+  /// `keys` returns an iterator over the keys, in ascending order.
+  ///
+  #[must_use]
+  pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator {
+    self.entries.iter().map(|(k, _)| k)
+  }
+
+  /// `last_key_value` returns the entry with the greatest key, should it exist.
+  ///
+  #[must_use]
+  pub fn last_key_value(&self) -> Option<(&K, &V)> {
+    self.entries.last().map(|(k, v)| (k, v))
+  }
+
+  /// `len` returns the number of entries in the map.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  /// `new` creates an empty map, which does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniVecMap<K, V> {
+    MiniVecMap {
+      entries: MiniVec::new(),
+    }
+  }
+
+  /// `retain` keeps only the entries for which `f` returns `true`, visiting them in ascending order of their keys.
+  ///
+  pub fn retain<F>(&mut self, mut f: F)
+  where
+    F: FnMut(&K, &mut V) -> bool,
+  {
+    self.entries.drain_filter(|(k, v)| !f(k, v));
+  }
+
+  /// `try_reserve` ensures that the map can hold at least `additional` more entries without reallocating.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.entries.try_reserve(additional)
+  }
+
+  /// `values` returns an iterator over the values, in ascending order of their keys.
+  ///
+  #[must_use]
+  pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+    self.entries.iter().map(|(_, v)| v)
+  }
+
+  /// `values_mut` returns an iterator over mutable references to the values, in ascending order of their keys.
+  ///
+  pub fn values_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut V> + ExactSizeIterator {
+    self.entries.iter_mut().map(|(_, v)| v)
+  }
+
+  /// `with_capacity` creates an empty map that can hold at least `capacity` entries without reallocating.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniVecMap<K, V> {
+    MiniVecMap {
+      entries: MiniVec::with_capacity(capacity),
+    }
+  }
+}
+
+// the indices of the first item inside of `range` and of the first one past it, `items` being sorted by `key`
+//
+pub(crate) fn range_indices<T, Q, R, F>(items: &[T], key: F, range: &R) -> (usize, usize)
+where
+  Q: Ord + ?Sized,
+  R: RangeBounds<Q>,
+  F: Fn(&T) -> &Q,
+{
+  // the bounds are checked against each other, like `BTreeMap` does, so that an invalid range panics no matter which
+  // keys are stored
+  //
+  match (range.start_bound(), range.end_bound()) {
+    (Bound::Excluded(s), Bound::Excluded(e)) if s == e => {
+      panic!("range start and end are equal and excluded")
+    }
+    (Bound::Included(s) | Bound::Excluded(s), Bound::Included(e) | Bound::Excluded(e)) if s > e => {
+      panic!("range start is greater than range end")
+    }
+    _ => {}
+  }
+
+  let start = match range.start_bound() {
+    Bound::Included(x) => items.partition_point(|item| key(item) < x),
+    Bound::Excluded(x) => items.partition_point(|item| key(item) <= x),
+    Bound::Unbounded => 0,
+  };
+
+  let end = match range.end_bound() {
+    Bound::Included(x) => items.partition_point(|item| key(item) <= x),
+    Bound::Excluded(x) => items.partition_point(|item| key(item) < x),
+    Bound::Unbounded => items.len(),
+  };
+
+  (start, end)
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+  /// `and_modify` calls `f` on the value of an occupied entry before any insertion.
+  ///
+  #[must_use]
+  pub fn and_modify<F>(self, f: F) -> Self
+  where
+    F: FnOnce(&mut V),
+  {
+    match self {
+      Entry::Occupied(mut entry) => {
+        f(entry.get_mut());
+        Entry::Occupied(entry)
+      }
+      Entry::Vacant(entry) => Entry::Vacant(entry),
+    }
+  }
+
+  /// `key` returns a reference to the key of the entry.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    match self {
+      Entry::Occupied(entry) => entry.key(),
+      Entry::Vacant(entry) => entry.key(),
+    }
+  }
+
+  /// `or_default` inserts the default value into a vacant entry, returning a mutable reference to the entry's value.
+  ///
+  pub fn or_default(self) -> &'a mut V
+  where
+    V: Default,
+  {
+    self.or_insert_with(V::default)
+  }
+
+  /// `or_insert` inserts `default` into a vacant entry, returning a mutable reference to the entry's value.
+  ///
+  pub fn or_insert(self, default: V) -> &'a mut V {
+    self.or_insert_with(|| default)
+  }
+
+  /// `or_insert_with` inserts the result of `default` into a vacant entry, returning a mutable reference to the entry's
+  /// value.
+  ///
+  pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+  where
+    F: FnOnce() -> V,
+  {
+    match self {
+      Entry::Occupied(entry) => entry.into_mut(),
+      Entry::Vacant(entry) => entry.insert(default()),
+    }
+  }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+  /// `insert` inserts `value` for the entry's key, returning a mutable reference to it.
+  ///
+  pub fn insert(self, value: V) -> &'a mut V {
+    self.map.entries.insert(self.idx, (self.key, value));
+    &mut self.map.entries[self.idx].1
+  }
+
+  /// `into_key` returns the key which would have been inserted.
+  ///
+  #[must_use]
+  pub fn into_key(self) -> K {
+    self.key
+  }
+
+  /// `key` returns a reference to the key which would be inserted.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    &self.key
+  }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+  /// `get` returns a reference to the value of the entry.
+  ///
+  #[must_use]
+  pub fn get(&self) -> &V {
+    &self.map.entries[self.idx].1
+  }
+
+  /// `get_mut` returns a mutable reference to the value of the entry.
+  ///
+  pub fn get_mut(&mut self) -> &mut V {
+    &mut self.map.entries[self.idx].1
+  }
+
+  /// `insert` replaces the value of the entry with `value`, returning the old one.
+  ///
+  pub fn insert(&mut self, value: V) -> V {
+    core::mem::replace(self.get_mut(), value)
+  }
+
+  /// `into_mut` converts the entry into a mutable reference to its value, bound to the lifetime of the map.
+  ///
+  #[must_use]
+  pub fn into_mut(self) -> &'a mut V {
+    &mut self.map.entries[self.idx].1
+  }
+
+  /// `key` returns a reference to the key of the entry.
+  ///
+  #[must_use]
+  pub fn key(&self) -> &K {
+    &self.map.entries[self.idx].0
+  }
+
+  /// `remove` removes the entry from the map, returning its value.
+  ///
+  #[must_use]
+  pub fn remove(self) -> V {
+    self.remove_entry().1
+  }
+
+  /// `remove_entry` removes the entry from the map, returning its key and value.
+  ///
+  #[must_use]
+  pub fn remove_entry(self) -> (K, V) {
+    self.map.entries.remove(self.idx)
+  }
+}
+
+impl<'a, K, V> Iterator for VecMapIter<'a, K, V> {
+  type Item = (&'a K, &'a V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(k, v)| (k, v))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<K, V> DoubleEndedIterator for VecMapIter<'_, K, V> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|(k, v)| (k, v))
+  }
+}
+
+impl<K, V> ExactSizeIterator for VecMapIter<'_, K, V> {}
+
+impl<K, V> core::iter::FusedIterator for VecMapIter<'_, K, V> {}
+
+impl<'a, K, V> Iterator for VecMapIterMut<'a, K, V> {
+  type Item = (&'a K, &'a mut V);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.iter.next().map(|(k, v)| (&*k, v))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.iter.size_hint()
+  }
+}
+
+impl<K, V> DoubleEndedIterator for VecMapIterMut<'_, K, V> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    self.iter.next_back().map(|(k, v)| (&*k, v))
+  }
+}
+
+impl<K, V> ExactSizeIterator for VecMapIterMut<'_, K, V> {}
+
+impl<K, V> core::iter::FusedIterator for VecMapIterMut<'_, K, V> {}
+
+impl<K: Clone, V: Clone> Clone for MiniVecMap<K, V> {
+  fn clone(&self) -> Self {
+    MiniVecMap {
+      entries: self.entries.clone(),
+    }
+  }
+}
+
+impl<K: core::fmt::Debug, V: core::fmt::Debug> core::fmt::Debug for MiniVecMap<K, V> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_map().entries(self.iter()).finish()
+  }
+}
+
+impl<K, V> Default for MiniVecMap<K, V> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<K: core::hash::Hash, V: core::hash::Hash> core::hash::Hash for MiniVecMap<K, V> {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.entries.hash(state);
+  }
+}
+
+impl<K: Eq, V: Eq> Eq for MiniVecMap<K, V> {}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for MiniVecMap<K, V> {
+  fn eq(&self, other: &Self) -> bool {
+    self.entries == other.entries
+  }
+}
+
+impl<K, Q, V> core::ops::Index<&Q> for MiniVecMap<K, V>
+where
+  K: Borrow<Q> + Ord,
+  Q: Ord + ?Sized,
+{
+  type Output = V;
+
+  fn index(&self, key: &Q) -> &V {
+    match self.get(key) {
+      Some(v) => v,
+      None => panic!("key not found in map"),
+    }
+  }
+}
+
+impl<K: Ord, V> Extend<(K, V)> for MiniVecMap<K, V> {
+  fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+    for (k, v) in iter {
+      self.insert(k, v);
+    }
+  }
+}
+
+impl<K: Ord, V> core::iter::FromIterator<(K, V)> for MiniVecMap<K, V> {
+  fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<MiniVec<(K, V)>>())
+  }
+}
+
+impl<K: Ord, V> From<MiniVec<(K, V)>> for MiniVecMap<K, V> {
+  fn from(mut entries: MiniVec<(K, V)>) -> Self {
+    // the sort is stable so the last value of a duplicated key is the one moved into the entry that's kept
+    //
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries.dedup_by(|later, kept| {
+      if later.0 != kept.0 {
+        return false;
+      }
+
+      core::mem::swap(&mut later.1, &mut kept.1);
+      true
+    });
+
+    MiniVecMap { entries }
+  }
+}
+
+impl<K, V> From<MiniVecMap<K, V>> for MiniVec<(K, V)> {
+  fn from(map: MiniVecMap<K, V>) -> Self {
+    map.entries
+  }
+}
+
+impl<K, V> IntoIterator for MiniVecMap<K, V> {
+  type Item = (K, V);
+  type IntoIter = crate::IntoIter<(K, V)>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.entries.into_iter()
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a MiniVecMap<K, V> {
+  type Item = (&'a K, &'a V);
+  type IntoIter = VecMapIter<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut MiniVecMap<K, V> {
+  type Item = (&'a K, &'a mut V);
+  type IntoIter = VecMapIterMut<'a, K, V>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter_mut()
+  }
+}
@@ -0,0 +1,497 @@
+use crate::mini_vec_map::range_indices;
+use crate::{MiniVec, TryReserveError};
+
+use core::borrow::Borrow;
+use core::ops::RangeBounds;
+
+/// `MiniVecSet` is an ordered set which keeps its elements sorted in a single [`MiniVec`], the counterpart of
+/// `alloc::collections::BTreeSet` for sets small enough that a binary search beats chasing tree nodes.
+///
+/// Lookups are `O(log n)` while insertions and removals are `O(n)` as they shift the elements after them. Bulk
+/// construction through `FromIterator` sorts and deduplicates the elements once instead, and the set operations are
+/// single merge passes over both sets.
+///
+/// # Example
+///
+/// ```
+/// let a: minivec::MiniVecSet<_> = [5, 1, 3, 1].iter().copied().collect();
+/// let b: minivec::MiniVecSet<_> = [3, 4, 5].iter().copied().collect();
+///
+/// assert_eq!(a.as_slice(), [1, 3, 5]);
+/// assert_eq!(a.union(&b).copied().collect::<Vec<_>>(), [1, 3, 4, 5]);
+/// assert_eq!(a.intersection(&b).copied().collect::<Vec<_>>(), [3, 5]);
+/// assert_eq!(a.difference(&b).copied().collect::<Vec<_>>(), [1]);
+/// ```
+///
+pub struct MiniVecSet<T> {
+  items: MiniVec<T>,
+}
+
+// walks two sorted slices in lockstep, yielding the smaller of their first elements or both of them when they're equal
+//
+#[derive(Clone)]
+struct Merge<'a, T> {
+  a: &'a [T],
+  b: &'a [T],
+}
+
+/// `Union` is the iterator returned by [`MiniVecSet::union`].
+///
+#[derive(Clone)]
+pub struct Union<'a, T> {
+  merge: Merge<'a, T>,
+}
+
+/// `Intersection` is the iterator returned by [`MiniVecSet::intersection`].
+///
+#[derive(Clone)]
+pub struct Intersection<'a, T> {
+  merge: Merge<'a, T>,
+}
+
+/// `Difference` is the iterator returned by [`MiniVecSet::difference`].
+///
+#[derive(Clone)]
+pub struct Difference<'a, T> {
+  merge: Merge<'a, T>,
+}
+
+/// `SymmetricDifference` is the iterator returned by [`MiniVecSet::symmetric_difference`].
+///
+#[derive(Clone)]
+pub struct SymmetricDifference<'a, T> {
+  merge: Merge<'a, T>,
+}
+
+impl<T: Ord> MiniVecSet<T> {
+  /// `contains` returns whether or not the set holds an element equal to `value`.
+  ///
+  pub fn contains<Q>(&self, value: &Q) -> bool
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.search(value).is_ok()
+  }
+
+  /// `difference` returns an iterator over the elements in `self` but not in `other`, in ascending order.
+  ///
+  #[must_use]
+  pub fn difference<'a>(&'a self, other: &'a MiniVecSet<T>) -> Difference<'a, T> {
+    Difference {
+      merge: Merge::new(self, other),
+    }
+  }
+
+  /// `get` returns a reference to the element equal to `value`, should it exist.
+  ///
+  pub fn get<Q>(&self, value: &Q) -> Option<&T>
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let idx = self.search(value).ok()?;
+    Some(&self.items[idx])
+  }
+
+  /// `insert` adds `value` to the set, returning whether or not it was newly inserted. The set is left untouched when
+  /// it already holds an equal element.
+  ///
+  pub fn insert(&mut self, value: T) -> bool {
+    match self.search(&value) {
+      Ok(_) => false,
+      Err(idx) => {
+        self.items.insert(idx, value);
+        true
+      }
+    }
+  }
+
+  /// `intersection` returns an iterator over the elements in both `self` and `other`, in ascending order.
+  ///
+  #[must_use]
+  pub fn intersection<'a>(&'a self, other: &'a MiniVecSet<T>) -> Intersection<'a, T> {
+    Intersection {
+      merge: Merge::new(self, other),
+    }
+  }
+
+  /// `is_disjoint` returns whether or not `self` and `other` have no elements in common.
+  ///
+  #[must_use]
+  pub fn is_disjoint(&self, other: &MiniVecSet<T>) -> bool {
+    self.intersection(other).next().is_none()
+  }
+
+  /// `is_subset` returns whether or not `other` holds all of the elements of `self`.
+  ///
+  #[must_use]
+  pub fn is_subset(&self, other: &MiniVecSet<T>) -> bool {
+    self.len() <= other.len() && self.difference(other).next().is_none()
+  }
+
+  /// `is_superset` returns whether or not `self` holds all of the elements of `other`.
+  ///
+  #[must_use]
+  pub fn is_superset(&self, other: &MiniVecSet<T>) -> bool {
+    other.is_subset(self)
+  }
+
+  /// `range` returns an iterator over the elements that lie in `range`, in ascending order.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the start of the range is greater than its end, or if both bounds are equal and excluded.
+  ///
+  pub fn range<Q, R>(&self, range: R) -> core::slice::Iter<'_, T>
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+    R: RangeBounds<Q>,
+  {
+    let (start, end) = range_indices(&self.items, Borrow::borrow, &range);
+    self.items[start..end].iter()
+  }
+
+  /// `remove` removes the element equal to `value`, returning whether or not it was present.
+  ///
+  pub fn remove<Q>(&mut self, value: &Q) -> bool
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.take(value).is_some()
+  }
+
+  /// `symmetric_difference` returns an iterator over the elements in either `self` or `other` but not in both, in
+  /// ascending order.
+  ///
+  #[must_use]
+  pub fn symmetric_difference<'a>(
+    &'a self,
+    other: &'a MiniVecSet<T>,
+  ) -> SymmetricDifference<'a, T> {
+    SymmetricDifference {
+      merge: Merge::new(self, other),
+    }
+  }
+
+  /// `take` removes the element equal to `value` and returns it, should it exist.
+  ///
+  pub fn take<Q>(&mut self, value: &Q) -> Option<T>
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    let idx = self.search(value).ok()?;
+    Some(self.items.remove(idx))
+  }
+
+  /// `union` returns an iterator over the elements in `self`, `other` or both, in ascending order and without
+  /// duplicates.
+  ///
+  #[must_use]
+  pub fn union<'a>(&'a self, other: &'a MiniVecSet<T>) -> Union<'a, T> {
+    Union {
+      merge: Merge::new(self, other),
+    }
+  }
+
+  fn search<Q>(&self, value: &Q) -> Result<usize, usize>
+  where
+    T: Borrow<Q>,
+    Q: Ord + ?Sized,
+  {
+    self.items.binary_search_by(|x| x.borrow().cmp(value))
+  }
+}
+
+impl<T> MiniVecSet<T> {
+  /// `as_slice` returns the elements as a slice, in ascending order.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    &self.items
+  }
+
+  /// `capacity` returns the number of elements the set can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.items.capacity()
+  }
+
+  /// `clear` removes all of the elements while keeping the allocation.
+  ///
+  pub fn clear(&mut self) {
+    self.items.clear();
+  }
+
+  /// `first` returns the smallest element, should it exist.
+  ///
+  #[must_use]
+  pub fn first(&self) -> Option<&T> {
+    self.items.first()
+  }
+
+  /// `is_empty` returns whether or not the set contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.items.is_empty()
+  }
+
+  /// `iter` returns an iterator over the elements, in ascending order.
+  ///
+  pub fn iter(&self) -> core::slice::Iter<'_, T> {
+    self.items.iter()
+  }
+
+  /// `last` returns the greatest element, should it exist.
+  ///
+  #[must_use]
+  pub fn last(&self) -> Option<&T> {
+    self.items.last()
+  }
+
+  /// `len` returns the number of elements in the set.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.items.len()
+  }
+
+  /// `new` creates an empty set, which does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> MiniVecSet<T> {
+    MiniVecSet {
+      items: MiniVec::new(),
+    }
+  }
+
+  /// `retain` keeps only the elements for which `f` returns `true`, visiting them in ascending order.
+  ///
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: FnMut(&T) -> bool,
+  {
+    self.items.retain(f);
+  }
+
+  /// `try_reserve` ensures that the set can hold at least `additional` more elements without reallocating.
+  ///
+  /// # Errors
+  ///
+  /// Returns a `TryReserveError` if the new capacity overflows or the allocator fails.
+  ///
+  pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+    self.items.try_reserve(additional)
+  }
+
+  /// `with_capacity` creates an empty set that can hold at least `capacity` elements without reallocating.
+  ///
+  #[must_use]
+  pub fn with_capacity(capacity: usize) -> MiniVecSet<T> {
+    MiniVecSet {
+      items: MiniVec::with_capacity(capacity),
+    }
+  }
+}
+
+impl<'a, T: Ord> Merge<'a, T> {
+  fn new(a: &'a MiniVecSet<T>, b: &'a MiniVecSet<T>) -> Self {
+    Merge {
+      a: a.as_slice(),
+      b: b.as_slice(),
+    }
+  }
+
+  fn next(&mut self) -> Option<(Option<&'a T>, Option<&'a T>)> {
+    let ordering = match (self.a.first(), self.b.first()) {
+      (None, None) => return None,
+      (Some(_), None) => core::cmp::Ordering::Less,
+      (None, Some(_)) => core::cmp::Ordering::Greater,
+      (Some(x), Some(y)) => x.cmp(y),
+    };
+
+    let (mut x, mut y) = (None, None);
+    if ordering != core::cmp::Ordering::Greater {
+      x = self.a.first();
+      self.a = &self.a[1..];
+    }
+    if ordering != core::cmp::Ordering::Less {
+      y = self.b.first();
+      self.b = &self.b[1..];
+    }
+
+    Some((x, y))
+  }
+}
+
+impl<'a, T: Ord> Iterator for Union<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    let (x, y) = self.merge.next()?;
+    x.or(y)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let (a, b) = (self.merge.a.len(), self.merge.b.len());
+    (core::cmp::max(a, b), Some(a + b))
+  }
+}
+
+impl<'a, T: Ord> Iterator for Intersection<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    while !self.merge.a.is_empty() && !self.merge.b.is_empty() {
+      if let Some((Some(x), Some(_))) = self.merge.next() {
+        return Some(x);
+      }
+    }
+
+    None
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (
+      0,
+      Some(core::cmp::min(self.merge.a.len(), self.merge.b.len())),
+    )
+  }
+}
+
+impl<'a, T: Ord> Iterator for Difference<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    while !self.merge.a.is_empty() {
+      if let Some((Some(x), None)) = self.merge.next() {
+        return Some(x);
+      }
+    }
+
+    None
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.merge.a.len()))
+  }
+}
+
+impl<'a, T: Ord> Iterator for SymmetricDifference<'a, T> {
+  type Item = &'a T;
+
+  fn next(&mut self) -> Option<&'a T> {
+    loop {
+      match self.merge.next()? {
+        (Some(x), None) | (None, Some(x)) => return Some(x),
+        _ => {}
+      }
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.merge.a.len() + self.merge.b.len()))
+  }
+}
+
+impl<T: Ord> core::iter::FusedIterator for Union<'_, T> {}
+impl<T: Ord> core::iter::FusedIterator for Intersection<'_, T> {}
+impl<T: Ord> core::iter::FusedIterator for Difference<'_, T> {}
+impl<T: Ord> core::iter::FusedIterator for SymmetricDifference<'_, T> {}
+
+impl<T: Clone> Clone for MiniVecSet<T> {
+  fn clone(&self) -> Self {
+    MiniVecSet {
+      items: self.items.clone(),
+    }
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for MiniVecSet<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+impl<T> Default for MiniVecSet<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for MiniVecSet<T> {
+  fn hash<S>(&self, state: &mut S)
+  where
+    S: core::hash::Hasher,
+  {
+    self.items.hash(state);
+  }
+}
+
+impl<T: Eq> Eq for MiniVecSet<T> {}
+
+impl<T: PartialEq> PartialEq for MiniVecSet<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.items == other.items
+  }
+}
+
+impl<T: Ord> Extend<T> for MiniVecSet<T> {
+  fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+    for x in iter {
+      self.insert(x);
+    }
+  }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for MiniVecSet<T> {
+  fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+    self.extend(iter.into_iter().copied());
+  }
+}
+
+impl<T: Ord> core::iter::FromIterator<T> for MiniVecSet<T> {
+  fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+    Self::from(iter.into_iter().collect::<MiniVec<T>>())
+  }
+}
+
+impl<T: Ord> From<MiniVec<T>> for MiniVecSet<T> {
+  fn from(mut items: MiniVec<T>) -> Self {
+    // the sort is stable so that, like with `insert`, the first of several equal elements is the one that's kept
+    //
+    items.sort();
+    items.dedup();
+
+    MiniVecSet { items }
+  }
+}
+
+impl<T> From<MiniVecSet<T>> for MiniVec<T> {
+  fn from(set: MiniVecSet<T>) -> Self {
+    set.items
+  }
+}
+
+impl<T> IntoIterator for MiniVecSet<T> {
+  type Item = T;
+  type IntoIter = crate::IntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.items.into_iter()
+  }
+}
+
+impl<'a, T> IntoIterator for &'a MiniVecSet<T> {
+  type Item = &'a T;
+  type IntoIter = core::slice::Iter<'a, T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
@@ -1,9 +1,12 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
-impl<T: Ord> core::cmp::Ord for MiniVec<T> {
+impl<T: Ord, A: Allocator, H: IndexType, G: GrowthPolicy> core::cmp::Ord for MiniVec<T, A, H, G> {
   fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-    let x: &[T] = &**self;
-    let y: &[T] = &**other;
+    let x: &[T] = self;
+    let y: &[T] = other;
 
     x.cmp(y)
   }
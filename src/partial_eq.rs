@@ -1,8 +1,11 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
 use crate::MiniVec;
 
 macro_rules! minivec_eq_impl {
   ([$($args:tt)*] $lhs:ty, $rhs:ty) => {
-    impl<T, U, $($args)*> PartialEq<$rhs> for $lhs
+    impl<T, U, A: Allocator, H: IndexType, G: GrowthPolicy, $($args)*> PartialEq<$rhs> for $lhs
     where
       T: PartialEq<U>,
     {
@@ -14,23 +17,23 @@ macro_rules! minivec_eq_impl {
   };
 }
 
-minivec_eq_impl! { [] MiniVec<T>, MiniVec<U> }
-minivec_eq_impl! { [] MiniVec<T>, [U] }
-minivec_eq_impl! { [] MiniVec<T>, &[U] }
-minivec_eq_impl! { [] MiniVec<T>, &mut [U] }
-minivec_eq_impl! { [] &[T], MiniVec<U> }
-minivec_eq_impl! { [] &mut [T], MiniVec<U> }
-minivec_eq_impl! { [] MiniVec<T>, alloc::vec::Vec<U> }
-minivec_eq_impl! { [const N: usize] MiniVec<T>, [U; N] }
-minivec_eq_impl! { [const N: usize] MiniVec<T>, &[U; N] }
+minivec_eq_impl! { [B: Allocator, H2: IndexType, G2: GrowthPolicy] MiniVec<T, A, H, G>, MiniVec<U, B, H2, G2> }
+minivec_eq_impl! { [] MiniVec<T, A, H, G>, [U] }
+minivec_eq_impl! { [] MiniVec<T, A, H, G>, &[U] }
+minivec_eq_impl! { [] MiniVec<T, A, H, G>, &mut [U] }
+minivec_eq_impl! { [] &[T], MiniVec<U, A, H, G> }
+minivec_eq_impl! { [] &mut [T], MiniVec<U, A, H, G> }
+minivec_eq_impl! { [] MiniVec<T, A, H, G>, alloc::vec::Vec<U> }
+minivec_eq_impl! { [const N: usize] MiniVec<T, A, H, G>, [U; N] }
+minivec_eq_impl! { [const N: usize] MiniVec<T, A, H, G>, &[U; N] }
 
-impl<T> PartialOrd for MiniVec<T>
+impl<T, A: Allocator, H: IndexType, G: GrowthPolicy> PartialOrd for MiniVec<T, A, H, G>
 where
   T: PartialOrd,
 {
   fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-    let x: &[T] = &**self;
-    let y: &[T] = &**other;
+    let x: &[T] = self;
+    let y: &[T] = other;
     PartialOrd::partial_cmp(x, y)
   }
 }
@@ -1,19 +1,29 @@
+use crate::Allocator;
+use crate::GrowthPolicy;
+use crate::IndexType;
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+use crate::MiniBytes;
 use crate::MiniVec;
+#[cfg(not(feature = "no_global_oom_handling"))]
+use crate::{
+  HeaderVec, MiniBitVec, MiniBoxSlice, MiniHashMap, MiniString, MiniVecDeque, MiniVecMap,
+  MiniVecSet,
+};
 
-use serde::de::{Deserialize, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::de::{Deserialize, DeserializeSeed, Deserializer, Error, SeqAccess, Visitor};
 use serde::ser::{Serialize, Serializer};
 
 use core::marker::PhantomData;
 use core::{cmp, fmt};
 
-impl<T: Serialize> Serialize for MiniVec<T> {
+impl<T: Serialize, A: Allocator, H: IndexType, G: GrowthPolicy> Serialize for MiniVec<T, A, H, G> {
   #[inline]
   fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
     serializer.collect_seq(self)
   }
 }
 
-#[inline(always)]
+#[inline]
 fn map_size_hint(hint: Option<usize>) -> usize {
   match hint {
     Some(hint) => cmp::min(hint, 1024),
@@ -26,7 +36,7 @@ fn map_size_hint(hint: Option<usize>) -> usize {
 //Taken from serde
 struct InPlaceSeed<'a, T: 'a>(pub &'a mut T);
 
-impl<'a, 'de, T: Deserialize<'de>> DeserializeSeed<'de> for InPlaceSeed<'a, T> {
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for InPlaceSeed<'_, T> {
   type Value = ();
 
   #[inline]
@@ -49,10 +59,11 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
       }
 
       fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
-        let mut values = MiniVec::with_capacity(map_size_hint(seq.size_hint()));
+        let mut values =
+          MiniVec::try_with_capacity(map_size_hint(seq.size_hint())).map_err(A::Error::custom)?;
 
         while let Some(value) = seq.next_element()? {
-          values.push(value);
+          values.try_push(value).map_err(A::Error::custom)?;
         }
 
         Ok(values)
@@ -74,7 +85,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
   ) -> Result<(), D::Error> {
     struct VecInPlaceVisitor<'a, T: 'a>(&'a mut MiniVec<T>);
 
-    impl<'a, 'de, T: Deserialize<'de>> Visitor<'de> for VecInPlaceVisitor<'a, T> {
+    impl<'de, T: Deserialize<'de>> Visitor<'de> for VecInPlaceVisitor<'_, T> {
       type Value = ();
 
       fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
@@ -84,7 +95,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
       fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
         let hint = map_size_hint(seq.size_hint());
         if let Some(additional) = hint.checked_sub(self.0.len()) {
-          self.0.reserve(additional);
+          self.0.try_reserve(additional).map_err(A::Error::custom)?;
         }
 
         for i in 0..self.0.len() {
@@ -99,7 +110,7 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
         }
 
         while let Some(value) = seq.next_element()? {
-          self.0.push(value);
+          self.0.try_push(value).map_err(A::Error::custom)?;
         }
 
         Ok(())
@@ -110,6 +121,318 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVec<T> {
   }
 }
 
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<T: Serialize> Serialize for MiniBoxSlice<T> {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.iter())
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniBoxSlice<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    MiniVec::deserialize(deserializer).map(MiniVec::into_boxed_mini_slice)
+  }
+}
+
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+impl Serialize for MiniBytes {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(self)
+  }
+}
+
+#[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+impl<'de> Deserialize<'de> for MiniBytes {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+      type Value = MiniBytes;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+      }
+
+      fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(MiniBytes::copy_from_slice(v))
+      }
+
+      fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut vec = MiniVec::<u8>::try_with_capacity(map_size_hint(seq.size_hint()))
+          .map_err(S::Error::custom)?;
+
+        while let Some(byte) = seq.next_element()? {
+          vec.try_push(byte).map_err(S::Error::custom)?;
+        }
+
+        Ok(vec.freeze())
+      }
+    }
+
+    deserializer.deserialize_bytes(BytesVisitor)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl Serialize for MiniString {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de> Deserialize<'de> for MiniString {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct StringVisitor;
+
+    impl Visitor<'_> for StringVisitor {
+      type Value = MiniString;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+      }
+
+      fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(MiniString::from(v))
+      }
+
+      fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        match core::str::from_utf8(v) {
+          Ok(s) => Ok(MiniString::from(s)),
+          Err(_) => Err(E::invalid_value(serde::de::Unexpected::Bytes(v), &self)),
+        }
+      }
+    }
+
+    deserializer.deserialize_string(StringVisitor)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<T: Serialize> Serialize for MiniVecDeque<T> {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.iter())
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MiniVecDeque<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    MiniVec::deserialize(deserializer).map(MiniVecDeque::from)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<K: Serialize, V: Serialize> Serialize for MiniVecMap<K, V> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    let mut map = serializer.serialize_map(Some(self.len()))?;
+    for (k, v) in self {
+      map.serialize_entry(k, v)?;
+    }
+    map.end()
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, K: Deserialize<'de> + Ord, V: Deserialize<'de>> Deserialize<'de> for MiniVecMap<K, V> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct MapVisitor<K, V>(PhantomData<(K, V)>);
+
+    impl<'de, K: Deserialize<'de> + Ord, V: Deserialize<'de>> Visitor<'de> for MapVisitor<K, V> {
+      type Value = MiniVecMap<K, V>;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+      }
+
+      // the entries are collected as-is and sorted once at the end
+      //
+      fn visit_map<M: serde::de::MapAccess<'de>>(
+        self,
+        mut map: M,
+      ) -> Result<Self::Value, M::Error> {
+        let mut entries =
+          MiniVec::try_with_capacity(map_size_hint(map.size_hint())).map_err(M::Error::custom)?;
+
+        while let Some(entry) = map.next_entry()? {
+          entries.try_push(entry).map_err(M::Error::custom)?;
+        }
+
+        Ok(MiniVecMap::from(entries))
+      }
+    }
+
+    deserializer.deserialize_map(MapVisitor(PhantomData))
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl Serialize for MiniBitVec {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.iter())
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de> Deserialize<'de> for MiniBitVec {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    MiniVec::<bool>::deserialize(deserializer).map(|values| MiniBitVec::from(&values[..]))
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<K: Serialize, V: Serialize, B> Serialize for MiniHashMap<K, V, B> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(self)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, K, V, B> Deserialize<'de> for MiniHashMap<K, V, B>
+where
+  K: Deserialize<'de> + core::hash::Hash + Eq,
+  V: Deserialize<'de>,
+  B: core::hash::BuildHasher + Default,
+{
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct MapVisitor<K, V, B>(PhantomData<(K, V, B)>);
+
+    impl<'de, K, V, B> Visitor<'de> for MapVisitor<K, V, B>
+    where
+      K: Deserialize<'de> + core::hash::Hash + Eq,
+      V: Deserialize<'de>,
+      B: core::hash::BuildHasher + Default,
+    {
+      type Value = MiniHashMap<K, V, B>;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map")
+      }
+
+      fn visit_map<M: serde::de::MapAccess<'de>>(
+        self,
+        mut map: M,
+      ) -> Result<Self::Value, M::Error> {
+        let mut values = MiniHashMap::new();
+        values
+          .try_reserve(map_size_hint(map.size_hint()))
+          .map_err(M::Error::custom)?;
+
+        while let Some((k, v)) = map.next_entry()? {
+          values.try_reserve(1).map_err(M::Error::custom)?;
+          values.insert(k, v);
+        }
+
+        Ok(values)
+      }
+    }
+
+    deserializer.deserialize_map(MapVisitor(PhantomData))
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<T: Serialize> Serialize for MiniVecSet<T> {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.iter())
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for MiniVecSet<T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    MiniVec::deserialize(deserializer).map(MiniVecSet::from)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<H: Serialize, T: Serialize> Serialize for HeaderVec<H, T> {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    (self.header(), self.as_slice()).serialize(serializer)
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<'de, H: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for HeaderVec<H, T> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    //The header comes first so the elements can be read straight into the vector's own allocation
+    //
+    struct ElemsSeed<H, T> {
+      header: H,
+      marker: PhantomData<T>,
+    }
+
+    impl<'de, H, T: Deserialize<'de>> DeserializeSeed<'de> for ElemsSeed<H, T> {
+      type Value = HeaderVec<H, T>;
+
+      #[inline]
+      fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+      }
+    }
+
+    impl<'de, H, T: Deserialize<'de>> Visitor<'de> for ElemsSeed<H, T> {
+      type Value = HeaderVec<H, T>;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = HeaderVec::try_with_capacity(self.header, map_size_hint(seq.size_hint()))
+          .map_err(A::Error::custom)?;
+
+        while let Some(value) = seq.next_element()? {
+          values.try_push(value).map_err(A::Error::custom)?;
+        }
+
+        Ok(values)
+      }
+    }
+
+    struct HeaderVecVisitor<H, T> {
+      marker: PhantomData<(H, T)>,
+    }
+
+    impl<'de, H: Deserialize<'de>, T: Deserialize<'de>> Visitor<'de> for HeaderVecVisitor<H, T> {
+      type Value = HeaderVec<H, T>;
+
+      fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a header followed by a sequence")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let header = seq
+          .next_element()?
+          .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+
+        let seed = ElemsSeed {
+          header,
+          marker: PhantomData,
+        };
+        seq
+          .next_element_seed(seed)?
+          .ok_or_else(|| A::Error::invalid_length(1, &self))
+      }
+    }
+
+    let visitor = HeaderVecVisitor {
+      marker: PhantomData,
+    };
+    deserializer.deserialize_tuple(2, visitor)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use crate::MiniVec;
@@ -120,17 +443,135 @@ mod tests {
   #[test]
   fn should_deserialize() {
     let input = [1u32, 2, 3, 10, 5];
-    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
     let result = MiniVec::<u32>::deserialize(deserializer).expect("To deserialize");
     assert_eq!(result, input);
 
-    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
     let mut vec = MiniVec::<u32>::new();
     MiniVec::<u32>::deserialize_in_place(deserializer, &mut vec).expect("To deserialize");
     assert_eq!(vec, input);
 
-    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().cloned());
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
     MiniVec::<u32>::deserialize_in_place(deserializer, &mut vec).expect("To deserialize");
     assert_eq!(vec, input);
   }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_box_slice() {
+    use crate::MiniBoxSlice;
+
+    let input = [1u32, 2, 3];
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+    let result = MiniBoxSlice::<u32>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(result, input);
+  }
+
+  #[test]
+  #[cfg(all(target_has_atomic = "ptr", not(feature = "no_global_oom_handling")))]
+  fn should_deserialize_bytes() {
+    use crate::MiniBytes;
+    use serde::de::value::BytesDeserializer;
+
+    let deserializer = BytesDeserializer::<ValueError>::new(b"abc");
+    let bytes = MiniBytes::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(bytes, b"abc"[..]);
+
+    let input = [1u8, 2, 3];
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+    let bytes = MiniBytes::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(bytes, input[..]);
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_deque() {
+    use crate::MiniVecDeque;
+
+    let input = [1u32, 2, 3];
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+    let result = MiniVecDeque::<u32>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(result, input);
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_map_and_set() {
+    use crate::{MiniVecMap, MiniVecSet};
+    use serde::de::value::MapDeserializer;
+
+    let input = [(3u32, 'c'), (1, 'a'), (3, 'z')];
+    let deserializer = MapDeserializer::<_, ValueError>::new(input.iter().copied());
+    let map = MiniVecMap::<u32, char>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(map.as_slice(), [(1, 'a'), (3, 'z')]);
+
+    let input = [2u32, 1, 2];
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+    let set = MiniVecSet::<u32>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(set.as_slice(), [1, 2]);
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_bits() {
+    use crate::MiniBitVec;
+
+    let input = [true, false, true];
+    let deserializer = SeqDeserializer::<_, ValueError>::new(input.iter().copied());
+    let bits = MiniBitVec::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(bits, MiniBitVec::from(&input[..]));
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_hash_map() {
+    use crate::MiniHashMap;
+    use serde::de::value::MapDeserializer;
+
+    extern crate std;
+    use std::collections::hash_map::RandomState;
+
+    let input = [(3u32, 'c'), (1, 'a'), (3, 'z')];
+    let deserializer = MapDeserializer::<_, ValueError>::new(input.iter().copied());
+    let map =
+      MiniHashMap::<u32, char, RandomState>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&1], 'a');
+    assert_eq!(map[&3], 'z');
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_string() {
+    use crate::MiniString;
+    use serde::de::value::{BorrowedStrDeserializer, BytesDeserializer};
+
+    let deserializer = BorrowedStrDeserializer::<ValueError>::new("hello");
+    let s = MiniString::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(s, "hello");
+
+    let deserializer = BytesDeserializer::<ValueError>::new(b"\xff");
+    assert!(MiniString::deserialize(deserializer).is_err());
+  }
+
+  #[test]
+  #[cfg(not(feature = "no_global_oom_handling"))]
+  fn should_deserialize_header_vec() {
+    use crate::HeaderVec;
+
+    let header = [7u32, 8];
+    let input = [1u32, 2, 3];
+    let fields = [header.iter().copied(), input.iter().copied()];
+
+    let deserializer =
+      SeqDeserializer::<_, ValueError>::new(fields.iter().cloned().map(SeqDeserializer::new));
+    let vec = HeaderVec::<MiniVec<u32>, u32>::deserialize(deserializer).expect("To deserialize");
+    assert_eq!(*vec.header(), header);
+    assert_eq!(vec.as_slice(), input);
+
+    let deserializer =
+      SeqDeserializer::<_, ValueError>::new(fields[..1].iter().cloned().map(SeqDeserializer::new));
+    assert!(HeaderVec::<MiniVec<u32>, u32>::deserialize(deserializer).is_err());
+  }
 }
@@ -0,0 +1,303 @@
+use crate::{Header, MiniVec};
+
+// every `MiniVec` points at a `Header<usize>`, be it the static empty one or the one at the head of its allocation,
+// which leaves the low bits of the pointer free to hold the tag
+//
+const TAG_MASK: usize = core::mem::align_of::<Header<usize>>() - 1;
+
+/// `TaggedMiniVec` is a [`MiniVec`] which stores a small tag in the low bits of its pointer, bits which are otherwise
+/// always zero because the header it points to is aligned to at least `align_of::<usize>()`. It's still only the size
+/// of a single pointer, making it a good fit for e.g. a list of child nodes which also needs to remember the kind of
+/// its parent.
+///
+/// The tag is any value up to and including [`MAX_TAG`](TaggedMiniVec::MAX_TAG), which is `7` on 64-bit targets.
+///
+/// Elements are read through the slice a `TaggedMiniVec` dereferences to while the full editing API of `MiniVec` is
+/// available through [`edit`](TaggedMiniVec::edit).
+///
+/// # Example
+///
+/// ```
+/// let mut vec = minivec::TaggedMiniVec::from(minivec::mini_vec![1, 2, 3]);
+/// vec.set_tag(5);
+///
+/// vec.edit().push(4);
+///
+/// assert_eq!(vec.tag(), 5);
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// assert_eq!(core::mem::size_of_val(&vec), core::mem::size_of::<usize>());
+/// ```
+///
+pub struct TaggedMiniVec<T> {
+  buf: core::ptr::NonNull<u8>,
+  phantom: core::marker::PhantomData<T>,
+}
+
+/// `TaggedEdit` is a mutable view of the [`MiniVec`] behind a [`TaggedMiniVec`], returned by
+/// [`TaggedMiniVec::edit`]. The vector is handed back along with its tag once it's dropped.
+///
+pub struct TaggedEdit<'a, T> {
+  vec: core::mem::ManuallyDrop<MiniVec<T>>,
+  tagged: &'a mut TaggedMiniVec<T>,
+}
+
+impl<T> TaggedMiniVec<T> {
+  /// `MAX_TAG` is the largest tag a `TaggedMiniVec` can hold.
+  ///
+  pub const MAX_TAG: usize = TAG_MASK;
+
+  /// `as_mut_slice` returns the elements as a mutable slice.
+  ///
+  pub fn as_mut_slice(&mut self) -> &mut [T] {
+    let len = self.len();
+    unsafe { core::slice::from_raw_parts_mut(self.data(), len) }
+  }
+
+  /// `as_slice` returns the elements as a slice.
+  ///
+  #[must_use]
+  pub fn as_slice(&self) -> &[T] {
+    unsafe { core::slice::from_raw_parts(self.data(), self.len()) }
+  }
+
+  /// `capacity` returns the number of elements the vector can hold without reallocating.
+  ///
+  #[must_use]
+  pub fn capacity(&self) -> usize {
+    self.view().capacity()
+  }
+
+  /// `edit` returns a guard which dereferences to the underlying `MiniVec`, giving access to all of its editing
+  /// methods. The tag is kept as is.
+  ///
+  /// While the guard is alive the `TaggedMiniVec` itself is empty, so leaking the guard leaks the elements instead of
+  /// leaving the vector dangling.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// let mut vec = minivec::TaggedMiniVec::<i32>::new();
+  /// vec.set_tag(1);
+  ///
+  /// {
+  ///   let mut edit = vec.edit();
+  ///   edit.extend_from_slice(&[3, 1, 2]);
+  ///   edit.sort();
+  /// }
+  ///
+  /// assert_eq!(vec.tag(), 1);
+  /// assert_eq!(vec, [1, 2, 3]);
+  /// ```
+  ///
+  pub fn edit(&mut self) -> TaggedEdit<'_, T> {
+    let tag = self.tag();
+    let vec = core::mem::ManuallyDrop::new(MiniVec::from_buf(self.untagged()));
+    self.buf = Self::tagged(MiniVec::<T>::empty_buf(), tag);
+
+    TaggedEdit { vec, tagged: self }
+  }
+
+  /// `from_parts` creates a `TaggedMiniVec` out of `vec` and `tag`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `tag > MAX_TAG`.
+  ///
+  #[must_use]
+  pub fn from_parts(vec: MiniVec<T>, tag: usize) -> TaggedMiniVec<T> {
+    let mut tagged = TaggedMiniVec::from(vec);
+    tagged.set_tag(tag);
+    tagged
+  }
+
+  /// `into_parts` splits the `TaggedMiniVec` into its `MiniVec` and its tag.
+  ///
+  #[must_use]
+  pub fn into_parts(self) -> (MiniVec<T>, usize) {
+    let this = core::mem::ManuallyDrop::new(self);
+    (MiniVec::from_buf(this.untagged()), this.tag())
+  }
+
+  /// `is_empty` returns whether or not the vector contains any elements.
+  ///
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// `len` returns the number of elements in the vector.
+  ///
+  #[must_use]
+  pub fn len(&self) -> usize {
+    self.header().len
+  }
+
+  /// `new` creates an empty vector with a tag of `0`. It does not allocate.
+  ///
+  #[must_use]
+  pub const fn new() -> TaggedMiniVec<T> {
+    TaggedMiniVec {
+      buf: MiniVec::<T>::empty_buf(),
+      phantom: core::marker::PhantomData,
+    }
+  }
+
+  /// `set_tag` replaces the tag of the vector.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `tag > MAX_TAG`.
+  ///
+  pub fn set_tag(&mut self, tag: usize) {
+    assert!(
+      (tag <= Self::MAX_TAG),
+      "tag (is {}) should be <= MAX_TAG (is {})",
+      tag,
+      Self::MAX_TAG
+    );
+
+    self.buf = Self::tagged(self.untagged(), tag);
+  }
+
+  /// `tag` returns the tag of the vector.
+  ///
+  #[must_use]
+  pub fn tag(&self) -> usize {
+    self.buf.as_ptr() as usize & TAG_MASK
+  }
+
+  fn data(&self) -> *mut T {
+    self.view().as_ptr().cast_mut()
+  }
+
+  fn header(&self) -> &Header<usize> {
+    #[allow(clippy::cast_ptr_alignment)]
+    unsafe {
+      &*self.untagged().as_ptr().cast::<Header<usize>>()
+    }
+  }
+
+  fn tagged(buf: core::ptr::NonNull<u8>, tag: usize) -> core::ptr::NonNull<u8> {
+    unsafe { core::ptr::NonNull::new_unchecked(buf.as_ptr().wrapping_add(tag)) }
+  }
+
+  fn untagged(&self) -> core::ptr::NonNull<u8> {
+    unsafe { core::ptr::NonNull::new_unchecked(self.buf.as_ptr().wrapping_sub(self.tag())) }
+  }
+
+  // the vector behind the tag, which must never be dropped as it doesn't own the allocation
+  //
+  fn view(&self) -> core::mem::ManuallyDrop<MiniVec<T>> {
+    core::mem::ManuallyDrop::new(MiniVec::from_buf(self.untagged()))
+  }
+}
+
+impl<T> core::ops::Deref for TaggedEdit<'_, T> {
+  type Target = MiniVec<T>;
+
+  fn deref(&self) -> &Self::Target {
+    &self.vec
+  }
+}
+
+impl<T> core::ops::DerefMut for TaggedEdit<'_, T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.vec
+  }
+}
+
+impl<T> Drop for TaggedEdit<'_, T> {
+  fn drop(&mut self) {
+    let tag = self.tagged.tag();
+    self.tagged.buf = TaggedMiniVec::<T>::tagged(self.vec.buf, tag);
+  }
+}
+
+impl<T> Drop for TaggedMiniVec<T> {
+  fn drop(&mut self) {
+    drop(MiniVec::<T>::from_buf(self.untagged()));
+  }
+}
+
+#[cfg(not(feature = "no_global_oom_handling"))]
+impl<T: Clone> Clone for TaggedMiniVec<T> {
+  fn clone(&self) -> Self {
+    let vec = MiniVec::clone(&self.view());
+    TaggedMiniVec::from_parts(vec, self.tag())
+  }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for TaggedMiniVec<T> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("TaggedMiniVec")
+      .field("tag", &self.tag())
+      .field("data", &self.as_slice())
+      .finish()
+  }
+}
+
+impl<T> Default for TaggedMiniVec<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<T> core::ops::Deref for TaggedMiniVec<T> {
+  type Target = [T];
+
+  fn deref(&self) -> &Self::Target {
+    self.as_slice()
+  }
+}
+
+impl<T> core::ops::DerefMut for TaggedMiniVec<T> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.as_mut_slice()
+  }
+}
+
+impl<T: Eq> Eq for TaggedMiniVec<T> {}
+
+impl<T> From<MiniVec<T>> for TaggedMiniVec<T> {
+  fn from(vec: MiniVec<T>) -> Self {
+    let vec = core::mem::ManuallyDrop::new(vec);
+    TaggedMiniVec {
+      buf: vec.buf,
+      phantom: core::marker::PhantomData,
+    }
+  }
+}
+
+impl<T> From<TaggedMiniVec<T>> for MiniVec<T> {
+  fn from(vec: TaggedMiniVec<T>) -> Self {
+    vec.into_parts().0
+  }
+}
+
+impl<T: core::hash::Hash> core::hash::Hash for TaggedMiniVec<T> {
+  fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+    self.tag().hash(state);
+    self.as_slice().hash(state);
+  }
+}
+
+impl<T: PartialEq> PartialEq for TaggedMiniVec<T> {
+  fn eq(&self, other: &Self) -> bool {
+    self.tag() == other.tag() && self.as_slice() == other.as_slice()
+  }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U; N]> for TaggedMiniVec<T> {
+  fn eq(&self, other: &[U; N]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for TaggedMiniVec<T> {
+  fn eq(&self, other: &[U]) -> bool {
+    self.as_slice() == other
+  }
+}
+
+unsafe impl<T: Send> Send for TaggedMiniVec<T> {}
+unsafe impl<T: Sync> Sync for TaggedMiniVec<T> {}
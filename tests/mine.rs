@@ -1,3 +1,5 @@
+#![cfg(not(feature = "no_global_oom_handling"))]
+
 extern crate minivec;
 
 use minivec::{mini_vec, MiniVec};
@@ -12,14 +14,93 @@ use std::{
 #[test]
 fn minivec_default_constructed() {
   let v: MiniVec<i32> = MiniVec::new();
-  assert!(v.capacity() > 0);
+  assert_eq!(v.capacity(), 0);
   assert_eq!(v.len(), 0);
   assert!(v.is_empty());
 
   let v: MiniVec<i32> = Default::default();
-  assert!(v.capacity() > 0);
+  assert_eq!(v.capacity(), 0);
   assert_eq!(v.len(), 0);
   assert!(v.is_empty());
+
+  let v: MiniVec<i32> = mini_vec![];
+  assert_eq!(v.capacity(), 0);
+
+  let v: MiniVec<i32> = MiniVec::with_capacity(0);
+  assert_eq!(v.capacity(), 0);
+}
+
+#[test]
+fn minivec_const_new() {
+  static EMPTY: MiniVec<String> = MiniVec::new();
+  const ALSO_EMPTY: MiniVec<String> = MiniVec::new();
+
+  assert!(EMPTY.is_empty());
+  assert_eq!(EMPTY.as_slice(), ALSO_EMPTY.as_slice());
+
+  let mut v = ALSO_EMPTY;
+  v.push(String::from("hello"));
+  assert_eq!(v, ["hello"]);
+}
+
+#[test]
+fn minivec_empty_allocates_lazily() {
+  let mut v = mini_vec![1, 2, 3];
+  let w = v.drain_vec();
+  assert_eq!(w, [1, 2, 3]);
+  assert_eq!(v.capacity(), 0);
+
+  v.push(4);
+  assert!(v.capacity() > 0);
+
+  v.clear();
+  v.shrink_to_fit();
+  assert_eq!(v.capacity(), 0);
+
+  unsafe { v.set_len(0) };
+  v.extend_from_slice(&[5, 6, 7]);
+  assert_eq!(v, [5, 6, 7]);
+
+  let v = MiniVec::<i32>::new();
+  assert_eq!(v.iter().count(), 0);
+  assert_eq!(v.into_iter().rev().count(), 0);
+
+  let mut v = MiniVec::<i32>::new();
+  assert_eq!(v.drain(..).count(), 0);
+  assert_eq!(v.splice(.., [1, 2]).count(), 0);
+  assert_eq!(v, [1, 2]);
+}
+
+#[test]
+fn minivec_empty_raw_parts() {
+  #[repr(align(512))]
+  #[derive(Clone, Copy)]
+  struct OverAligned {
+    _data: [u8; 512],
+  }
+
+  let v = MiniVec::<i32>::new();
+  let (ptr, len, cap) = v.into_raw_parts();
+  assert_eq!((len, cap), (0, 0));
+  let mut v = unsafe { MiniVec::<i32>::from_raw_parts(ptr, len, cap) };
+  assert_eq!(v.capacity(), 0);
+  v.push(1);
+  assert_eq!(v, [1]);
+
+  let v = MiniVec::<OverAligned>::new();
+  assert_eq!(
+    v.as_ptr() as usize % core::mem::align_of::<OverAligned>(),
+    0
+  );
+  let (ptr, len, cap) = v.into_raw_parts();
+  let mut v = unsafe { MiniVec::<OverAligned>::from_raw_parts(ptr, len, cap) };
+  assert_eq!(v.capacity(), 0);
+  v.push(OverAligned { _data: [1; 512] });
+  assert_eq!(v.len(), 1);
+  assert_eq!(
+    v.as_ptr() as usize % core::mem::align_of::<OverAligned>(),
+    0
+  );
 }
 
 #[test]
@@ -152,6 +233,7 @@ fn minivec_push() {
 }
 
 #[test]
+#[allow(clippy::explicit_auto_deref)]
 fn minivec_deref_test() {
   let mut v: MiniVec<i32> = MiniVec::new();
   v.push(1);
@@ -276,7 +358,7 @@ fn minivec_from_raw_part() {
       ptr::write(p.offset(i), 4 + i);
     }
 
-    let rebuilt = MiniVec::from_raw_part(p);
+    let rebuilt = MiniVec::<isize>::from_raw_part(p);
     assert_eq!(rebuilt, [4, 5, 6]);
     assert_eq!(rebuilt.capacity(), cap);
     assert_eq!(rebuilt.len(), len);
@@ -299,7 +381,7 @@ fn minivec_from_raw_parts() {
       ptr::write(p.offset(i), 4 + i);
     }
 
-    let rebuilt = MiniVec::from_raw_parts(p, len, cap);
+    let rebuilt = MiniVec::<isize>::from_raw_parts(p, len, cap);
     assert_eq!(rebuilt, [4, 5, 6]);
     assert_eq!(rebuilt.capacity(), cap);
     assert_eq!(rebuilt.len(), len);
@@ -464,6 +546,7 @@ fn minivec_shrink_to_panic() {
 }
 
 #[test]
+#[allow(clippy::useless_conversion)]
 fn minivec_extend() {
   let mut v = mini_vec![1, 2, 3];
   let other = mini_vec![4, 5, 6];
@@ -878,7 +961,7 @@ fn minivec_splice() {
   let mut v = mini_vec![1, 2, 3, 4, 5, 6];
   let new = [7, 8, 9, 10, 11];
   let mut iter = v.splice(..4, new.iter().cloned());
-  let mut u = MiniVec::new();
+  let mut u = MiniVec::<i32>::new();
 
   u.push(iter.next().unwrap());
   u.push(iter.next().unwrap());
@@ -992,7 +1075,7 @@ fn minivec_splice_raii() {
   ];
 
   let mut iter = v.splice(..4, new.iter().cloned());
-  let mut u = MiniVec::new();
+  let mut u = MiniVec::<String>::new();
 
   u.push(iter.next().unwrap());
   u.push(iter.next().unwrap());
@@ -1068,7 +1151,7 @@ fn minivec_into_raw_parts() {
   assert_eq!(len, old_len);
   assert_eq!(cap, old_cap);
 
-  let vec = unsafe { minivec::MiniVec::from_raw_parts(ptr, len, cap) };
+  let vec = unsafe { minivec::MiniVec::<i32>::from_raw_parts(ptr, len, cap) };
   assert_eq!(vec, [1, 2, 3, 4, 5]);
 }
 
@@ -1080,7 +1163,7 @@ fn minivec_split_at_spare_mut() {
 
   let (init, uninit) = vec.split_at_spare_mut();
   assert_eq!(init, []);
-  assert!(!uninit.is_empty());
+  assert!(uninit.is_empty());
 
   // Copy type
   //
@@ -1266,3 +1349,2048 @@ fn minivec_try_reserve_exact() {
   assert!(result.is_ok());
   assert_eq!(v.capacity(), 1337);
 }
+
+#[test]
+fn minivec_zst_drops() {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+
+  static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+  struct Zst;
+
+  impl Drop for Zst {
+    fn drop(&mut self) {
+      DROPS.fetch_add(1, Ordering::SeqCst);
+    }
+  }
+
+  let mut v = minivec::MiniVec::<Zst>::new();
+  for _ in 0..16 {
+    v.push(Zst);
+  }
+
+  assert_eq!(v.len(), 16);
+  assert_eq!(v.capacity(), usize::MAX);
+
+  v.truncate(12);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 4);
+
+  v.retain({
+    let mut keep = false;
+    move |_| {
+      keep = !keep;
+      keep
+    }
+  });
+  assert_eq!(v.len(), 6);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 10);
+
+  v.dedup_by(|_, _| true);
+  assert_eq!(v.len(), 1);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 15);
+
+  v.extend((0..4).map(|_| Zst));
+  drop(v.drain(1..3));
+  assert_eq!(v.len(), 3);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 17);
+
+  drop(v.into_iter());
+  assert_eq!(DROPS.load(Ordering::SeqCst), 20);
+
+  // an empty vector already reports room for its ZSTs so writing its length allocates a header of its own
+  //
+  let mut v = minivec::MiniVec::<Zst>::new();
+  assert_eq!(v.spare_capacity_mut().len(), usize::MAX);
+  unsafe { v.set_len(2) };
+  assert_eq!(v.len(), 2);
+  assert_eq!(v.capacity(), usize::MAX);
+
+  drop(v);
+  assert_eq!(DROPS.load(Ordering::SeqCst), 22);
+}
+
+#[test]
+fn minivec_with_alignment() {
+  use minivec::{LayoutErr, MiniVec};
+
+  assert!(matches!(
+    MiniVec::<i32>::with_alignment(16, 0),
+    Err(LayoutErr::AlignmentNotDivisibleByTwo)
+  ));
+
+  assert!(matches!(
+    MiniVec::<i32>::with_alignment(16, 48),
+    Err(LayoutErr::AlignmentNotDivisibleByTwo)
+  ));
+
+  assert!(matches!(
+    MiniVec::<i32>::with_alignment(16, 2),
+    Err(LayoutErr::AlignmentTooSmall)
+  ));
+
+  for alignment in [8, 16, 32, 64, 4096] {
+    let is_aligned = |v: &MiniVec<i32>| (v.as_ptr() as usize).is_multiple_of(alignment);
+
+    let mut vec = MiniVec::<i32>::with_alignment(16, alignment).unwrap();
+    assert!(is_aligned(&vec));
+    assert_eq!(vec.capacity(), 16);
+
+    vec.extend(0..1000);
+    assert!(is_aligned(&vec));
+
+    vec.truncate(500);
+    vec.shrink_to(500);
+    assert!(is_aligned(&vec));
+    assert!(vec.capacity() >= 500);
+
+    let cloned = vec.clone();
+    assert!(is_aligned(&cloned));
+    assert_eq!(cloned, vec);
+
+    let tail = vec.split_off(100);
+    assert!(is_aligned(&tail));
+    assert_eq!(tail.len(), 400);
+    assert_eq!(tail[0], 100);
+
+    let head = vec.split_off(0);
+    assert!(is_aligned(&head));
+    assert!(is_aligned(&vec));
+
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 0);
+    vec.push(1337);
+    assert!(is_aligned(&vec));
+
+    let drained = vec.drain_vec();
+    assert!(is_aligned(&drained));
+    vec.push(7331);
+    assert!(is_aligned(&vec));
+
+    let (ptr, len, cap) = vec.into_raw_parts();
+    let vec = unsafe { MiniVec::<i32>::from_raw_parts(ptr, len, cap) };
+    assert_eq!(vec, [7331]);
+  }
+
+  let mut zsts = MiniVec::<()>::with_alignment(0, 64).unwrap();
+  assert_eq!(zsts.as_ptr() as usize % 64, 0);
+  zsts.push(());
+  zsts.push(());
+  assert_eq!(zsts.len(), 2);
+  assert_eq!(zsts.capacity(), usize::MAX);
+}
+
+#[derive(Clone)]
+struct CountingAllocator<'a> {
+  live: &'a core::cell::Cell<isize>,
+}
+
+unsafe impl minivec::Allocator for CountingAllocator<'_> {
+  fn allocate(
+    &self,
+    layout: std::alloc::Layout,
+  ) -> Result<core::ptr::NonNull<[u8]>, minivec::AllocError> {
+    self.live.set(self.live.get() + 1);
+    minivec::Global.allocate(layout)
+  }
+
+  unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+    self.live.set(self.live.get() - 1);
+    minivec::Global.deallocate(ptr, layout);
+  }
+}
+
+#[test]
+fn minivec_stateful_allocator() {
+  use minivec::MiniVec;
+
+  let live = core::cell::Cell::new(0);
+
+  {
+    let alloc = CountingAllocator { live: &live };
+
+    let mut vec = MiniVec::<String, _>::new_in(alloc.clone());
+    assert_eq!(live.get(), 1);
+    assert_eq!(vec.capacity(), 0);
+    assert!(core::ptr::eq(vec.allocator().live, &live));
+
+    for i in 0..100 {
+      vec.push(i.to_string());
+    }
+    assert_eq!(live.get(), 1);
+    assert_eq!(vec.len(), 100);
+    assert_eq!(vec[99], "99");
+
+    let cloned = vec.clone();
+    assert_eq!(live.get(), 2);
+    assert_eq!(cloned, vec);
+
+    let tail = vec.split_off(50);
+    assert_eq!(live.get(), 3);
+    assert_eq!(tail[0], "50");
+
+    vec.truncate(0);
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 0);
+    assert_eq!(live.get(), 3);
+
+    vec.extend(tail.into_iter().rev());
+    assert_eq!(live.get(), 2);
+    assert_eq!(vec[0], "99");
+
+    let drained: MiniVec<String> = vec.drain(..10).collect();
+    assert_eq!(drained.len(), 10);
+
+    let with_capacity = MiniVec::<u8, _>::with_capacity_in(64, &alloc);
+    assert_eq!(with_capacity.capacity(), 64);
+    assert_eq!(live.get(), 3);
+  }
+
+  assert_eq!(live.get(), 0);
+  assert_eq!(
+    core::mem::size_of::<MiniVec<i32, CountingAllocator<'_>>>(),
+    core::mem::size_of::<*const ()>()
+  );
+}
+
+#[test]
+fn minivec_zero_sized_allocator() {
+  use minivec::{Global, MiniVec};
+
+  #[derive(Clone, Copy)]
+  struct Zst;
+
+  unsafe impl minivec::Allocator for Zst {
+    fn allocate(
+      &self,
+      layout: std::alloc::Layout,
+    ) -> Result<core::ptr::NonNull<[u8]>, minivec::AllocError> {
+      minivec::Allocator::allocate(&Global, layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: core::ptr::NonNull<u8>, layout: std::alloc::Layout) {
+      minivec::Allocator::deallocate(&Global, ptr, layout);
+    }
+  }
+
+  assert_eq!(
+    core::mem::size_of::<MiniVec<i32, Zst>>(),
+    core::mem::size_of::<*const ()>()
+  );
+
+  let mut vec = MiniVec::<i32, _>::new_in(Zst);
+  assert_eq!(vec.capacity(), 0);
+
+  vec.extend(0..32);
+  assert_eq!(vec.len(), 32);
+
+  vec.clear();
+  vec.shrink_to_fit();
+  assert_eq!(vec.capacity(), 0);
+
+  let vec = MiniVec::<i32, _>::with_capacity_in(16, Zst);
+  assert_eq!(vec.capacity(), 16);
+}
+
+#[test]
+fn minivec_compact_header() {
+  use minivec::{CompactMiniVec, Global, MiniVec, TryReserveErrorKind};
+
+  assert_eq!(
+    core::mem::size_of::<CompactMiniVec<i32>>(),
+    core::mem::size_of::<*const ()>()
+  );
+
+  let mut vec = CompactMiniVec::<i32>::default();
+  assert_eq!(vec.capacity(), 0);
+
+  for i in 0..128 {
+    vec.push(i);
+  }
+  assert_eq!(vec.len(), 128);
+  assert!(vec.capacity() >= 128);
+  assert!(vec.iter().copied().eq(0..128));
+
+  vec.retain(|x| x % 2 == 0);
+  assert_eq!(vec.len(), 64);
+
+  let drained: Vec<i32> = vec.drain(..4).collect();
+  assert_eq!(drained, [0, 2, 4, 6]);
+  assert_eq!(vec.pop(), Some(126));
+
+  let copy = vec.clone();
+  assert_eq!(copy, vec);
+
+  let collected: CompactMiniVec<i32> = vec.into_iter().rev().collect();
+  assert_eq!(collected.len(), 59);
+  assert_eq!(collected[0], 124);
+
+  let vec = CompactMiniVec::<i32>::new();
+  assert_eq!(vec.capacity(), 0);
+
+  let vec = CompactMiniVec::<i32>::with_capacity(16);
+  assert_eq!(vec.capacity(), 16);
+
+  let vec = CompactMiniVec::try_from_iter(0..4).unwrap();
+  assert_eq!(vec, [0, 1, 2, 3]);
+
+  // the top bit of the stored capacity is reserved so a `u16` header can count up to `i16::MAX`
+  //
+  let mut vec = MiniVec::<u8, Global, u16>::new_in(Global);
+  assert!(vec.try_reserve_exact(i16::MAX as usize).is_ok());
+  assert_eq!(vec.capacity(), i16::MAX as usize);
+
+  vec.resize(i16::MAX as usize, 0);
+  assert_eq!(
+    vec.try_reserve(1).unwrap_err().kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+  assert_eq!(
+    vec.try_reserve_exact(1).unwrap_err().kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+
+  let mut vec = CompactMiniVec::<u8>::default();
+  assert_eq!(
+    vec.try_reserve(u32::MAX as usize).unwrap_err().kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+  assert_eq!(vec.capacity(), 0);
+
+  // ZSTs can count as high as the index type can
+  //
+  let mut vec = MiniVec::<(), Global, u16>::default();
+  assert_eq!(vec.capacity(), u16::MAX as usize);
+  vec.push(());
+  assert_eq!(vec.capacity(), u16::MAX as usize);
+  vec.extend(core::iter::repeat_n((), u16::MAX as usize - 1));
+  assert_eq!(vec.len(), u16::MAX as usize);
+  assert_eq!(
+    vec.try_reserve(1).unwrap_err().kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+
+  let mut vec = CompactMiniVec::<()>::default();
+  assert!(vec.try_reserve_exact(u32::MAX as usize).is_ok());
+  assert_eq!(vec.capacity(), u32::MAX as usize);
+
+  let vec = MiniVec::<(), Global, u16>::with_alignment(1, 64).unwrap();
+  assert_eq!(vec.as_ptr() as usize % 64, 0);
+  assert_eq!(vec.capacity(), u16::MAX as usize);
+}
+
+#[test]
+#[should_panic(expected = "capacity overflow")]
+fn minivec_compact_header_overflow() {
+  let mut vec = minivec::MiniVec::<u8, minivec::Global, u16>::default();
+  vec.resize(i16::MAX as usize + 1, 0);
+}
+
+#[test]
+fn minivec_growth_policy() {
+  use minivec::{FixedIncrement, Global, MiniVec, OneAndAHalf, PageRounded};
+
+  let mut vec = MiniVec::<i32, Global, usize, FixedIncrement<2>>::default();
+  let mut capacities = Vec::new();
+  for i in 0..7 {
+    vec.push(i);
+    capacities.push(vec.capacity());
+  }
+  assert_eq!(capacities, [2, 2, 4, 4, 6, 6, 8]);
+
+  vec.insert(0, -1);
+  assert_eq!(vec.capacity(), 8);
+  vec.insert(0, -2);
+  assert_eq!(vec.capacity(), 10);
+
+  vec.reserve(5);
+  assert_eq!(vec.capacity(), 14);
+
+  // `reserve_exact` bypasses the policy
+  //
+  vec.reserve_exact(5);
+  assert_eq!(vec.capacity(), 14);
+  vec.reserve_exact(6);
+  assert_eq!(vec.capacity(), 15);
+
+  let mut vec = MiniVec::<i32, Global, usize, OneAndAHalf>::default();
+  vec.extend(core::iter::repeat_n(0, 4));
+  assert_eq!(vec.capacity(), 4);
+  vec.push(0);
+  assert_eq!(vec.capacity(), 6);
+  vec.extend([0, 0]);
+  assert_eq!(vec.capacity(), 9);
+
+  let mut vec = MiniVec::<i32, Global, usize, OneAndAHalf>::with_capacity(3);
+  assert_eq!(vec.capacity(), 3);
+  vec.extend([0; 4]);
+  assert_eq!(vec.capacity(), 4);
+
+  // the first allocation is sized by the policy unless more is required
+  //
+  let mut vec = MiniVec::<i32, Global, usize, FixedIncrement<4>>::new();
+  vec.reserve(6);
+  assert_eq!(vec.capacity(), 6);
+
+  let mut vec = MiniVec::<u64, Global, usize, PageRounded>::default();
+  vec.push(0);
+  assert_eq!(vec.capacity(), 512);
+  vec.resize(513, 0);
+  assert_eq!(vec.capacity(), 1024);
+
+  // growth past the size hint also follows the policy
+  //
+  let vec: MiniVec<i32, Global, usize, FixedIncrement<3>> =
+    (0..10).filter(|x| x % 2 == 0).collect();
+  assert_eq!(vec, [0, 2, 4, 6, 8]);
+  assert_eq!(vec.capacity(), 7);
+}
+
+#[test]
+fn minivec_fallible_allocation() {
+  use minivec::TryReserveErrorKind;
+
+  let mut vec = MiniVec::<i32>::try_with_capacity(4).unwrap();
+  assert_eq!(vec.capacity(), 4);
+
+  *vec.try_push(1).unwrap() += 1;
+  vec.try_insert(0, 0).unwrap();
+  vec.try_extend_from_slice(&[3, 4, 5]).unwrap();
+  assert_eq!(vec, [0, 2, 3, 4, 5]);
+
+  vec.try_resize(7, 6).unwrap();
+  assert_eq!(vec, [0, 2, 3, 4, 5, 6, 6]);
+
+  let tail = vec.try_split_off(5).unwrap();
+  assert_eq!(vec, [0, 2, 3, 4, 5]);
+  assert_eq!(tail, [6, 6]);
+
+  let copy = vec.try_clone().unwrap();
+  assert_eq!(copy, vec);
+
+  let vec = MiniVec::<i32>::try_from_iter((0..3).map(|x| x * 3)).unwrap();
+  assert_eq!(vec, [0, 3, 6]);
+
+  assert_eq!(
+    MiniVec::<i32>::try_with_capacity(usize::MAX)
+      .unwrap_err()
+      .kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+
+  // a failed attempt leaves the vector untouched
+  //
+  let mut vec = minivec::MiniVec::<u8, minivec::Global, u16>::default();
+  vec.try_push(1).unwrap();
+  assert_eq!(
+    vec.try_resize(u16::MAX as usize, 0).unwrap_err().kind(),
+    TryReserveErrorKind::CapacityOverflow
+  );
+  assert_eq!(vec, [1]);
+}
+
+#[test]
+fn minivec_checked_editing() {
+  use minivec::{CheckedError, IndexError, RangeError, TryReserveErrorKind};
+
+  let mut vec = mini_vec![1, 2, 3, 4];
+
+  assert_eq!(
+    vec.checked_insert(5, 0),
+    Err(CheckedError::Index(IndexError { index: 5, len: 4 }))
+  );
+  assert_eq!(vec.checked_insert(4, 5), Ok(()));
+  assert_eq!(vec, [1, 2, 3, 4, 5]);
+
+  assert_eq!(
+    vec.checked_remove(5).unwrap_err(),
+    IndexError { index: 5, len: 5 }
+  );
+  assert_eq!(vec.checked_remove(0), Ok(1));
+  assert_eq!(
+    vec.checked_swap_remove(4).unwrap_err(),
+    IndexError { index: 4, len: 4 }
+  );
+  assert_eq!(vec.checked_swap_remove(0), Ok(2));
+  assert_eq!(vec, [5, 3, 4]);
+
+  assert_eq!(
+    vec.checked_split_off(4).unwrap_err(),
+    CheckedError::Index(IndexError { index: 4, len: 3 })
+  );
+  assert_eq!(vec.checked_split_off(3).unwrap(), []);
+
+  assert_eq!(
+    vec
+      .try_drain((core::ops::Bound::Included(2), core::ops::Bound::Excluded(1)))
+      .err(),
+    Some(RangeError::StartAfterEnd { start: 2, end: 1 })
+  );
+  assert_eq!(
+    vec
+      .try_drain((
+        core::ops::Bound::Excluded(usize::MAX),
+        core::ops::Bound::Unbounded
+      ))
+      .err(),
+    Some(RangeError::StartOverflow)
+  );
+  assert_eq!(
+    vec.try_splice(..=3, []).err(),
+    Some(RangeError::EndOutOfBounds { end: 4, len: 3 })
+  );
+  assert_eq!(
+    vec.try_extend_from_within(..=usize::MAX),
+    Err(CheckedError::Range(RangeError::EndOverflow))
+  );
+  assert_eq!(vec, [5, 3, 4]);
+
+  assert_eq!(vec.try_drain(..1).unwrap().collect::<Vec<_>>(), [5]);
+  assert_eq!(
+    vec.try_splice(1.., [6, 7]).unwrap().collect::<Vec<_>>(),
+    [4]
+  );
+  vec.try_extend_from_within(1..).unwrap();
+  assert_eq!(vec, [3, 6, 7, 6, 7]);
+
+  assert_eq!(
+    IndexError { index: 5, len: 4 }.to_string(),
+    "index (is 5) is out of bounds for a vector of len (is 4)"
+  );
+
+  // running out of room is reported instead of aborting
+  //
+  let mut vec = MiniVec::<u8, minivec::Global, u16>::new_in(minivec::Global);
+  vec.resize(i16::MAX as usize, 0);
+
+  let Err(CheckedError::Reserve(e)) = vec.checked_insert(0, 1) else {
+    panic!("checked_insert should fail to grow");
+  };
+  assert_eq!(e.kind(), TryReserveErrorKind::CapacityOverflow);
+
+  let Err(CheckedError::Reserve(e)) = vec.try_extend_from_within(..1) else {
+    panic!("try_extend_from_within should fail to grow");
+  };
+  assert_eq!(e.kind(), TryReserveErrorKind::CapacityOverflow);
+  assert_eq!(vec.len(), i16::MAX as usize);
+}
+
+#[test]
+#[should_panic(expected = "invalid drain range: end index (is 4) should be <= len (is 3)")]
+fn minivec_drain_out_of_bounds() {
+  let mut vec = mini_vec![1, 2, 3];
+  vec.drain(..4);
+}
+
+#[test]
+fn ministring() {
+  use core::fmt::Write;
+  use minivec::MiniString;
+
+  assert_eq!(
+    core::mem::size_of::<Option<MiniString>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut s = MiniString::new();
+  s.push_str("hello");
+  s.push(' ');
+  let world = "wörld";
+  write!(s, "{world} {}", 1).unwrap();
+  assert_eq!(s, "hello wörld 1");
+  assert_eq!(s.len(), 14);
+
+  s.insert_str(0, ">> ");
+  s.insert(3, 'ß');
+  assert_eq!(s, ">> ßhello wörld 1");
+  assert_eq!(s.remove(3), 'ß');
+  assert_eq!(s.pop(), Some('1'));
+
+  let drained: MiniString = s.drain(3..8).collect();
+  assert_eq!(drained, "hello");
+  assert_eq!(s, ">>  wörld ");
+
+  // a partially consumed drain still removes the whole range
+  //
+  let mut drain = s.drain(4..);
+  assert_eq!(drain.next(), Some('w'));
+  assert_eq!(drain.next_back(), Some(' '));
+  assert_eq!(drain.as_str(), "örld");
+  drop(drain);
+  assert_eq!(s, ">>  ");
+
+  s.retain(|c| c != ' ');
+  assert_eq!(s, ">>");
+
+  let s: MiniString = "ab".chars().chain("cd".chars()).collect();
+  assert_eq!(s, "abcd");
+  assert_eq!(s.to_uppercase(), "ABCD");
+  assert_eq!(&s[1..3], "bc");
+
+  let words: MiniString = ["foo", "bar"].iter().copied().collect();
+  assert_eq!(words, String::from("foobar"));
+
+  let (a, ab, b) = (
+    MiniString::from("a"),
+    MiniString::from("ab"),
+    MiniString::from("b"),
+  );
+  assert!(a < b);
+  assert_eq!(a.cmp(&ab), "a".cmp("ab"));
+
+  let mut set = std::collections::HashSet::new();
+  set.insert(MiniString::from("key"));
+  assert!(set.contains("key"));
+
+  let err = MiniString::from_utf8(mini_vec![b'a', 0xc0]).unwrap_err();
+  assert_eq!(err.utf8_error().valid_up_to(), 1);
+  assert_eq!(err.into_bytes(), [b'a', 0xc0]);
+  assert_eq!(MiniString::from_utf8_lossy(b"a\xc0b"), "a\u{FFFD}b");
+
+  let mut s = MiniString::from("Hello, World!");
+  let world = s.split_off(7);
+  assert_eq!((s.as_str(), world.as_str()), ("Hello, ", "World!"));
+  assert_eq!(format!("{:?}", world), "\"World!\"");
+}
+
+#[test]
+#[should_panic(expected = "drain start (is 1) is not a char boundary")]
+fn ministring_drain_char_boundary() {
+  let mut s = minivec::MiniString::from("ß");
+  s.drain(1..);
+}
+
+#[test]
+fn mini_box_slice() {
+  use minivec::MiniBoxSlice;
+
+  assert_eq!(
+    core::mem::size_of::<Option<MiniBoxSlice<String>>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let empty = MiniBoxSlice::<String>::new();
+  assert!(empty.is_empty());
+  assert_eq!(empty.into_mini_vec().capacity(), 0);
+
+  let mut vec = MiniVec::<String>::with_capacity(32);
+  vec.extend((0..4).map(|x| x.to_string()));
+
+  let mut slice = vec.into_boxed_mini_slice();
+  assert_eq!(slice.len(), 4);
+  assert_eq!(slice, ["0", "1", "2", "3"]);
+
+  slice[0].push('!');
+  slice.sort_by(|a, b| b.cmp(a));
+  assert_eq!(slice, ["3", "2", "1", "0!"]);
+
+  let copy = slice.clone();
+  assert_eq!(copy, slice);
+
+  let mut iter = copy.into_iter();
+  assert_eq!(iter.next().as_deref(), Some("3"));
+  assert_eq!(iter.next_back().as_deref(), Some("0!"));
+  assert_eq!(iter.as_slice(), ["2", "1"]);
+  drop(iter);
+
+  let vec = slice.into_mini_vec();
+  assert_eq!(vec.capacity(), 4);
+  assert_eq!(vec, ["3", "2", "1", "0!"]);
+
+  let zsts: MiniBoxSlice<()> = core::iter::repeat_n((), 10).collect();
+  assert_eq!(zsts.len(), 10);
+  assert_eq!(zsts.into_iter().count(), 10);
+
+  #[repr(align(64))]
+  #[derive(Clone, Copy, Debug, PartialEq)]
+  struct OverAligned(u8);
+
+  let slice = MiniBoxSlice::from(&[OverAligned(1), OverAligned(2)][..]);
+  assert_eq!(slice.as_ptr() as usize % 64, 0);
+  assert_eq!(slice, [OverAligned(1), OverAligned(2)]);
+
+  let vec = slice.into_mini_vec();
+  assert_eq!(vec.as_ptr() as usize % 64, 0);
+  assert_eq!(vec, [OverAligned(1), OverAligned(2)]);
+  assert_eq!(
+    vec.into_boxed_mini_slice(),
+    [OverAligned(1), OverAligned(2)]
+  );
+
+  // vectors whose allocation isn't aligned like the slice's have their elements copied instead
+  //
+  let mut vec = MiniVec::<u8, minivec::Global, u16>::with_capacity(8);
+  vec.extend_from_slice(b"abc");
+  let slice = MiniBoxSlice::from(vec);
+  assert_eq!(slice, *b"abc");
+
+  let mut vec = MiniVec::<u8, minivec::Global, u16>::with_alignment(8, 8).unwrap();
+  vec.extend_from_slice(b"xyz");
+  assert_eq!(vec.into_boxed_mini_slice(), *b"xyz");
+
+  let mut vec = MiniVec::<String>::with_alignment(4, 128).unwrap();
+  vec.push(String::from("over-aligned"));
+  assert_eq!(vec.into_boxed_mini_slice(), ["over-aligned"]);
+
+  let vec = core::iter::repeat_n((), 3)
+    .collect::<MiniBoxSlice<()>>()
+    .into_mini_vec();
+  assert_eq!(vec.len(), 3);
+  assert_eq!(vec.capacity(), usize::MAX);
+}
+
+#[test]
+fn mini_shared() {
+  use minivec::{MiniArc, MiniRc};
+
+  assert_eq!(
+    core::mem::size_of::<Option<MiniArc<String>>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let rc: MiniRc<String> = (0..3).map(|x| x.to_string()).collect();
+  let weak = MiniRc::downgrade(&rc);
+  let other = rc.clone();
+  assert_eq!(MiniRc::strong_count(&rc), 2);
+  assert_eq!(MiniRc::weak_count(&rc), 1);
+  assert!(MiniRc::ptr_eq(&rc, &other));
+
+  // shared elements are cloned before they're handed out mutably
+  //
+  let mut rc = rc;
+  MiniRc::make_mut(&mut rc)[0].push('!');
+  assert_eq!(*rc, ["0!", "1", "2"]);
+  assert_eq!(*other, ["0", "1", "2"]);
+  assert!(!MiniRc::ptr_eq(&rc, &other));
+  assert!(MiniRc::get_mut(&mut rc).is_some());
+
+  // the only strong handle left still can't hand out its elements mutably while a weak one exists
+  //
+  let mut other = other;
+  assert!(MiniRc::get_mut(&mut other).is_none());
+  assert_eq!(weak.upgrade().as_deref(), Some(&other[..]));
+
+  let vec = MiniRc::try_unwrap(other).unwrap();
+  assert_eq!(vec, ["0", "1", "2"]);
+  assert!(weak.upgrade().is_none());
+  assert_eq!(weak.strong_count(), 0);
+  assert_eq!(weak.weak_count(), 0);
+
+  // the allocation of a vector whose header has room for the counts is reused in both directions
+  //
+  #[repr(align(32))]
+  #[derive(Clone, Debug, PartialEq)]
+  struct Aligned(String);
+
+  let mut vec = MiniVec::<Aligned>::with_capacity(8);
+  vec.push(Aligned(String::from("a")));
+  vec.push(Aligned(String::from("b")));
+  let data = vec.as_ptr();
+
+  let arc = MiniArc::from(vec);
+  assert_eq!(arc.as_ptr(), data);
+
+  let vec = MiniArc::try_unwrap(arc).unwrap();
+  assert_eq!(vec.as_ptr(), data);
+  assert_eq!(vec.capacity(), 8);
+  assert_eq!(
+    vec,
+    [Aligned(String::from("a")), Aligned(String::from("b"))]
+  );
+
+  let arc = MiniArc::from(mini_vec![1, 2, 3]);
+  let threads: Vec<_> = (0..4)
+    .map(|_| {
+      let arc = arc.clone();
+      std::thread::spawn(move || arc.iter().sum::<i32>())
+    })
+    .collect();
+
+  for t in threads {
+    assert_eq!(t.join().unwrap(), 6);
+  }
+  assert_eq!(MiniArc::strong_count(&arc), 1);
+
+  let empty = MiniArc::<String>::default();
+  assert!(empty.is_empty());
+  assert_eq!(MiniArc::try_unwrap(empty).unwrap().len(), 0);
+}
+
+#[test]
+fn mini_shared_get_mut_race() {
+  use minivec::MiniArc;
+
+  // another strong handle downgrading and then dropping itself must never let `get_mut` through while the new weak
+  // handle is still alive
+  //
+  for _ in 0..200 {
+    let mut arc = MiniArc::from(mini_vec![0]);
+    let other = arc.clone();
+
+    let t = std::thread::spawn(move || {
+      let weak = MiniArc::downgrade(&other);
+      drop(other);
+      weak.upgrade().map(|arc| arc[0])
+    });
+
+    loop {
+      if let Some(slice) = MiniArc::get_mut(&mut arc) {
+        slice[0] = 1;
+        break;
+      }
+
+      std::thread::yield_now();
+    }
+
+    assert_eq!(MiniArc::strong_count(&arc), 1);
+    assert_eq!(MiniArc::weak_count(&arc), 0);
+    assert!(matches!(t.join().unwrap(), None | Some(0)));
+  }
+}
+
+#[test]
+fn mini_bytes() {
+  use minivec::MiniBytes;
+
+  let mut vec = MiniVec::with_capacity(32);
+  vec.extend_from_slice(b"GET /index.html HTTP/1.1");
+  let data = vec.as_ptr();
+
+  let mut bytes = vec.freeze();
+  assert_eq!(bytes.as_ptr(), data);
+  assert_eq!(bytes.len(), 24);
+
+  let method = bytes.split_to(4);
+  let version = bytes.split_off(11);
+  assert_eq!(method, b"GET "[..]);
+  assert_eq!(bytes, b"/index.html"[..]);
+  assert_eq!(version, b" HTTP/1.1"[..]);
+  assert_eq!(version.slice(1..5), b"HTTP"[..]);
+  assert_eq!(method.as_ptr(), data);
+
+  let path = bytes.clone();
+  let bytes = bytes.try_into_mut().unwrap_err();
+  drop((method, version, path));
+
+  // the last view reuses the allocation, shifting its bytes down to the front
+  //
+  let mut vec = bytes.try_into_mut().unwrap();
+  assert_eq!(vec.as_ptr(), data);
+  assert_eq!(vec.capacity(), 32);
+  assert_eq!(vec, b"/index.html");
+  vec.push(b'?');
+  assert_eq!(vec, b"/index.html?");
+
+  let bytes: MiniBytes = (0..10_u8).collect();
+  let threads: Vec<_> = (0..4)
+    .map(|i| {
+      let view = bytes.slice(i..i + 4);
+      std::thread::spawn(move || view.iter().map(|&b| u32::from(b)).sum::<u32>())
+    })
+    .collect();
+
+  for (i, t) in threads.into_iter().enumerate() {
+    assert_eq!(t.join().unwrap(), 4 * i as u32 + 6);
+  }
+  assert_eq!(bytes.try_into_mut().unwrap().len(), 10);
+
+  let empty = MiniBytes::new();
+  assert!(empty.slice(..).is_empty());
+  assert_eq!(MiniVec::new().freeze(), empty);
+  assert_eq!(empty.try_into_mut().unwrap().capacity(), 0);
+
+  let bytes = MiniBytes::copy_from_slice(b"abc");
+  let result = catch_unwind(|| bytes.slice(2..4));
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_vec_deque() {
+  use minivec::MiniVecDeque;
+
+  let mut deque = MiniVecDeque::with_capacity(4);
+  assert_eq!(deque.capacity(), 4);
+
+  deque.push_back(String::from("b"));
+  deque.push_back(String::from("c"));
+  deque.push_front(String::from("a"));
+  deque.push_back(String::from("d"));
+  assert_eq!(deque.capacity(), 4);
+  assert_eq!(deque, ["a", "b", "c", "d"]);
+  assert_eq!(deque.as_slices().0, ["a"]);
+  assert_eq!(deque.front().unwrap(), "a");
+  assert_eq!(deque.back().unwrap(), "d");
+  assert_eq!(deque[2], "c");
+
+  // growing unwraps the elements into a single slice
+  //
+  deque.push_front(String::from("_"));
+  assert_eq!(deque, ["_", "a", "b", "c", "d"]);
+  assert_eq!(deque.pop_front().unwrap(), "_");
+  assert_eq!(deque.pop_back().unwrap(), "d");
+
+  let mut hasher = DefaultHasher::new();
+  deque.hash(&mut hasher);
+  let mut other = DefaultHasher::new();
+  ["a", "b", "c"].map(String::from).hash(&mut other);
+  assert_eq!(hasher.finish(), other.finish());
+
+  // drains close the gap from whichever side is shorter, wrapped around or not
+  //
+  for start in 0..6 {
+    for end in start..6 {
+      for offset in 0..8 {
+        let mut deque = MiniVecDeque::with_capacity(8);
+        for _ in 0..offset {
+          deque.push_back(0);
+          deque.pop_front();
+        }
+        deque.extend(0..6);
+
+        let drained: Vec<_> = deque.drain(start..end).collect();
+        let mut expected: Vec<_> = (0..6).collect();
+        assert_eq!(drained, expected.drain(start..end).collect::<Vec<_>>());
+        assert_eq!(deque, expected[..]);
+      }
+    }
+  }
+
+  let mut deque: MiniVecDeque<_> = (0..5).collect();
+  drop(deque.drain(..2));
+  deque.extend(&[5, 6]);
+  assert_eq!(deque.make_contiguous(), [2, 3, 4, 5, 6]);
+  assert_eq!(deque.iter_mut().map(|x| *x).sum::<i32>(), 20);
+  assert_eq!(
+    deque.clone().into_iter().rev().collect::<Vec<_>>(),
+    [6, 5, 4, 3, 2]
+  );
+
+  let vec = MiniVec::from(deque);
+  assert_eq!(vec, [2, 3, 4, 5, 6]);
+
+  // element types whose alignment leaves room in the vector's header are converted in place
+  //
+  #[repr(align(32))]
+  #[derive(Debug, PartialEq)]
+  struct Aligned(i32);
+
+  let mut vec = MiniVec::<Aligned>::with_capacity(4);
+  vec.push(Aligned(1));
+  vec.push(Aligned(2));
+  let data = vec.as_ptr();
+
+  let mut deque = MiniVecDeque::from(vec);
+  deque.push_front(Aligned(0));
+  deque.pop_back();
+  assert_eq!(deque.capacity(), 4);
+
+  let vec = MiniVec::from(deque);
+  assert_eq!(vec.as_ptr(), data);
+  assert_eq!(vec, [Aligned(0), Aligned(1)]);
+
+  let mut deque = MiniVecDeque::new();
+  deque.push_front(());
+  deque.push_back(());
+  assert_eq!(deque.len(), 2);
+  assert_eq!(deque.capacity(), usize::MAX);
+
+  let mut deque = MiniVecDeque::<String>::default();
+  assert!(deque.pop_front().is_none());
+  assert_eq!(deque.drain(..).count(), 0);
+  assert_eq!(MiniVec::from(deque).capacity(), 0);
+}
+
+#[test]
+fn mini_ring_buffer() {
+  use minivec::MiniRingBuffer;
+
+  let mut ring = MiniRingBuffer::with_capacity(3);
+  assert_eq!(ring.capacity(), 3);
+  assert!(ring.oldest().is_none());
+
+  ring.push(String::from("a"));
+  ring.push(String::from("b"));
+  assert!(!ring.is_full());
+  assert_eq!(ring.to_minivec(), ["a", "b"]);
+
+  ring.push(String::from("c"));
+  assert!(ring.is_full());
+  assert_eq!(ring.push(String::from("d")).unwrap(), "a");
+  assert_eq!(ring.push(String::from("e")).unwrap(), "b");
+  assert_eq!(ring.capacity(), 3);
+  assert_eq!(ring.oldest().unwrap(), "c");
+  assert_eq!(ring.newest().unwrap(), "e");
+  assert_eq!(ring.iter().collect::<Vec<_>>(), ["c", "d", "e"]);
+  assert_eq!(format!("{:?}", ring), r#"["c", "d", "e"]"#);
+
+  let copy = ring.clone();
+  assert_eq!(ring.drain_oldest(1), ["c"]);
+  assert_eq!(ring.len(), 2);
+  assert_eq!(ring.push(String::from("f")), None);
+  assert_eq!(ring.to_minivec(), ["d", "e", "f"]);
+
+  let vec = MiniVec::from(copy);
+  assert_eq!(vec, ["c", "d", "e"]);
+  assert_eq!(vec.capacity(), 3);
+
+  ring.clear();
+  assert!(ring.is_empty());
+  ring.extend((0..10).map(|x| x.to_string()));
+  assert_eq!(ring.drain_oldest(usize::MAX), ["7", "8", "9"]);
+
+  let mut empty = MiniRingBuffer::with_capacity(0);
+  assert_eq!(empty.push(1), Some(1));
+  assert!(empty.is_empty());
+
+  let mut zsts = MiniRingBuffer::with_capacity(2);
+  assert_eq!(zsts.capacity(), 2);
+  assert_eq!(zsts.push(()), None);
+  assert_eq!(zsts.push(()), None);
+  assert!(zsts.is_full());
+  assert_eq!(zsts.push(()), Some(()));
+  assert_eq!(zsts.len(), 2);
+  assert_eq!(zsts.clone().capacity(), 2);
+}
+
+#[test]
+fn mini_binary_heap() {
+  use minivec::{MiniBinaryHeap, PeekMut};
+
+  let mut heap = MiniBinaryHeap::from(mini_vec![4, 1, 7, 3, 9, 2]);
+  assert_eq!(heap.len(), 6);
+  assert_eq!(heap.peek(), Some(&9));
+
+  heap.push(8);
+  heap.push(0);
+  assert_eq!(heap.pop(), Some(9));
+  assert_eq!(heap.pop(), Some(8));
+
+  // the top element only moves once the guard is dropped
+  //
+  {
+    let mut top = heap.peek_mut().unwrap();
+    *top = -1;
+    assert_eq!(*top, -1);
+  }
+  assert_eq!(heap.peek(), Some(&4));
+  assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), 4);
+
+  heap.retain(|&x| x != 2);
+  let mut other: MiniBinaryHeap<_> = (10..13).collect();
+  heap.append(&mut other);
+  assert!(other.is_empty());
+
+  assert_eq!(heap.clone().into_sorted_vec(), [-1, 0, 1, 3, 10, 11, 12]);
+
+  let mut drain = heap.drain_sorted();
+  assert_eq!(drain.len(), 7);
+  assert_eq!(drain.next(), Some(12));
+  drop(drain);
+  assert!(heap.is_empty());
+
+  let mut vec = MiniVec::with_capacity(16);
+  vec.extend_from_slice(&[String::from("b"), String::from("c"), String::from("a")]);
+  let data = vec.as_ptr();
+
+  let heap = MiniBinaryHeap::from(vec);
+  assert_eq!(heap.peek().unwrap(), "c");
+
+  let vec = heap.into_sorted_vec();
+  assert_eq!(vec, ["a", "b", "c"]);
+  assert_eq!(vec.as_ptr(), data);
+  assert_eq!(MiniBinaryHeap::from(vec).into_vec().capacity(), 16);
+}
+
+#[test]
+fn mini_vec_map_and_set() {
+  use minivec::{Entry, MiniVecMap, MiniVecSet};
+
+  let mut map: MiniVecMap<String, i32> = [("b", 2), ("a", 1), ("c", 3), ("a", 10)]
+    .iter()
+    .map(|&(k, v)| (String::from(k), v))
+    .collect();
+
+  // bulk construction keeps the last value of a duplicated key
+  //
+  assert_eq!(map.len(), 3);
+  assert_eq!(map["a"], 10);
+  assert_eq!(map.get("z"), None);
+  assert!(map.contains_key("c"));
+
+  assert_eq!(map.insert(String::from("d"), 4), None);
+  assert_eq!(map.insert(String::from("b"), 20), Some(2));
+  assert_eq!(map.remove("c"), Some(3));
+  assert_eq!(
+    map.keys().map(String::as_str).collect::<Vec<_>>(),
+    ["a", "b", "d"]
+  );
+  assert_eq!(map.values().copied().collect::<Vec<_>>(), [10, 20, 4]);
+
+  match map.entry(String::from("e")) {
+    Entry::Vacant(entry) => {
+      assert_eq!(entry.key(), "e");
+      *entry.insert(5) += 1;
+    }
+    Entry::Occupied(_) => unreachable!(),
+  }
+  match map.entry(String::from("a")) {
+    Entry::Occupied(entry) => assert_eq!(entry.remove(), 10),
+    Entry::Vacant(_) => unreachable!(),
+  }
+  map
+    .entry(String::from("b"))
+    .and_modify(|v| *v += 1)
+    .or_insert(0);
+  *map.entry(String::from("f")).or_default() += 7;
+  assert_eq!(format!("{:?}", map), r#"{"b": 21, "d": 4, "e": 6, "f": 7}"#);
+
+  let range: Vec<_> = map
+    .range::<str, _>((
+      std::ops::Bound::Excluded("b"),
+      std::ops::Bound::Included("e"),
+    ))
+    .map(|(k, v)| (k.as_str(), *v))
+    .collect();
+  assert_eq!(range, [("d", 4), ("e", 6)]);
+  assert_eq!(map.range(String::from("z")..).count(), 0);
+
+  for (_, v) in &mut map {
+    *v *= 2;
+  }
+  map.retain(|k, v| k != "d" && *v > 12);
+  assert_eq!(map.first_key_value(), Some((&String::from("b"), &42)));
+  assert_eq!(map.last_key_value(), Some((&String::from("f"), &14)));
+  assert_eq!(MiniVec::from(map.clone()).len(), 2);
+
+  let result = catch_unwind(|| map.range(String::from("c")..String::from("a")).count());
+  assert!(result.is_err());
+  let result = catch_unwind(|| {
+    map
+      .range::<str, _>((
+        std::ops::Bound::Excluded("b"),
+        std::ops::Bound::Excluded("b"),
+      ))
+      .count()
+  });
+  assert_eq!(
+    result.unwrap_err().downcast_ref::<&str>(),
+    Some(&"range start and end are equal and excluded")
+  );
+  let result = catch_unwind(|| {
+    MiniVecMap::<u32, u32>::new()
+      .range((std::ops::Bound::Included(5), std::ops::Bound::Included(3)))
+      .count()
+  });
+  assert_eq!(
+    result.unwrap_err().downcast_ref::<&str>(),
+    Some(&"range start is greater than range end")
+  );
+
+  let a: MiniVecSet<_> = [7, 1, 5, 3, 1].iter().copied().collect();
+  let mut b = MiniVecSet::new();
+  b.extend(&[5, 6, 7, 8]);
+
+  assert_eq!(a.as_slice(), [1, 3, 5, 7]);
+  assert_eq!(a.union(&b).copied().collect::<Vec<_>>(), [1, 3, 5, 6, 7, 8]);
+  assert_eq!(a.intersection(&b).copied().collect::<Vec<_>>(), [5, 7]);
+  assert_eq!(a.difference(&b).copied().collect::<Vec<_>>(), [1, 3]);
+  assert_eq!(b.difference(&a).copied().collect::<Vec<_>>(), [6, 8]);
+  assert_eq!(
+    a.symmetric_difference(&b).copied().collect::<Vec<_>>(),
+    [1, 3, 6, 8]
+  );
+  assert_eq!(a.range(2..=5).copied().collect::<Vec<_>>(), [3, 5]);
+  assert_eq!(a.range(3..3).count(), 0);
+  let result = catch_unwind(|| {
+    a.range((std::ops::Bound::Excluded(3), std::ops::Bound::Excluded(3)))
+      .count()
+  });
+  assert_eq!(
+    result.unwrap_err().downcast_ref::<&str>(),
+    Some(&"range start and end are equal and excluded")
+  );
+
+  let c: MiniVecSet<_> = a.intersection(&b).copied().collect();
+  assert!(c.is_subset(&a) && c.is_subset(&b));
+  assert!(a.is_superset(&c));
+  assert!(!a.is_subset(&b));
+  assert!(a.is_disjoint(&[2, 4].iter().copied().collect()));
+
+  let mut a = a;
+  assert!(!a.insert(3));
+  assert!(a.insert(4));
+  assert!(a.remove(&1));
+  assert_eq!(a.take(&7), Some(7));
+  a.retain(|&x| x != 5);
+  assert_eq!(format!("{:?}", a), "{3, 4}");
+  assert_eq!(MiniVec::from(a), [3, 4]);
+}
+
+#[test]
+fn mini_hash_map() {
+  use minivec::{HashEntry, MiniHashMap};
+  use std::collections::hash_map::{DefaultHasher, RandomState};
+  use std::hash::BuildHasherDefault;
+
+  type Map<K, V> = MiniHashMap<K, V, RandomState>;
+
+  assert_eq!(
+    core::mem::size_of::<MiniHashMap<u64, u64, BuildHasherDefault<DefaultHasher>>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut map: Map<String, i32> = Map::new();
+  assert!(map.is_empty());
+  assert_eq!(map.capacity(), 0);
+  assert_eq!(map.get("a"), None);
+  assert_eq!(map.remove("a"), None);
+
+  for i in 0..1000 {
+    assert_eq!(map.insert(i.to_string(), i), None);
+  }
+  assert_eq!(map.len(), 1000);
+  assert!(map.capacity() >= 1000);
+  assert_eq!(map.insert(String::from("7"), 70), Some(7));
+  assert_eq!(map["7"], 70);
+  assert_eq!(map.insert(String::from("7"), 7), Some(70));
+
+  // removing and re-inserting leaves tombstones behind which must not break lookups or grow the table forever
+  //
+  let capacity = map.capacity();
+  for _ in 0..10 {
+    for i in (0..1000).step_by(2) {
+      assert!(map.remove(&i.to_string()).is_some());
+    }
+    for i in (0..1000).step_by(2) {
+      assert_eq!(map.insert(i.to_string(), i), None);
+    }
+  }
+  assert_eq!(map.capacity(), capacity);
+  assert!((0..1000).all(|i| map.contains_key(&i.to_string())));
+
+  for i in (0..1000).step_by(2) {
+    assert!(map.remove(&i.to_string()).is_some());
+  }
+  assert_eq!(
+    map.try_reserve(usize::MAX - map.len()).unwrap_err().kind(),
+    minivec::TryReserveErrorKind::CapacityOverflow
+  );
+  for i in (0..1000).step_by(2) {
+    assert_eq!(map.insert(i.to_string(), i), None);
+  }
+
+  let mut keys: Vec<i32> = map.keys().map(|k| k.parse().unwrap()).collect();
+  keys.sort_unstable();
+  assert_eq!(keys, (0..1000).collect::<Vec<_>>());
+  assert_eq!(map.values().len(), 1000);
+
+  for (_, v) in &mut map {
+    *v *= 2;
+  }
+  map.retain(|_, v| *v % 4 == 0);
+  assert_eq!(map.len(), 500);
+  assert_eq!(map.get("8"), Some(&16));
+  assert_eq!(map.get("9"), None);
+
+  match map.entry(String::from("9")) {
+    HashEntry::Vacant(entry) => {
+      assert_eq!(entry.key(), "9");
+      *entry.insert(9) += 1;
+    }
+    HashEntry::Occupied(_) => unreachable!(),
+  }
+  match map.entry(String::from("8")) {
+    HashEntry::Occupied(entry) => assert_eq!(entry.remove_entry(), (String::from("8"), 16)),
+    HashEntry::Vacant(_) => unreachable!(),
+  }
+  map
+    .entry(String::from("9"))
+    .and_modify(|v| *v += 1)
+    .or_insert(0);
+  *map.entry(String::from("x")).or_default() += 3;
+  assert_eq!(map["9"], 11);
+  assert_eq!(map["x"], 3);
+
+  let clone = map.clone();
+  assert_eq!(clone, map);
+  assert_eq!(clone.len(), map.len());
+
+  let mut drained: Vec<_> = map.drain().take(3).collect();
+  assert_eq!(drained.len(), 3);
+  assert!(map.is_empty());
+  assert!(map.capacity() > 0);
+  assert_eq!(map.insert(String::from("y"), 1), None);
+  assert_eq!(format!("{:?}", map), r#"{"y": 1}"#);
+
+  drained.sort();
+  let mut owned: Vec<_> = clone.into_iter().collect();
+  owned.sort();
+  assert_eq!(owned.len(), 501);
+  assert!(drained.iter().all(|entry| owned.contains(entry)));
+
+  // a leaked drain leaves the entries it didn't reach in the map, where they can still be found
+  //
+  let mut map: Map<i32, i32> = (0..200).map(|i| (i, i)).collect();
+  let mut drain = map.drain();
+  let taken: Vec<_> = drain.by_ref().take(100).collect();
+  core::mem::forget(drain);
+  assert_eq!(map.len(), 100);
+  for i in 0..200 {
+    let was_taken = taken.contains(&(i, i));
+    assert_eq!(map.get(&i), if was_taken { None } else { Some(&i) });
+  }
+
+  // partially consumed owning iterators drop their remaining entries
+  //
+  let counter = std::rc::Rc::new(());
+  let map: Map<i32, std::rc::Rc<()>> = (0..20).map(|i| (i, counter.clone())).collect();
+  assert_eq!(std::rc::Rc::strong_count(&counter), 21);
+  let mut iter = map.into_iter();
+  drop(iter.next());
+  assert_eq!(iter.len(), 19);
+  drop(iter);
+  assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+
+  let result = catch_unwind(|| {
+    let map: Map<i32, i32> = Map::new();
+    map[&0]
+  });
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_bit_vec() {
+  use minivec::MiniBitVec;
+
+  assert_eq!(
+    core::mem::size_of::<MiniBitVec>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut bits = MiniBitVec::new();
+  assert!(bits.is_empty());
+  assert_eq!(bits.capacity(), 0);
+  assert_eq!(bits.pop(), None);
+  assert_eq!(bits.get(0), None);
+
+  // every operation is checked against a plain `Vec<bool>`, across several word boundaries
+  //
+  let mut model = Vec::new();
+  for i in 0..300 {
+    let value = i % 3 == 0 || i % 7 == 0;
+    bits.push(value);
+    model.push(value);
+  }
+  assert_eq!(bits.len(), 300);
+  assert!(bits.capacity() >= 300);
+  assert_eq!(bits.iter().collect::<Vec<_>>(), model);
+
+  for &(idx, value) in &[
+    (0, false),
+    (63, true),
+    (64, true),
+    (65, false),
+    (299, true),
+    (150, true),
+  ] {
+    bits.insert(idx, value);
+    model.insert(idx, value);
+  }
+  assert_eq!(bits.iter().collect::<Vec<_>>(), model);
+
+  for &idx in &[0, 63, 64, 127, 200, 299] {
+    assert_eq!(bits.remove(idx), model.remove(idx));
+  }
+  assert_eq!(bits.iter().collect::<Vec<_>>(), model);
+
+  bits.set(10, true);
+  model[10] = true;
+  bits.set(12, false);
+  model[12] = false;
+  assert_eq!(bits.get(10), Some(true));
+  assert_eq!(bits.count_ones(), model.iter().filter(|&&b| b).count());
+  assert_eq!(
+    bits.iter_ones().collect::<Vec<_>>(),
+    (0..model.len()).filter(|&i| model[i]).collect::<Vec<_>>()
+  );
+  assert_eq!(bits.pop(), model.pop());
+  assert_eq!(
+    bits.iter().rev().collect::<Vec<_>>(),
+    model.iter().rev().copied().collect::<Vec<_>>()
+  );
+  assert_eq!(bits, MiniBitVec::from(&model[..]));
+
+  bits.resize(70, false);
+  bits.resize(250, true);
+  model.resize(70, false);
+  model.resize(250, true);
+  assert_eq!(bits, model.iter().copied().collect());
+  assert_eq!(
+    bits.as_words().len(),
+    250_usize.div_ceil(usize::BITS as usize)
+  );
+
+  let other: MiniBitVec = (0..250).map(|i| i % 2 == 0).collect();
+  let mut and = bits.clone();
+  and &= &other;
+  let mut or = bits.clone();
+  or |= &other;
+  let mut xor = bits.clone();
+  xor ^= &other;
+  for (i, &a) in model.iter().enumerate() {
+    let b = i % 2 == 0;
+    assert_eq!(and.get(i), Some(a && b));
+    assert_eq!(or.get(i), Some(a || b));
+    assert_eq!(xor.get(i), Some(a != b));
+  }
+
+  // negation leaves the unused bits of the last word alone
+  //
+  let not = !bits.clone();
+  assert_eq!(not.count_ones() + bits.count_ones(), 250);
+  assert_eq!(!not, bits);
+
+  bits.clear();
+  assert!(bits.is_empty());
+  assert_eq!(bits.count_ones(), 0);
+  bits.push(true);
+  assert_eq!(format!("{:?}", bits), "[true]");
+
+  let result = catch_unwind(|| {
+    let mut bits = MiniBitVec::from(&[true][..]);
+    bits.insert(2, true);
+  });
+  assert!(result.is_err());
+
+  let result = catch_unwind(|| {
+    let mut bits = MiniBitVec::from(&[true][..]);
+    bits &= &MiniBitVec::new();
+  });
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_matrix() {
+  use minivec::MiniMatrix;
+
+  assert_eq!(
+    core::mem::size_of::<MiniMatrix<f64>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut m = MiniMatrix::<i32>::new();
+  assert_eq!(m.shape(), (0, 0));
+  assert!(m.is_empty());
+  assert_eq!(m.get(0, 0), None);
+  assert_eq!(m.iter_rows().count(), 0);
+
+  m.push_row(vec![1, 2, 3]);
+  m.push_row(vec![4, 5, 6]);
+  assert_eq!(m.shape(), (2, 3));
+  assert_eq!(m.len(), 6);
+  assert_eq!(m[(0, 2)], 3);
+  m[(1, 0)] = 40;
+  *m.get_mut(1, 1).unwrap() = 50;
+  assert_eq!(m.get(2, 0), None);
+  assert_eq!(m.get(0, 3), None);
+
+  assert_eq!(m.iter_rows().collect::<Vec<_>>(), [[1, 2, 3], [40, 50, 6]]);
+  assert_eq!(m.iter_rows().next_back(), Some(&[40, 50, 6][..]));
+  assert_eq!(
+    m.iter_columns()
+      .map(|col| col.copied().collect::<Vec<_>>())
+      .collect::<Vec<_>>(),
+    [[1, 40], [2, 50], [3, 6]]
+  );
+  m.column_mut(2).for_each(|x| *x *= 10);
+  m.row_mut(0)[0] = 10;
+  assert_eq!(format!("{:?}", m), "[[10, 2, 30], [40, 50, 60]]");
+
+  m.insert_column(0, vec![-1, -4]);
+  m.insert_column(4, vec![-3, -6]);
+  assert_eq!(m.shape(), (2, 5));
+  assert_eq!(m.as_slice(), [-1, 10, 2, 30, -3, -4, 40, 50, 60, -6]);
+
+  m.transpose();
+  assert_eq!(m.shape(), (5, 2));
+  assert_eq!(m.row(1), [10, 40]);
+  assert_eq!(
+    m.column(0).copied().collect::<Vec<_>>(),
+    [-1, 10, 2, 30, -3]
+  );
+
+  let clone = m.clone();
+  assert_eq!(clone, m);
+  m.resize(2, 3, 0);
+  assert_eq!(m.as_slice(), [-1, -4, 0, 10, 40, 0]);
+  m.resize(3, 3, 7);
+  assert_eq!(m.as_slice(), [-1, -4, 0, 10, 40, 0, 7, 7, 7]);
+  assert_ne!(clone, m);
+
+  // the conversions hand the allocation back and forth without moving the elements
+  //
+  let ptr = m.as_slice().as_ptr();
+  let vec = m.into_minivec();
+  assert_eq!(vec.as_ptr(), ptr);
+  assert_eq!(vec, [-1, -4, 0, 10, 40, 0, 7, 7, 7]);
+  let m = MiniMatrix::from_minivec(vec, 1, 9);
+  assert_eq!(m.as_slice().as_ptr(), ptr);
+  assert_eq!(m.shape(), (1, 9));
+  assert_eq!(MiniVec::from(m).len(), 9);
+
+  // empty matrices still remember their shape
+  //
+  let mut empty = MiniMatrix::from_minivec(MiniVec::<String>::new(), 0, 4);
+  assert_eq!(empty.shape(), (0, 4));
+  assert_eq!(empty.iter_columns().count(), 4);
+  empty.push_row(["a", "b", "c", "d"].iter().map(|s| s.to_string()));
+  empty.transpose();
+  assert_eq!(empty.shape(), (4, 1));
+  assert_eq!(empty.iter_rows().count(), 4);
+  assert_eq!(empty.row(3), ["d"]);
+  empty.resize(0, 0, String::new());
+  assert!(empty.is_empty());
+
+  let mut units = MiniMatrix::from_minivec(minivec::mini_vec![(); 6], 3, 2);
+  units.insert_column(1, vec![(); 3]);
+  assert_eq!(units.shape(), (3, 3));
+  let units = MiniMatrix::from_minivec(MiniVec::<()>::new(), 5, 0);
+  assert_eq!(units.shape(), (5, 0));
+  assert_eq!(units.iter_rows().count(), 5);
+
+  // a row of the wrong length leaves the matrix as it was
+  //
+  let mut m = MiniMatrix::new();
+  m.push_row(vec![1, 2]);
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| m.push_row(vec![1, 2, 3])));
+  assert!(result.is_err());
+  assert_eq!(m.shape(), (1, 2));
+  assert_eq!(m.row(0), [1, 2]);
+
+  let result = catch_unwind(|| MiniMatrix::from_minivec(minivec::mini_vec![1, 2, 3], 2, 2));
+  assert!(result.is_err());
+
+  let result = catch_unwind(|| {
+    let m = MiniMatrix::from_minivec(minivec::mini_vec![1, 2, 3, 4], 2, 2);
+    m[(0, 2)]
+  });
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_jagged() {
+  use minivec::MiniJagged;
+
+  assert_eq!(
+    core::mem::size_of::<MiniJagged<u8>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut jagged = MiniJagged::<String>::new();
+  assert!(jagged.is_empty());
+  assert_eq!(jagged.get(0), None);
+  assert_eq!(jagged.pop(), None);
+  assert_eq!(jagged.flat_len(), 0);
+
+  // enough sequences and elements to grow both the elements and the table a few times over
+  //
+  let mut model: Vec<Vec<String>> = Vec::new();
+  for i in 0..100 {
+    let seq: Vec<String> = (0..i % 7).map(|j| format!("{}-{}", i, j)).collect();
+    jagged.push_seq(&seq);
+    model.push(seq);
+
+    if i % 10 == 0 {
+      jagged.extend_last(vec![String::from("x"); 3]);
+      model.last_mut().unwrap().extend(vec![String::from("x"); 3]);
+    }
+  }
+  assert_eq!(jagged.len(), 100);
+  assert_eq!(jagged.flat_len(), model.iter().map(Vec::len).sum::<usize>());
+  assert!(jagged.iter().eq(model.iter().map(Vec::as_slice)));
+  assert_eq!(&jagged[10], &model[10][..]);
+
+  for &idx in &[0, 50, 97, 12] {
+    assert_eq!(jagged.remove(idx), model.remove(idx));
+  }
+  assert_eq!(jagged.pop().unwrap(), model.pop().unwrap());
+  assert!(jagged
+    .iter()
+    .rev()
+    .eq(model.iter().rev().map(Vec::as_slice)));
+
+  jagged[3][0].push('!');
+  model[3][0].push('!');
+  jagged.get_mut(3).unwrap().reverse();
+  model[3].reverse();
+
+  let clone = jagged.clone();
+  assert_eq!(clone, jagged);
+
+  let flat = jagged.flatten();
+  assert_eq!(flat, model.concat());
+
+  let mut nums: MiniJagged<i32> = vec![vec![1, 2], vec![], vec![3]].into_iter().collect();
+  nums.extend(vec![vec![4, 5, 6]]);
+  assert_eq!(format!("{:?}", nums), "[[1, 2], [], [3], [4, 5, 6]]");
+  nums.clear();
+  assert!(nums.is_empty());
+  assert_eq!(nums.as_flat_slice(), []);
+  nums.push_seq(&[]);
+  assert_eq!(nums.len(), 1);
+  assert!(nums.flatten().is_empty());
+
+  let units: MiniJagged<()> = vec![vec![(); 3], vec![(); 2]].into_iter().collect();
+  assert_eq!(units.flat_len(), 5);
+  assert_eq!(units[1].len(), 2);
+  assert_eq!(units.flatten().len(), 5);
+
+  let result = catch_unwind(|| MiniJagged::<i32>::new().extend_last(vec![1]));
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_vec2() {
+  use minivec::{MiniVec2, MiniVec3, MiniVec4};
+
+  assert_eq!(
+    core::mem::size_of::<MiniVec2<u8, u64>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut soa = MiniVec2::<String, u8>::new();
+  assert!(soa.is_empty());
+  assert_eq!(soa.capacity(), 0);
+  assert_eq!(soa.pop(), None);
+  assert_eq!(soa.get(0), None);
+  assert_eq!(soa.column_a(), &[] as &[String]);
+
+  // columns of different alignments and enough rows to grow a few times over
+  //
+  let mut model: Vec<(String, u8)> = Vec::new();
+  for i in 0..100_u8 {
+    soa.push((format!("{}", i), 99 - i));
+    model.push((format!("{}", i), 99 - i));
+  }
+  assert_eq!(soa.len(), 100);
+  assert!(soa.capacity() >= 100);
+  assert!(soa.column_a().iter().eq(model.iter().map(|row| &row.0)));
+  assert!(soa.column_b().iter().eq(model.iter().map(|row| &row.1)));
+  assert_eq!(soa.get(7), Some((&String::from("7"), &92)));
+
+  assert_eq!(soa.swap_remove(10), model.swap_remove(10));
+  assert_eq!(
+    soa.swap_remove(soa.len() - 1),
+    model.swap_remove(model.len() - 1)
+  );
+  assert_eq!(soa.pop(), model.pop());
+
+  soa.sort_by_key(|(_, b)| *b);
+  model.sort_by_key(|row| row.1);
+  assert!(soa.column_a().iter().eq(model.iter().map(|row| &row.0)));
+  assert!(soa.column_b().iter().eq(model.iter().map(|row| &row.1)));
+
+  // stability, the rows sharing a key keep their relative order
+  //
+  soa.sort_by_key(|(_, b)| *b % 3);
+  model.sort_by_key(|row| row.1 % 3);
+  assert!(soa.column_a().iter().eq(model.iter().map(|row| &row.0)));
+
+  soa.column_a_mut()[0].push('!');
+  model[0].0.push('!');
+  let (a, b) = soa.columns_mut();
+  a.reverse();
+  b.reverse();
+  model.reverse();
+
+  let clone = soa.clone();
+  assert_eq!(clone, soa);
+
+  soa.truncate(50);
+  model.truncate(50);
+  let (a, b) = soa.into_columns();
+  assert_eq!(a, model.iter().map(|row| row.0.clone()).collect::<Vec<_>>());
+  assert_eq!(b, model.iter().map(|row| row.1).collect::<Vec<_>>());
+  assert_eq!(clone.columns().0.len(), 97);
+
+  let mut triples: MiniVec3<u8, (), u32> = vec![(3, (), 30), (1, (), 10)].into_iter().collect();
+  triples.extend(vec![(2, (), 20)]);
+  assert_eq!(
+    format!("{:?}", triples),
+    "[(3, (), 30), (1, (), 10), (2, (), 20)]"
+  );
+  triples.sort_by_key(|(a, _, _)| *a);
+  assert_eq!(triples.column_c(), [10, 20, 30]);
+  triples.clear();
+  assert!(triples.is_empty());
+
+  let mut quads = MiniVec4::<u8, u16, u32, u64>::with_capacity(4);
+  assert!(quads.capacity() >= 4);
+  quads.push((1, 2, 3, 4));
+  assert_eq!(quads.column_d(), [4]);
+  assert_eq!(
+    quads.into_columns(),
+    (mini_vec![1], mini_vec![2], mini_vec![3], mini_vec![4])
+  );
+
+  let mut units = MiniVec2::<(), ()>::new();
+  units.extend((0..1000).map(|_| ((), ())));
+  assert_eq!(units.len(), 1000);
+  assert_eq!(units.column_b().len(), 1000);
+
+  let result = catch_unwind(|| MiniVec2::<u8, u8>::new().swap_remove(0));
+  assert!(result.is_err());
+
+  let result = catch_unwind(|| MiniVec2::<u64, u64>::with_capacity(usize::MAX / 8));
+  assert!(result.is_err());
+}
+
+#[test]
+fn header_vec() {
+  use minivec::HeaderVec;
+
+  #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+  struct Meta {
+    id: u32,
+    name: String,
+  }
+
+  assert_eq!(
+    core::mem::size_of::<HeaderVec<Meta, u8>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let meta = Meta {
+    id: 7,
+    name: String::from("seven"),
+  };
+
+  let mut vec = HeaderVec::<Meta, String>::new(meta.clone());
+  assert!(vec.is_empty());
+  assert_eq!(vec.header(), &meta);
+
+  // the header has to survive every reallocation
+  //
+  for i in 0..100 {
+    vec.push(i.to_string());
+  }
+  assert_eq!(vec.header(), &meta);
+  assert_eq!(vec.len(), 100);
+
+  vec.header_mut().id = 8;
+  vec.header_mut().name.push('!');
+  vec.retain(|s| s.len() == 1);
+  vec.insert(0, String::from("x"));
+  vec.truncate(5);
+  vec.shrink_to_fit();
+  assert_eq!(vec, ["x", "0", "1", "2", "3"]);
+  assert_eq!(vec.header().id, 8);
+  assert_eq!(vec.header().name, "seven!");
+
+  vec.clear();
+  vec.shrink_to_fit();
+  assert_eq!(vec.capacity(), 0);
+  assert_eq!(vec.header().name, "seven!");
+  vec.extend(vec![String::from("a"), String::from("b")]);
+
+  let clone = vec.clone();
+  assert_eq!(clone, vec);
+  assert_eq!(clone.header(), vec.header());
+
+  let mut other = clone.clone();
+  other.header_mut().id = 9;
+  assert_ne!(other, vec);
+
+  let mut h1 = DefaultHasher::new();
+  let mut h2 = DefaultHasher::new();
+  vec.hash(&mut h1);
+  clone.hash(&mut h2);
+  assert_eq!(h1.finish(), h2.finish());
+
+  let tail = vec.split_off(1);
+  assert_eq!(tail, ["b"]);
+  assert_eq!(tail.allocator().header(), vec.header());
+
+  let ptr = vec.into_raw_part();
+  let vec = unsafe { HeaderVec::<Meta, String>::from_raw_part(ptr) };
+  assert_eq!(vec, ["a"]);
+  assert_eq!(vec.header().id, 8);
+  assert_eq!(
+    format!("{:?}", vec),
+    "HeaderVec { header: Meta { id: 8, name: \"seven!\" }, data: [\"a\"] }"
+  );
+
+  let mut units = HeaderVec::<(), ()>::with_capacity((), 4);
+  units.push(());
+  *units.header_mut() = ();
+  assert_eq!(units.len(), 1);
+
+  let result = catch_unwind(|| HeaderVec::<u8, u64>::with_capacity(0, usize::MAX));
+  assert!(result.is_err());
+}
+
+#[test]
+fn tagged_mini_vec() {
+  use minivec::TaggedMiniVec;
+
+  assert_eq!(
+    core::mem::size_of::<TaggedMiniVec<u8>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut vec = TaggedMiniVec::<String>::new();
+  assert_eq!(vec.tag(), 0);
+  assert!(vec.is_empty());
+  assert_eq!(vec.capacity(), 0);
+
+  // every tag has to survive the reallocations done through the guard
+  //
+  for tag in 0..=TaggedMiniVec::<String>::MAX_TAG {
+    vec.set_tag(tag);
+
+    let mut edit = vec.edit();
+    for i in 0..20 {
+      edit.push(format!("{}-{}", tag, i));
+    }
+    drop(edit);
+
+    assert_eq!(vec.tag(), tag);
+    assert_eq!(vec.len(), (tag + 1) * 20);
+    assert_eq!(vec.last().unwrap(), &format!("{}-19", tag));
+  }
+
+  vec.set_tag(3);
+  vec.edit().retain(|s| s.ends_with("-0"));
+  vec[0].push('!');
+  assert_eq!(vec.tag(), 3);
+  assert_eq!(vec.len(), TaggedMiniVec::<String>::MAX_TAG + 1);
+  assert_eq!(vec[0], "0-0!");
+
+  let clone = vec.clone();
+  assert_eq!(clone, vec);
+  assert_eq!(clone.tag(), 3);
+
+  let mut h1 = DefaultHasher::new();
+  let mut h2 = DefaultHasher::new();
+  vec.hash(&mut h1);
+  clone.hash(&mut h2);
+  assert_eq!(h1.finish(), h2.finish());
+
+  let mut other = clone.clone();
+  other.set_tag(1);
+  assert_ne!(other, vec);
+  assert_eq!(other.as_slice(), vec.as_slice());
+
+  // a leaked guard leaves the vector empty rather than dangling
+  //
+  core::mem::forget(other.edit());
+  assert!(other.is_empty());
+  assert_eq!(other.tag(), 1);
+
+  let (inner, tag) = vec.into_parts();
+  assert_eq!(tag, 3);
+  assert_eq!(inner.len(), TaggedMiniVec::<String>::MAX_TAG + 1);
+
+  let vec = TaggedMiniVec::from_parts(inner, 2);
+  assert_eq!(vec.tag(), 2);
+  let inner = MiniVec::from(vec);
+  assert_eq!(inner[1], "1-0");
+
+  let vec = TaggedMiniVec::from_parts(mini_vec![1, 2], 7 & TaggedMiniVec::<i32>::MAX_TAG);
+  assert_eq!(
+    format!("{:?}", vec),
+    format!(
+      "TaggedMiniVec {{ tag: {}, data: [1, 2] }}",
+      7 & TaggedMiniVec::<i32>::MAX_TAG
+    )
+  );
+
+  #[repr(align(64))]
+  #[derive(Clone, Copy, Debug, PartialEq)]
+  struct OverAligned(u8);
+
+  let mut vec = TaggedMiniVec::<OverAligned>::default();
+  vec.set_tag(1);
+  assert!(vec.is_empty());
+  vec.edit().push(OverAligned(1));
+  assert_eq!(vec, [OverAligned(1)]);
+
+  let result = catch_unwind(|| {
+    TaggedMiniVec::<i32>::new().set_tag(TaggedMiniVec::<i32>::MAX_TAG + 1);
+  });
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_small_bytes() {
+  use minivec::MiniSmallBytes;
+
+  assert_eq!(
+    core::mem::size_of::<MiniSmallBytes>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let inline_cap = MiniSmallBytes::INLINE_CAPACITY;
+  assert_eq!(inline_cap, core::mem::size_of::<usize>() - 1);
+
+  let mut bytes = MiniSmallBytes::new();
+  assert!(bytes.is_inline());
+  assert!(bytes.is_empty());
+  assert_eq!(bytes.capacity(), inline_cap);
+  assert_eq!(bytes.pop(), None);
+
+  // fill the inline storage to the brim, then spill over
+  //
+  let mut model = Vec::new();
+  for i in 0..inline_cap {
+    bytes.push(b'a' + i as u8);
+    model.push(b'a' + i as u8);
+    assert!(bytes.is_inline());
+  }
+  assert_eq!(bytes, &model[..]);
+
+  bytes[0] = b'z';
+  model[0] = b'z';
+  assert_eq!(bytes.pop(), model.pop());
+  bytes.push(b'!');
+  model.push(b'!');
+  assert!(bytes.is_inline());
+
+  bytes.push(b'?');
+  model.push(b'?');
+  assert!(!bytes.is_inline());
+  assert!(bytes.capacity() >= 2 * inline_cap);
+  assert_eq!(bytes, &model[..]);
+
+  bytes.extend_from_slice(b"0123456789");
+  model.extend_from_slice(b"0123456789");
+  bytes.extend(b"xy");
+  model.extend(b"xy");
+  assert_eq!(bytes.as_slice(), &model[..]);
+
+  let clone = bytes.clone();
+  assert_eq!(clone, bytes);
+  assert!(!clone.is_inline());
+
+  let mut h1 = DefaultHasher::new();
+  let mut h2 = DefaultHasher::new();
+  bytes.hash(&mut h1);
+  model.as_slice().hash(&mut h2);
+  assert_eq!(h1.finish(), h2.finish());
+
+  bytes.truncate(2);
+  assert!(!bytes.is_inline());
+  let short = bytes.clone();
+  assert!(short.is_inline());
+  bytes.shrink_to_fit();
+  assert!(bytes.is_inline());
+  assert_eq!(bytes, &model[..2]);
+  assert!(short < clone);
+
+  let vec = MiniVec::from(clone);
+  assert_eq!(vec, model);
+  let spilled = MiniSmallBytes::from(vec);
+  assert!(!spilled.is_inline());
+  assert_eq!(spilled, &model[..]);
+
+  let vec = MiniVec::from(bytes);
+  assert_eq!(vec, model[..2]);
+  assert!(MiniSmallBytes::from(MiniVec::new()).is_inline());
+
+  let collected: MiniSmallBytes = (0..3).collect();
+  assert!(collected.is_inline());
+  assert_eq!(format!("{:?}", collected), "[0, 1, 2]");
+  assert_eq!(collected.iter().copied().sum::<u8>(), 3);
+
+  let mut cleared = MiniSmallBytes::with_capacity(64);
+  assert!(!cleared.is_inline());
+  cleared.extend_from_slice(b"abc");
+  cleared.clear();
+  assert!(cleared.is_empty());
+  assert!(cleared.capacity() >= 64);
+
+  let result = catch_unwind(|| MiniSmallBytes::new().reserve(usize::MAX));
+  assert!(result.is_err());
+}
+
+#[test]
+fn mini_cow() {
+  use minivec::MiniCow;
+
+  static TABLE: [&str; 3] = ["zero", "one", "two"];
+
+  assert_eq!(
+    core::mem::size_of::<MiniCow<'static, u8>>(),
+    core::mem::size_of::<usize>()
+  );
+
+  let mut cow = MiniCow::from_static(&TABLE);
+  assert!(cow.is_borrowed());
+  assert_eq!(cow.len(), 3);
+  assert_eq!(cow, TABLE);
+  assert_eq!(cow[1], "one");
+
+  let clone = cow.clone();
+  assert!(clone.is_borrowed());
+  assert_eq!(clone, cow);
+
+  // the clone shares the header describing the borrowed slice, which outlives the original once it's made owned
+  //
+  assert_eq!(
+    unsafe { core::mem::transmute_copy::<MiniCow<&str>, usize>(&clone) },
+    unsafe { core::mem::transmute_copy::<MiniCow<&str>, usize>(&cow) }
+  );
+
+  cow.to_mut().push("three");
+  assert!(cow.is_owned());
+  assert_eq!(cow, ["zero", "one", "two", "three"]);
+  assert_eq!(TABLE.len(), 3);
+  assert!(clone < cow);
+
+  let owned_clone = cow.clone();
+  assert!(owned_clone.is_owned());
+  assert_eq!(owned_clone, cow);
+
+  // borrowing a vector reuses its header
+  //
+  let vec: MiniVec<String> = (0..10).map(|i| i.to_string()).collect();
+  let mut cow = MiniCow::from(&vec);
+  assert!(cow.is_borrowed());
+  assert_eq!(cow.as_slice(), vec.as_slice());
+  assert_eq!(cow.iter().count(), 10);
+  assert_eq!((&cow).into_iter().last().unwrap(), "9");
+
+  let mut h1 = DefaultHasher::new();
+  let mut h2 = DefaultHasher::new();
+  cow.hash(&mut h1);
+  vec.as_slice().hash(&mut h2);
+  assert_eq!(h1.finish(), h2.finish());
+
+  let clone = cow.clone();
+  cow.to_mut()[0].push('!');
+  assert!(cow.is_owned());
+  assert_eq!(cow[0], "0!");
+  assert_eq!(vec[0], "0");
+  assert_eq!(clone[0], "0");
+  assert_eq!(format!("{:?}", cow.get(..2).unwrap()), "[\"0!\", \"1\"]");
+
+  let owned = MiniVec::from(cow);
+  assert_eq!(owned.len(), 10);
+  assert_eq!(clone.into_owned(), vec);
+
+  let slice = [1, 2, 3];
+  let cow = MiniCow::from(&slice[1..]);
+  assert_eq!(cow, [2, 3]);
+  assert_eq!(cow.into_owned(), [2, 3]);
+
+  let empty = MiniCow::<i32>::default();
+  assert!(empty.is_owned());
+  assert!(empty.is_empty());
+
+  let empty_vec = MiniVec::new();
+  let empty = MiniCow::<i32>::from(&empty_vec).clone();
+  assert!(empty.is_borrowed());
+  assert!(empty.is_empty());
+
+  let result = catch_unwind(|| MiniCow::from_static(&TABLE)[3]);
+  assert!(result.is_err());
+}
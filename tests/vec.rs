@@ -1,3 +1,4 @@
+#![cfg(not(feature = "no_global_oom_handling"))]
 #![allow(unknown_lints, unused_must_use, clippy::all)]
 #![cfg_attr(
   feature = "minivec_nightly",
@@ -113,10 +114,11 @@ fn test_reserve() {
   assert!(v.capacity() >= 33)
 }
 
-// #[test]
-// fn test_zst_capacity() {
-//   assert_eq!(Vec::<()>::new().capacity(), usize::MAX);
-// }
+#[test]
+fn test_zst_capacity() {
+  assert_eq!(MiniVec::<()>::new().capacity(), usize::MAX);
+  assert_eq!(mini_vec![()].capacity(), usize::MAX);
+}
 
 #[test]
 fn test_indexing() {
@@ -180,14 +182,14 @@ fn test_extend() {
   assert!(v.iter().eq(w.iter().chain(w.iter())));
 
   // Zero sized types
-  // #[derive(PartialEq, Debug)]
-  // struct Foo;
+  #[derive(PartialEq, Debug)]
+  struct Foo;
 
-  // let mut a = Vec::new();
-  // let b = mini_vec![Foo, Foo];
+  let mut a = MiniVec::<Foo>::new();
+  let b = mini_vec![Foo, Foo];
 
-  // a.extend(b);
-  // assert_eq!(a, &[Foo, Foo]);
+  a.extend(b);
+  assert_eq!(a, &[Foo, Foo]);
 
   // Double drop
   let mut count_x = 0;
@@ -456,38 +458,38 @@ fn test_dedup_unique() {
   // and/or rt should raise errors.
 }
 
-// #[test]
-// fn zero_sized_values() {
-//   let mut v = MiniVec::new();
-//   assert_eq!(v.len(), 0);
-//   v.push(());
-//   assert_eq!(v.len(), 1);
-//   v.push(());
-//   assert_eq!(v.len(), 2);
-//   assert_eq!(v.pop(), Some(()));
-//   assert_eq!(v.pop(), Some(()));
-//   assert_eq!(v.pop(), None);
-
-//   assert_eq!(v.iter().count(), 0);
-//   v.push(());
-//   assert_eq!(v.iter().count(), 1);
-//   v.push(());
-//   assert_eq!(v.iter().count(), 2);
-
-//   for &() in &v {}
-
-//   assert_eq!(v.iter_mut().count(), 2);
-//   v.push(());
-//   assert_eq!(v.iter_mut().count(), 3);
-//   v.push(());
-//   assert_eq!(v.iter_mut().count(), 4);
-
-//   for &mut () in &mut v {}
-//   unsafe {
-//     v.set_len(0);
-//   }
-//   assert_eq!(v.iter_mut().count(), 0);
-// }
+#[test]
+fn zero_sized_values() {
+  let mut v = MiniVec::<()>::new();
+  assert_eq!(v.len(), 0);
+  v.push(());
+  assert_eq!(v.len(), 1);
+  v.push(());
+  assert_eq!(v.len(), 2);
+  assert_eq!(v.pop(), Some(()));
+  assert_eq!(v.pop(), Some(()));
+  assert_eq!(v.pop(), None);
+
+  assert_eq!(v.iter().count(), 0);
+  v.push(());
+  assert_eq!(v.iter().count(), 1);
+  v.push(());
+  assert_eq!(v.iter().count(), 2);
+
+  for &() in &v {}
+
+  assert_eq!(v.iter_mut().count(), 2);
+  v.push(());
+  assert_eq!(v.iter_mut().count(), 3);
+  v.push(());
+  assert_eq!(v.iter_mut().count(), 4);
+
+  for &mut () in &mut v {}
+  unsafe {
+    v.set_len(0);
+  }
+  assert_eq!(v.iter_mut().count(), 0);
+}
 
 #[test]
 fn test_partition() {
@@ -546,6 +548,7 @@ fn test_cmp() {
 #[test]
 fn test_vec_truncate_drop() {
   static mut DROPS: u32 = 0;
+  #[allow(dead_code)]
   struct Elem(i32);
   impl Drop for Elem {
     fn drop(&mut self) {
@@ -655,15 +658,15 @@ fn test_move_items_reverse() {
   assert_eq!(vec2, [3, 2, 1]);
 }
 
-// #[test]
-// fn test_move_items_zero_sized() {
-//   let vec = mini_vec![(), (), ()];
-//   let mut vec2 = mini_vec![];
-//   for i in vec {
-//     vec2.push(i);
-//   }
-//   assert_eq!(vec2, [(), (), ()]);
-// }
+#[test]
+fn test_move_items_zero_sized() {
+  let vec = mini_vec![(), (), ()];
+  let mut vec2 = mini_vec![];
+  for i in vec {
+    vec2.push(i);
+  }
+  assert_eq!(vec2, [(), (), ()]);
+}
 
 #[test]
 fn test_drain_empty_vec() {
@@ -698,16 +701,16 @@ fn test_drain_items_reverse() {
   assert_eq!(vec2, [3, 2, 1]);
 }
 
-// #[test]
-// fn test_drain_items_zero_sized() {
-//   let mut vec = mini_vec![(), (), ()];
-//   let mut vec2 = mini_vec![];
-//   for i in vec.drain(..) {
-//     vec2.push(i);
-//   }
-//   assert_eq!(vec, []);
-//   assert_eq!(vec2, [(), (), ()]);
-// }
+#[test]
+fn test_drain_items_zero_sized() {
+  let mut vec = mini_vec![(), (), ()];
+  let mut vec2 = mini_vec![];
+  for i in vec.drain(..) {
+    vec2.push(i);
+  }
+  assert_eq!(vec, []);
+  assert_eq!(vec2, [(), (), ()]);
+}
 
 #[test]
 #[should_panic]
@@ -730,9 +733,9 @@ fn test_drain_range() {
   for _ in v.drain(1..4).rev() {}
   assert_eq!(v, &[1.to_string(), 5.to_string()]);
 
-  // let mut v: MiniVec<_> = mini_vec![(); 5];
-  // for _ in v.drain(1..4).rev() {}
-  // assert_eq!(v, &[(), ()]);
+  let mut v: MiniVec<_> = mini_vec![(); 5];
+  for _ in v.drain(1..4).rev() {}
+  assert_eq!(v, &[(), ()]);
 }
 
 #[test]
@@ -760,14 +763,14 @@ fn test_drain_inclusive_range() {
 
 #[test]
 fn test_drain_max_vec_size() {
-  let mut v = Vec::<()>::with_capacity(usize::MAX);
+  let mut v = MiniVec::<()>::with_capacity(usize::MAX);
   unsafe {
     v.set_len(usize::MAX);
   }
   for _ in v.drain(usize::MAX - 1..) {}
   assert_eq!(v.len(), usize::MAX - 1);
 
-  let mut v = Vec::<()>::with_capacity(usize::MAX);
+  let mut v = MiniVec::<()>::with_capacity(usize::MAX);
   unsafe {
     v.set_len(usize::MAX);
   }
@@ -778,7 +781,7 @@ fn test_drain_max_vec_size() {
 #[test]
 #[should_panic]
 fn test_drain_index_overflow() {
-  let mut v = Vec::<()>::with_capacity(usize::MAX);
+  let mut v = MiniVec::<()>::with_capacity(usize::MAX);
   unsafe {
     v.set_len(usize::MAX);
   }
@@ -882,14 +885,14 @@ fn test_splice_inclusive_out_of_bounds() {
   v.splice(5..=5, a);
 }
 
-// #[test]
-// fn test_splice_items_zero_sized() {
-//   let mut vec = mini_vec![(), (), ()];
-//   let vec2 = mini_vec![];
-//   let t: MiniVec<_> = vec.splice(1..2, vec2.iter().cloned()).collect();
-//   assert_eq!(vec, &[(), ()]);
-//   assert_eq!(t, &[()]);
-// }
+#[test]
+fn test_splice_items_zero_sized() {
+  let mut vec = mini_vec![(), (), ()];
+  let vec2 = mini_vec![];
+  let t: MiniVec<_> = vec.splice(1..2, vec2.iter().cloned()).collect();
+  assert_eq!(vec, &[(), ()]);
+  assert_eq!(t, &[()]);
+}
 
 #[test]
 fn test_splice_unbounded() {
@@ -1290,27 +1293,27 @@ fn drain_filter_empty() {
   assert_eq!(vec, mini_vec![]);
 }
 
-// #[test]
-// fn drain_filter_zst() {
-//   let mut vec = mini_vec![(), (), (), (), ()];
-//   let initial_len = vec.len();
-//   let mut count = 0;
-//   {
-//     let mut iter = vec.drain_filter(|_| true);
-//     assert_eq!(iter.size_hint(), (0, Some(initial_len)));
-//     while let Some(_) = iter.next() {
-//       count += 1;
-//       assert_eq!(iter.size_hint(), (0, Some(initial_len - count)));
-//     }
-//     assert_eq!(iter.size_hint(), (0, Some(0)));
-//     assert_eq!(iter.next(), None);
-//     assert_eq!(iter.size_hint(), (0, Some(0)));
-//   }
+#[test]
+fn drain_filter_zst() {
+  let mut vec = mini_vec![(), (), (), (), ()];
+  let initial_len = vec.len();
+  let mut count = 0;
+  {
+    let mut iter = vec.drain_filter(|_| true);
+    assert_eq!(iter.size_hint(), (0, Some(initial_len)));
+    while let Some(_) = iter.next() {
+      count += 1;
+      assert_eq!(iter.size_hint(), (0, Some(initial_len - count)));
+    }
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+  }
 
-//   assert_eq!(count, initial_len);
-//   assert_eq!(vec.len(), 0);
-//   assert_eq!(vec, mini_vec![]);
-// }
+  assert_eq!(count, initial_len);
+  assert_eq!(vec.len(), 0);
+  assert_eq!(vec, mini_vec![]);
+}
 
 #[test]
 fn drain_filter_false() {
@@ -1952,7 +1955,7 @@ fn vec_macro_repeating_null_raw_fat_pointer() {
 
   let vec = mini_vec![null_raw_dyn; 1];
   dbg!(ptr_metadata(vec[0]));
-  assert!(vec[0] == null_raw_dyn);
+  assert!(std::ptr::eq(vec[0], null_raw_dyn));
 
   // Polyfill for https://github.com/rust-lang/rfcs/pull/2580
 
@@ -2226,7 +2229,7 @@ fn test_zero_sized_vec_push() {
   const N: usize = 8;
 
   for len in 0..N {
-    let mut tester = Vec::with_capacity(len);
+    let mut tester = MiniVec::<()>::with_capacity(len);
     assert_eq!(tester.len(), 0);
     assert!(tester.capacity() >= len);
     for _ in 0..len {
@@ -2318,13 +2321,13 @@ fn test_extend_from_within_out_of_rande() {
   v.extend_from_within(..3);
 }
 
-// #[test]
-// fn test_extend_from_within_zst() {
-//   let mut v = mini_vec![(); 8];
-//   v.extend_from_within(3..7);
+#[test]
+fn test_extend_from_within_zst() {
+  let mut v = mini_vec![(); 8];
+  v.extend_from_within(3..7);
 
-//   assert_eq!(v, [(); 12]);
-// }
+  assert_eq!(v, [(); 12]);
+}
 
 #[test]
 fn test_extend_from_within_empty_vec() {
@@ -2382,6 +2385,7 @@ fn test_vec_dedup_multiple_ident() {
 #[test]
 fn test_vec_dedup_partialeq() {
   #[derive(Debug)]
+  #[allow(dead_code)]
   struct Foo(i32, i32);
 
   impl PartialEq for Foo {